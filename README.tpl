@@ -3,7 +3,7 @@
 
 [![Latest Version](https://img.shields.io/crates/v/uuid.svg)](https://crates.io/crates/uuid)
 [![Join the chat at https://gitter.im/uuid-rs/Lobby](https://badges.gitter.im/Join%20Chat.svg)](https://gitter.im/uuid-rs/Lobby?utm_source=badge&utm_medium=badge&utm_content=badge)
-![Minimum rustc version](https://img.shields.io/badge/rustc-1.31.0+-yellow.svg)
+![Minimum rustc version](https://img.shields.io/badge/rustc-1.81.0+-yellow.svg)
 {{badges}}
 
 ---
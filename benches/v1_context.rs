@@ -0,0 +1,72 @@
+#![cfg(feature = "v1")]
+#![feature(test)]
+extern crate test;
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Barrier,
+    },
+    thread,
+};
+use test::Bencher;
+use uuid::v1::{ClockSequence, Context, ShardedContext};
+
+const THREADS: u16 = 32;
+const PER_THREAD: usize = 1000;
+
+/// Runs `PER_THREAD` sequences from each context on its own thread, once per
+/// iteration.
+///
+/// The threads are spawned before the timed loop and woken through a
+/// barrier, so the iterations measure the contexts rather than thread
+/// startup.
+fn bench_threads<C, I>(b: &mut Bencher, contexts: I)
+where
+    C: ClockSequence + Send,
+    I: IntoIterator<Item = C>,
+{
+    let start = Barrier::new(usize::from(THREADS) + 1);
+    let finish = Barrier::new(usize::from(THREADS) + 1);
+    let done = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        for context in contexts {
+            let (start, finish, done) = (&start, &finish, &done);
+
+            scope.spawn(move || loop {
+                start.wait();
+
+                if done.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                for _ in 0..PER_THREAD {
+                    test::black_box(context.generate_sequence(0, 0));
+                }
+
+                finish.wait();
+            });
+        }
+
+        b.iter(|| {
+            start.wait();
+            finish.wait();
+        });
+
+        done.store(true, Ordering::Relaxed);
+        start.wait();
+    });
+}
+
+#[bench]
+fn bench_shared_context(b: &mut Bencher) {
+    let context = Context::new(0);
+
+    bench_threads(b, (0..THREADS).map(|_| &context));
+}
+
+#[bench]
+fn bench_sharded_context(b: &mut Bencher) {
+    bench_threads(b, ShardedContext::partition(THREADS));
+}
@@ -11,11 +11,16 @@
 
 //! Adapters for various formats for UUIDs
 
+use crate::parser::{self, error::ExpectedLength};
 use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::std::string::String;
 use crate::std::{fmt, str};
 
 #[cfg(feature = "serde")]
 pub mod compact;
+#[cfg(all(feature = "simd", not(feature = "no-unsafe")))]
+mod simd;
 
 /// An adaptor for formatting an [`Uuid`] as a hyphenated string.
 ///
@@ -65,6 +70,24 @@ pub struct Urn(Uuid);
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct UrnRef<'a>(&'a Uuid);
 
+/// An adaptor for formatting an [`Uuid`] as a hyphenated string enclosed
+/// in braces.
+///
+/// Takes an owned instance of the [`Uuid`].
+///
+/// [`Uuid`]: ../struct.Uuid.html
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Braced(Uuid);
+
+/// An adaptor for formatting an [`Uuid`] as a hyphenated string enclosed
+/// in braces.
+///
+/// Takes a reference of the [`Uuid`].
+///
+/// [`Uuid`]: ../struct.Uuid.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BracedRef<'a>(&'a Uuid);
+
 impl Uuid {
     /// Get a [`Hyphenated`] formatter.
     ///
@@ -115,6 +138,22 @@ impl Uuid {
     pub const fn to_urn_ref(&self) -> UrnRef<'_> {
         UrnRef::from_uuid_ref(self)
     }
+
+    /// Get a [`Braced`] formatter.
+    ///
+    /// [`Braced`]: adapter/struct.Braced.html
+    #[inline]
+    pub const fn to_braced(self) -> Braced {
+        Braced::from_uuid(self)
+    }
+
+    /// Get a borrowed [`BracedRef`] formatter.
+    ///
+    /// [`BracedRef`]: adapter/struct.BracedRef.html
+    #[inline]
+    pub const fn to_braced_ref(&self) -> BracedRef<'_> {
+        BracedRef::from_uuid_ref(self)
+    }
 }
 
 const UPPER: [u8; 16] = [
@@ -148,6 +187,31 @@ fn encode<'a>(
 ) -> &'a mut str {
     let len = if hyphens { 36 } else { 32 };
 
+    #[cfg(all(feature = "simd", not(feature = "no-unsafe")))]
+    {
+        if let Some(digits) = simd::encode(uuid.as_bytes(), upper) {
+            let buffer = &mut full_buffer[start..start + len];
+
+            if hyphens {
+                buffer[..8].copy_from_slice(&digits[..8]);
+                buffer[9..13].copy_from_slice(&digits[8..12]);
+                buffer[14..18].copy_from_slice(&digits[12..16]);
+                buffer[19..23].copy_from_slice(&digits[16..20]);
+                buffer[24..].copy_from_slice(&digits[20..]);
+
+                for &i in &HYPHEN_POSITIONS {
+                    buffer[i] = b'-';
+                }
+            } else {
+                buffer.copy_from_slice(&digits);
+            }
+
+            return str::from_utf8_mut(&mut full_buffer[..start + len]).expect(
+                "found non-ASCII output characters while encoding a UUID",
+            );
+        }
+    }
+
     {
         let buffer = &mut full_buffer[start..start + len];
         let bytes = uuid.as_bytes();
@@ -178,6 +242,21 @@ fn encode<'a>(
         .expect("found non-ASCII output characters while encoding a UUID")
 }
 
+/// Encodes the `uuid` with hyphens and enclosed in braces to
+/// buffer[..38], and returns the str sliced from it.
+fn encode_braced<'a>(
+    buffer: &'a mut [u8],
+    uuid: &Uuid,
+    upper: bool,
+) -> &'a mut str {
+    buffer[0] = b'{';
+    encode(buffer, 1, uuid, true, upper);
+    buffer[Braced::LENGTH - 1] = b'}';
+
+    str::from_utf8_mut(&mut buffer[..Braced::LENGTH])
+        .expect("found non-ASCII output characters while encoding a UUID")
+}
+
 impl Hyphenated {
     /// The length of a hyphenated [`Uuid`] string.
     ///
@@ -734,7 +813,7 @@ impl<'a> UrnRef<'a> {
     /// [`Uuid`]: ../struct.Uuid.html
     /// [`UrnRef`]: struct.UrnRef.html
     pub const fn from_uuid_ref(uuid: &'a Uuid) -> Self {
-        UrnRef(&uuid)
+        UrnRef(uuid)
     }
 
     /// Writes the [`Uuid`] as a lower-case URN string to
@@ -837,6 +916,228 @@ impl<'a> UrnRef<'a> {
     }
 }
 
+impl Braced {
+    /// The length of a braced [`Uuid`] string.
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    pub const LENGTH: usize = 38;
+
+    /// Creates a [`Braced`] from a [`Uuid`].
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    /// [`Braced`]: struct.Braced.html
+    pub const fn from_uuid(uuid: Uuid) -> Self {
+        Braced(uuid)
+    }
+
+    /// Writes the [`Uuid`] as a lower-case hyphenated string enclosed in
+    /// braces to `buffer`, and returns the subslice of the buffer that
+    /// contains the encoded UUID.
+    ///
+    /// This is slightly more efficient than using the formatting
+    /// infrastructure as it avoids virtual calls, and may avoid
+    /// double buffering.
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is not large enough: it must have length at least
+    /// [`LENGTH`]. [`Uuid::encode_buffer`] can be used to get a
+    /// sufficiently-large temporary buffer.
+    ///
+    /// [`LENGTH`]: #associatedconstant.LENGTH
+    /// [`Uuid::encode_buffer`]: ../struct.Uuid.html#method.encode_buffer
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01f9abd4d9d80c702af85c822a8").unwrap();
+    ///
+    /// // the encoded portion is returned
+    /// assert_eq!(
+    ///     uuid.to_braced().encode_lower(&mut Uuid::encode_buffer()),
+    ///     "{936da01f-9abd-4d9d-80c7-02af85c822a8}"
+    /// );
+    ///
+    /// // the buffer is mutated directly, and trailing contents remains
+    /// let mut buf = [b'!'; 42];
+    /// assert_eq!(
+    ///     uuid.to_braced().encode_lower(&mut buf),
+    ///     "{936da01f-9abd-4d9d-80c7-02af85c822a8}"
+    /// );
+    /// assert_eq!(
+    ///     &buf as &[_],
+    ///     b"{936da01f-9abd-4d9d-80c7-02af85c822a8}!!!!" as &[_]
+    /// );
+    /// ```
+    /// */
+    pub fn encode_lower<'buf>(&self, buffer: &'buf mut [u8]) -> &'buf mut str {
+        encode_braced(buffer, &self.0, false)
+    }
+
+    /// Writes the [`Uuid`] as an upper-case hyphenated string enclosed in
+    /// braces to `buffer`, and returns the subslice of the buffer that
+    /// contains the encoded UUID.
+    ///
+    /// This is slightly more efficient than using the formatting
+    /// infrastructure as it avoids virtual calls, and may avoid
+    /// double buffering.
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is not large enough: it must have length at least
+    /// [`LENGTH`]. [`Uuid::encode_buffer`] can be used to get a
+    /// sufficiently-large temporary buffer.
+    ///
+    /// [`LENGTH`]: #associatedconstant.LENGTH
+    /// [`Uuid::encode_buffer`]: ../struct.Uuid.html#method.encode_buffer
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936da01f9abd4d9d80c702af85c822a8").unwrap();
+    ///
+    /// // the encoded portion is returned
+    /// assert_eq!(
+    ///     uuid.to_braced().encode_upper(&mut Uuid::encode_buffer()),
+    ///     "{936DA01F-9ABD-4D9D-80C7-02AF85C822A8}"
+    /// );
+    ///
+    /// // the buffer is mutated directly, and trailing contents remains
+    /// let mut buf = [b'!'; 42];
+    /// assert_eq!(
+    ///     uuid.to_braced().encode_upper(&mut buf),
+    ///     "{936DA01F-9ABD-4D9D-80C7-02AF85C822A8}"
+    /// );
+    /// assert_eq!(
+    ///     &buf as &[_],
+    ///     b"{936DA01F-9ABD-4D9D-80C7-02AF85C822A8}!!!!" as &[_]
+    /// );
+    /// ```
+    /// */
+    pub fn encode_upper<'buf>(&self, buffer: &'buf mut [u8]) -> &'buf mut str {
+        encode_braced(buffer, &self.0, true)
+    }
+}
+
+impl<'a> BracedRef<'a> {
+    /// The length of a braced [`Uuid`] string.
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    pub const LENGTH: usize = 38;
+
+    /// Creates a [`BracedRef`] from a [`Uuid`] reference.
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    /// [`BracedRef`]: struct.BracedRef.html
+    pub const fn from_uuid_ref(uuid: &'a Uuid) -> Self {
+        BracedRef(uuid)
+    }
+
+    /// Writes the [`Uuid`] as a lower-case hyphenated string enclosed in
+    /// braces to `buffer`, and returns the subslice of the buffer that
+    /// contains the encoded UUID.
+    ///
+    /// This is slightly more efficient than using the formatting
+    /// infrastructure as it avoids virtual calls, and may avoid
+    /// double buffering.
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is not large enough: it must have length at least
+    /// [`LENGTH`]. [`Uuid::encode_buffer`] can be used to get a
+    /// sufficiently-large temporary buffer.
+    ///
+    /// [`LENGTH`]: #associatedconstant.LENGTH
+    /// [`Uuid::encode_buffer`]: ../struct.Uuid.html#method.encode_buffer
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01f9abd4d9d80c702af85c822a8").unwrap();
+    ///
+    /// // the encoded portion is returned
+    /// assert_eq!(
+    ///     uuid.to_braced_ref().encode_lower(&mut Uuid::encode_buffer()),
+    ///     "{936da01f-9abd-4d9d-80c7-02af85c822a8}"
+    /// );
+    ///
+    /// // the buffer is mutated directly, and trailing contents remains
+    /// let mut buf = [b'!'; 42];
+    /// assert_eq!(
+    ///     uuid.to_braced_ref().encode_lower(&mut buf),
+    ///     "{936da01f-9abd-4d9d-80c7-02af85c822a8}"
+    /// );
+    /// assert_eq!(
+    ///     &buf as &[_],
+    ///     b"{936da01f-9abd-4d9d-80c7-02af85c822a8}!!!!" as &[_]
+    /// );
+    /// ```
+    /// */
+    pub fn encode_lower<'buf>(&self, buffer: &'buf mut [u8]) -> &'buf mut str {
+        encode_braced(buffer, self.0, false)
+    }
+
+    /// Writes the [`Uuid`] as an upper-case hyphenated string enclosed in
+    /// braces to `buffer`, and returns the subslice of the buffer that
+    /// contains the encoded UUID.
+    ///
+    /// This is slightly more efficient than using the formatting
+    /// infrastructure as it avoids virtual calls, and may avoid
+    /// double buffering.
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is not large enough: it must have length at least
+    /// [`LENGTH`]. [`Uuid::encode_buffer`] can be used to get a
+    /// sufficiently-large temporary buffer.
+    ///
+    /// [`LENGTH`]: #associatedconstant.LENGTH
+    /// [`Uuid::encode_buffer`]: ../struct.Uuid.html#method.encode_buffer
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936da01f9abd4d9d80c702af85c822a8").unwrap();
+    ///
+    /// // the encoded portion is returned
+    /// assert_eq!(
+    ///     uuid.to_braced_ref().encode_upper(&mut Uuid::encode_buffer()),
+    ///     "{936DA01F-9ABD-4D9D-80C7-02AF85C822A8}"
+    /// );
+    ///
+    /// // the buffer is mutated directly, and trailing contents remains
+    /// let mut buf = [b'!'; 42];
+    /// assert_eq!(
+    ///     uuid.to_braced_ref().encode_upper(&mut buf),
+    ///     "{936DA01F-9ABD-4D9D-80C7-02AF85C822A8}"
+    /// );
+    /// assert_eq!(
+    ///     &buf as &[_],
+    ///     b"{936DA01F-9ABD-4D9D-80C7-02AF85C822A8}!!!!" as &[_]
+    /// );
+    /// ```
+    /// */
+    pub fn encode_upper<'buf>(&self, buffer: &'buf mut [u8]) -> &'buf mut str {
+        encode_braced(buffer, self.0, true)
+    }
+}
+
 macro_rules! impl_adapter_traits {
     ($($T:ident<$($a:lifetime),*>),+) => {$(
         impl<$($a),*> fmt::Display for $T<$($a),*> {
@@ -849,14 +1150,31 @@ macro_rules! impl_adapter_traits {
         impl<$($a),*> fmt::LowerHex for $T<$($a),*> {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 // TODO: Self doesn't work https://github.com/rust-lang/rust/issues/52808
-                f.write_str(self.encode_lower(&mut [0; $T::LENGTH]))
+                f.pad(self.encode_lower(&mut [0; $T::LENGTH]))
             }
         }
 
         impl<$($a),*> fmt::UpperHex for $T<$($a),*> {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 // TODO: Self doesn't work https://github.com/rust-lang/rust/issues/52808
-                f.write_str(self.encode_upper(&mut [0; $T::LENGTH]))
+                f.pad(self.encode_upper(&mut [0; $T::LENGTH]))
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<$($a),*> $T<$($a),*> {
+            /// Returns the [`Uuid`] encoded in this format with upper-case
+            /// digits.
+            ///
+            /// Unlike upper-casing the result of `to_string`, this only
+            /// allocates once.
+            ///
+            /// Note that usage of this method requires the `std` feature
+            /// of this crate to be enabled.
+            ///
+            /// [`Uuid`]: ../struct.Uuid.html
+            pub fn to_string_upper(&self) -> String {
+                String::from(&*self.encode_upper(&mut [0; $T::LENGTH]))
             }
         }
 
@@ -866,14 +1184,64 @@ macro_rules! impl_adapter_traits {
 
 macro_rules! impl_adapter_from {
     ($T:ident<>) => {
+        impl $T {
+            /// Returns a reference to the underlying [`Uuid`].
+            ///
+            /// [`Uuid`]: ../struct.Uuid.html
+            pub const fn as_uuid(&self) -> &Uuid {
+                &self.0
+            }
+
+            /// Consumes the adapter, returning the underlying [`Uuid`].
+            ///
+            /// [`Uuid`]: ../struct.Uuid.html
+            pub const fn into_uuid(self) -> Uuid {
+                self.0
+            }
+        }
+
         impl From<Uuid> for $T {
             #[inline]
             fn from(f: Uuid) -> Self {
                 $T::from_uuid(f)
             }
         }
+
+        impl From<$T> for Uuid {
+            #[inline]
+            fn from(f: $T) -> Self {
+                f.into_uuid()
+            }
+        }
+
+        impl str::FromStr for $T {
+            type Err = crate::Error;
+
+            /// Parses the string, which must be in exactly this format.
+            /// The hexadecimal digits may be in either case.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                if s.len() != $T::LENGTH {
+                    return Err(parser::Error::InvalidLength {
+                        expected: ExpectedLength::Exact($T::LENGTH),
+                        found: s.len(),
+                    }
+                    .into());
+                }
+
+                Uuid::parse_str(s).map($T)
+            }
+        }
     };
     ($T:ident<$a:lifetime>) => {
+        impl<$a> $T<$a> {
+            /// Returns a reference to the underlying [`Uuid`].
+            ///
+            /// [`Uuid`]: ../struct.Uuid.html
+            pub const fn as_uuid(&self) -> &$a Uuid {
+                self.0
+            }
+        }
+
         impl<$a> From<&$a Uuid> for $T<$a> {
             #[inline]
             fn from(f: &$a Uuid) -> Self {
@@ -889,7 +1257,9 @@ impl_adapter_traits! {
     Simple<>,
     SimpleRef<'a>,
     Urn<>,
-    UrnRef<'a>
+    UrnRef<'a>,
+    Braced<>,
+    BracedRef<'a>
 }
 
 #[cfg(test)]
@@ -944,6 +1314,119 @@ mod tests {
         assert!(buf[len..].iter().all(|x| *x == b'x'));
     }
 
+    #[test]
+    fn braced_trailing() {
+        let mut buf = [b'x'; 100];
+        let len = Uuid::nil().to_braced().encode_lower(&mut buf).len();
+        assert_eq!(len, super::Braced::LENGTH);
+        assert!(buf[len..].iter().all(|x| *x == b'x'));
+    }
+
+    #[test]
+    fn braced_ref_trailing() {
+        let mut buf = [b'x'; 100];
+        let len = Uuid::nil().to_braced_ref().encode_lower(&mut buf).len();
+        assert_eq!(len, super::BracedRef::LENGTH);
+        assert!(buf[len..].iter().all(|x| *x == b'x'));
+    }
+
+    #[test]
+    fn braced_roundtrip() {
+        let uuid = crate::test_util::new();
+        let mut buf = [0; 38];
+        let braced = uuid.to_braced().encode_lower(&mut buf);
+
+        assert_eq!(braced, "{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}");
+        assert_eq!(Uuid::parse_str(braced), Ok(uuid));
+        assert_eq!(
+            Uuid::parse_str(uuid.to_braced_ref().encode_upper(&mut [0; 38])),
+            Ok(uuid)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn to_string_upper() {
+        let uuid = crate::test_util::new();
+
+        assert_eq!(
+            uuid.to_hyphenated().to_string_upper(),
+            "F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4"
+        );
+        assert_eq!(
+            uuid.to_simple_ref().to_string_upper(),
+            "F9168C5ECEB24FAAB6BF329BF39FA1E4"
+        );
+        assert_eq!(
+            uuid.to_urn().to_string_upper(),
+            "urn:uuid:F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4"
+        );
+        assert_eq!(
+            uuid.to_braced_ref().to_string_upper(),
+            "{F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4}"
+        );
+        assert_eq!(
+            uuid.to_braced().to_string_upper(),
+            format!("{:X}", uuid.to_braced())
+        );
+    }
+
+    #[test]
+    fn from_str_exact() {
+        use super::*;
+
+        let uuid = crate::test_util::new();
+
+        assert_eq!(
+            "F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4".parse::<Hyphenated>(),
+            Ok(uuid.to_hyphenated())
+        );
+        assert_eq!(
+            "f9168c5eceb24faab6bf329bf39fa1e4".parse::<Simple>(),
+            Ok(uuid.to_simple())
+        );
+        assert_eq!(
+            "urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4".parse::<Urn>(),
+            Ok(uuid.to_urn())
+        );
+        assert_eq!(
+            "{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}".parse::<Braced>(),
+            Ok(uuid.to_braced())
+        );
+
+        assert!("f9168c5eceb24faab6bf329bf39fa1e4"
+            .parse::<Hyphenated>()
+            .is_err());
+        assert!("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4"
+            .parse::<Simple>()
+            .is_err());
+        assert!("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4"
+            .parse::<Urn>()
+            .is_err());
+        assert!("{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4)"
+            .parse::<Braced>()
+            .is_err());
+        assert_eq!(
+            "f9168c5eceb24faab6bf329bf39fa1e4"
+                .parse::<Braced>()
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidLength {
+                expected: ExpectedLength::Exact(38),
+                found: 32,
+            })
+        );
+    }
+
+    #[test]
+    fn into_uuid() {
+        let uuid = crate::test_util::new();
+
+        assert_eq!(Uuid::from(uuid.to_simple()), uuid);
+        assert_eq!(uuid.to_urn().into_uuid(), uuid);
+        assert_eq!(uuid.to_braced().as_uuid(), &uuid);
+        assert_eq!(uuid.to_hyphenated_ref().as_uuid(), &uuid);
+    }
+
     #[test]
     #[should_panic]
     fn hyphenated_too_small() {
@@ -976,4 +1459,14 @@ mod tests {
     fn urn_ref_too_small() {
         Uuid::nil().to_urn_ref().encode_lower(&mut [0; 44]);
     }
+    #[test]
+    #[should_panic]
+    fn braced_too_small() {
+        Uuid::nil().to_braced().encode_lower(&mut [0; 37]);
+    }
+    #[test]
+    #[should_panic]
+    fn braced_ref_too_small() {
+        Uuid::nil().to_braced_ref().encode_lower(&mut [0; 37]);
+    }
 }
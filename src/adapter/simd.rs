@@ -0,0 +1,263 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Vectorized fast path for formatting.
+
+use crate::prelude::*;
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::std::arch::aarch64::*;
+#[cfg(target_arch = "x86")]
+use crate::std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use crate::std::arch::x86_64::*;
+
+/// A backend for [`encode`], which is only safe to call if the CPU
+/// supports the features it's compiled with.
+///
+/// [`encode`]: fn.encode.html
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+type Encode = unsafe fn(&Bytes, bool) -> Option<[u8; 32]>;
+
+/// Encodes the bytes of a UUID as 32 hexadecimal digits, returning `None`
+/// if there's no vectorized implementation for the CPU.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(super) fn encode(bytes: &Bytes, upper: bool) -> Option<[u8; 32]> {
+    // `backend` only returns backends the CPU supports
+    unsafe { backend()(bytes, upper) }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+pub(super) fn encode(bytes: &Bytes, upper: bool) -> Option<[u8; 32]> {
+    // NEON is part of the aarch64 baseline, so it's enabled at compile time
+    unsafe { encode_neon(bytes, upper) }
+}
+
+#[cfg(not(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_feature = "neon")
+)))]
+pub(super) fn encode(_: &Bytes, _: bool) -> Option<[u8; 32]> {
+    None
+}
+
+/// Picks the fastest backend the CPU supports, detecting the features on
+/// the first call.
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+fn backend() -> Encode {
+    use crate::std::{mem, ptr, sync::atomic};
+
+    static BACKEND: atomic::AtomicPtr<()> =
+        atomic::AtomicPtr::new(ptr::null_mut());
+
+    let backend = BACKEND.load(atomic::Ordering::Relaxed);
+    if !backend.is_null() {
+        // only `Encode` function pointers are stored
+        return unsafe { mem::transmute::<*mut (), Encode>(backend) };
+    }
+
+    let backend: Encode = if is_x86_feature_detected!("avx2") {
+        encode_avx2
+    } else if is_x86_feature_detected!("sse2") {
+        encode_sse2
+    } else {
+        encode_none
+    };
+
+    BACKEND.store(backend as *mut (), atomic::Ordering::Relaxed);
+
+    backend
+}
+
+/// Picks the fastest backend enabled at compile time, since detecting CPU
+/// features at runtime needs `std`.
+#[cfg(all(
+    not(feature = "std"),
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+fn backend() -> Encode {
+    if cfg!(target_feature = "avx2") {
+        encode_avx2
+    } else if cfg!(target_feature = "sse2") {
+        encode_sse2
+    } else {
+        encode_none
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+unsafe fn encode_none(_: &Bytes, _: bool) -> Option<[u8; 32]> {
+    None
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn encode_sse2(bytes: &Bytes, upper: bool) -> Option<[u8; 32]> {
+    let mut digits = [0; 32];
+
+    let input = _mm_loadu_si128(bytes.as_ptr() as *const __m128i);
+    let mask = _mm_set1_epi8(0x0f);
+
+    let high = _mm_and_si128(_mm_srli_epi16(input, 4), mask);
+    let low = _mm_and_si128(input, mask);
+
+    let output = digits.as_mut_ptr() as *mut __m128i;
+
+    _mm_storeu_si128(
+        output,
+        to_ascii_sse2(_mm_unpacklo_epi8(high, low), upper),
+    );
+    _mm_storeu_si128(
+        output.add(1),
+        to_ascii_sse2(_mm_unpackhi_epi8(high, low), upper),
+    );
+
+    Some(digits)
+}
+
+/// Maps each nibble in `0..16` to its hexadecimal digit.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn to_ascii_sse2(nibbles: __m128i, upper: bool) -> __m128i {
+    let is_alpha = _mm_cmpgt_epi8(nibbles, _mm_set1_epi8(9));
+    let offset = _mm_and_si128(is_alpha, _mm_set1_epi8(alpha_offset(upper)));
+
+    _mm_add_epi8(_mm_add_epi8(nibbles, _mm_set1_epi8(b'0' as i8)), offset)
+}
+
+/// Encodes all 16 bytes at once, widening each of them to a 16-bit lane
+/// holding its two digits.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn encode_avx2(bytes: &Bytes, upper: bool) -> Option<[u8; 32]> {
+    let mut digits = [0; 32];
+
+    let input =
+        _mm256_cvtepu8_epi16(_mm_loadu_si128(bytes.as_ptr() as *const __m128i));
+
+    // the high nibble goes in the low byte of each lane, so that it's
+    // written first
+    let high = _mm256_srli_epi16(input, 4);
+    let low =
+        _mm256_slli_epi16(_mm256_and_si256(input, _mm256_set1_epi16(0x0f)), 8);
+    let nibbles = _mm256_or_si256(high, low);
+
+    let is_alpha = _mm256_cmpgt_epi8(nibbles, _mm256_set1_epi8(9));
+    let offset =
+        _mm256_and_si256(is_alpha, _mm256_set1_epi8(alpha_offset(upper)));
+
+    _mm256_storeu_si256(
+        digits.as_mut_ptr() as *mut __m256i,
+        _mm256_add_epi8(
+            _mm256_add_epi8(nibbles, _mm256_set1_epi8(b'0' as i8)),
+            offset,
+        ),
+    );
+
+    Some(digits)
+}
+
+/// Encodes all 16 bytes at once, looking the digits up in a table and
+/// interleaving the high and low ones.
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+#[target_feature(enable = "neon")]
+unsafe fn encode_neon(bytes: &Bytes, upper: bool) -> Option<[u8; 32]> {
+    let mut digits = [0; 32];
+
+    let hex: &[u8; 16] = if upper {
+        b"0123456789ABCDEF"
+    } else {
+        b"0123456789abcdef"
+    };
+    let table = vld1q_u8(hex.as_ptr());
+
+    let input = vld1q_u8(bytes.as_ptr());
+    let high = vqtbl1q_u8(table, vshrq_n_u8(input, 4));
+    let low = vqtbl1q_u8(table, vandq_u8(input, vdupq_n_u8(0x0f)));
+
+    vst1q_u8(digits.as_mut_ptr(), vzip1q_u8(high, low));
+    vst1q_u8(digits.as_mut_ptr().add(16), vzip2q_u8(high, low));
+
+    Some(digits)
+}
+
+/// The distance from `'9' + 1` to `'a'` or `'A'`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn alpha_offset(upper: bool) -> i8 {
+    if upper {
+        (b'A' - b'9' - 1) as i8
+    } else {
+        (b'a' - b'9' - 1) as i8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std::vec::Vec;
+    use crate::test_util;
+
+    #[test]
+    fn test_encode() {
+        for &uuid in &[
+            Uuid::nil(),
+            Uuid::max(),
+            test_util::new(),
+            test_util::new2(),
+        ] {
+            for &upper in &[false, true] {
+                let digits = match encode(uuid.as_bytes(), upper) {
+                    Some(digits) => digits,
+                    None => return,
+                };
+
+                let hex: &[u8; 16] = if upper {
+                    b"0123456789ABCDEF"
+                } else {
+                    b"0123456789abcdef"
+                };
+
+                let mut expected = [0; 32];
+                for (i, &b) in uuid.as_bytes().iter().enumerate() {
+                    expected[2 * i] = hex[(b >> 4) as usize];
+                    expected[2 * i + 1] = hex[(b & 0xf) as usize];
+                }
+
+                assert_eq!(digits, expected);
+            }
+        }
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn test_backends() {
+        let uuid = test_util::new();
+        let lower = *b"f9168c5eceb24faab6bf329bf39fa1e4";
+        let upper = *b"F9168C5ECEB24FAAB6BF329BF39FA1E4";
+
+        let mut backends: Vec<Encode> = vec![];
+
+        if is_x86_feature_detected!("sse2") {
+            backends.push(encode_sse2);
+        }
+        if is_x86_feature_detected!("avx2") {
+            backends.push(encode_avx2);
+        }
+
+        for backend in backends {
+            unsafe {
+                assert_eq!(backend(uuid.as_bytes(), false), Some(lower));
+                assert_eq!(backend(uuid.as_bytes(), true), Some(upper));
+            }
+        }
+    }
+}
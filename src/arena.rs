@@ -0,0 +1,228 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A compact arena of interned [`Uuid`]s.
+//!
+//! Note that you need feature `std` in order to use this module.
+//!
+//! [`Uuid`]: ../struct.Uuid.html
+
+use crate::prelude::*;
+use crate::std::{collections::HashMap, slice, vec::Vec};
+
+/// Assigns dense `u32` handles to interned [`Uuid`]s.
+///
+/// Looking up the [`Uuid`] for a handle and the handle for a [`Uuid`] are
+/// both `O(1)`. Handles are indexes into a contiguous buffer, so they are
+/// always in the range `0..arena.len()`.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::{arena::UuidArena, Uuid};
+///
+/// let mut arena = UuidArena::new();
+///
+/// let a = arena.intern(Uuid::NAMESPACE_DNS);
+/// let b = arena.intern(Uuid::NAMESPACE_URL);
+///
+/// assert_eq!((a, b), (0, 1));
+/// assert_eq!(arena.intern(Uuid::NAMESPACE_DNS), a);
+///
+/// assert_eq!(arena.get(b), Some(Uuid::NAMESPACE_URL));
+/// assert_eq!(arena.handle(&Uuid::NAMESPACE_DNS), Some(a));
+/// ```
+///
+/// [`Uuid`]: ../struct.Uuid.html
+#[derive(Clone, Debug, Default)]
+pub struct UuidArena {
+    uuids: Vec<Uuid>,
+    handles: HashMap<Uuid, u32>,
+}
+
+impl UuidArena {
+    /// Creates an empty arena.
+    pub fn new() -> Self {
+        UuidArena::default()
+    }
+
+    /// Creates an empty arena with space for at least `capacity` UUIDs.
+    pub fn with_capacity(capacity: usize) -> Self {
+        UuidArena {
+            uuids: Vec::with_capacity(capacity),
+            handles: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the handle for `uuid`, interning it first if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena already holds `u32::MAX` UUIDs.
+    pub fn intern(&mut self, uuid: Uuid) -> u32 {
+        if let Some(&handle) = self.handles.get(&uuid) {
+            return handle;
+        }
+
+        let handle = self.uuids.len();
+        assert!(handle < u32::MAX as usize, "UUID arena is full");
+
+        let handle = handle as u32;
+        self.uuids.push(uuid);
+        self.handles.insert(uuid, handle);
+
+        handle
+    }
+
+    /// Returns the handle for `uuid`, if it has been interned.
+    pub fn handle(&self, uuid: &Uuid) -> Option<u32> {
+        self.handles.get(uuid).cloned()
+    }
+
+    /// Returns the UUID for `handle`, if it is in use.
+    pub fn get(&self, handle: u32) -> Option<Uuid> {
+        self.uuids.get(handle as usize).cloned()
+    }
+
+    /// Tests if `uuid` has been interned.
+    pub fn contains(&self, uuid: &Uuid) -> bool {
+        self.handles.contains_key(uuid)
+    }
+
+    /// Removes the UUID for `handle`, returning it.
+    ///
+    /// To keep handles dense, the most recently assigned handle is moved
+    /// into the removed slot, just like `Vec::swap_remove`. Afterwards, the
+    /// UUID that was previously stored under `arena.len()` (if any) is found
+    /// under `handle` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{arena::UuidArena, Uuid};
+    ///
+    /// let mut arena = UuidArena::new();
+    ///
+    /// let a = arena.intern(Uuid::NAMESPACE_DNS);
+    /// let b = arena.intern(Uuid::NAMESPACE_URL);
+    ///
+    /// assert_eq!(arena.swap_remove(a), Some(Uuid::NAMESPACE_DNS));
+    ///
+    /// // `NAMESPACE_URL` moved into the freed handle
+    /// assert_eq!(arena.handle(&Uuid::NAMESPACE_URL), Some(a));
+    /// assert_eq!(arena.get(b), None);
+    /// ```
+    pub fn swap_remove(&mut self, handle: u32) -> Option<Uuid> {
+        let index = handle as usize;
+
+        if index >= self.uuids.len() {
+            return None;
+        }
+
+        let removed = self.uuids.swap_remove(index);
+        self.handles.remove(&removed);
+
+        if let Some(moved) = self.uuids.get(index) {
+            self.handles.insert(*moved, handle);
+        }
+
+        Some(removed)
+    }
+
+    /// Returns the number of interned UUIDs.
+    pub fn len(&self) -> usize {
+        self.uuids.len()
+    }
+
+    /// Tests if the arena is empty.
+    pub fn is_empty(&self) -> bool {
+        self.uuids.is_empty()
+    }
+
+    /// Removes all UUIDs, invalidating every handle.
+    pub fn clear(&mut self) {
+        self.uuids.clear();
+        self.handles.clear();
+    }
+
+    /// Returns the interned UUIDs in handle order.
+    pub fn as_slice(&self) -> &[Uuid] {
+        &self.uuids
+    }
+
+    /// Returns an iterator over the interned UUIDs in handle order.
+    pub fn iter(&self) -> slice::Iter<'_, Uuid> {
+        self.uuids.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a UuidArena {
+    type Item = &'a Uuid;
+    type IntoIter = slice::Iter<'a, Uuid>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_intern() {
+        let mut arena = UuidArena::new();
+
+        assert!(arena.is_empty());
+
+        let a = arena.intern(test_util::new());
+        let b = arena.intern(test_util::new2());
+
+        assert_eq!(a, 0);
+        assert_eq!(b, 1);
+        assert_eq!(arena.intern(test_util::new()), a);
+        assert_eq!(arena.len(), 2);
+
+        assert_eq!(arena.get(a), Some(test_util::new()));
+        assert_eq!(arena.get(b), Some(test_util::new2()));
+        assert_eq!(arena.get(2), None);
+
+        assert_eq!(arena.handle(&test_util::new2()), Some(b));
+        assert_eq!(arena.handle(&Uuid::nil()), None);
+        assert!(arena.contains(&test_util::new()));
+    }
+
+    #[test]
+    fn test_swap_remove() {
+        let mut arena = UuidArena::new();
+
+        let a = arena.intern(test_util::new());
+        let b = arena.intern(test_util::new2());
+        let c = arena.intern(Uuid::nil());
+
+        assert_eq!(arena.swap_remove(a), Some(test_util::new()));
+        assert_eq!(arena.len(), 2);
+        assert!(!arena.contains(&test_util::new()));
+
+        assert_eq!(arena.handle(&Uuid::nil()), Some(a));
+        assert_eq!(arena.handle(&test_util::new2()), Some(b));
+        assert_eq!(arena.get(c), None);
+
+        assert_eq!(arena.swap_remove(b), Some(test_util::new2()));
+        assert_eq!(arena.swap_remove(b), None);
+        assert_eq!(arena.as_slice(), &[Uuid::nil()]);
+
+        arena.clear();
+        assert!(arena.is_empty());
+        assert_eq!(arena.handle(&Uuid::nil()), None);
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::parser::{
+    self,
+    error::{ExpectedLength, UrnPrefix},
+};
+use crate::prelude::*;
+use crate::std::str;
+#[cfg(feature = "std")]
+use crate::std::string::String;
+
+/// Crockford's base32 alphabet, which leaves out `I`, `L`, `O` and `U`.
+const ALPHABET: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// The length of a base32 encoded [`Uuid`].
+///
+/// [`Uuid`]: struct.Uuid.html
+const LENGTH: usize = 26;
+
+/// The characters that can start an encoded `Uuid`, as only the lower three
+/// of the first five bits are used.
+const FIRST_CHARS: &str = "01234567";
+
+impl Uuid {
+    /// Writes the UUID as 26 characters of Crockford's base32 to `buffer`,
+    /// and returns the subslice of the buffer that contains it.
+    ///
+    /// This is the textual form of a [ULID]. The digits are written most
+    /// significant first and in upper case, so sorting the strings sorts
+    /// the UUIDs by their bytes, which for time-ordered UUIDs is the
+    /// order they were generated in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than 26 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(
+    ///     uuid.encode_base32(&mut [0; 26]),
+    ///     "4KDPG1Z6NX9PER1HR2NY2WG8N8"
+    /// );
+    /// ```
+    ///
+    /// [ULID]: https://github.com/ulid/spec
+    pub fn encode_base32<'buf>(&self, buffer: &'buf mut [u8]) -> &'buf mut str {
+        let buffer = &mut buffer[..LENGTH];
+        let alphabet = ALPHABET.as_bytes();
+        let value = self.as_u128();
+
+        // the first character only holds the upper 3 bits
+        for (i, chr) in buffer.iter_mut().enumerate() {
+            *chr = alphabet[(value >> (5 * (LENGTH - 1 - i))) as usize & 0x1f];
+        }
+
+        str::from_utf8_mut(buffer).expect("found non-ASCII base32 output")
+    }
+
+    /// Returns the UUID as 26 characters of Crockford's base32.
+    ///
+    /// See [`Uuid::encode_base32`] for details.
+    ///
+    /// Note that usage of this method requires the `std` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(uuid.to_base32(), "4KDPG1Z6NX9PER1HR2NY2WG8N8");
+    /// ```
+    ///
+    /// [`Uuid::encode_base32`]: #method.encode_base32
+    #[cfg(feature = "std")]
+    pub fn to_base32(&self) -> String {
+        String::from(&*self.encode_base32(&mut [0; LENGTH]))
+    }
+
+    /// Parses a UUID from 26 characters of Crockford's base32, as produced
+    /// by [`Uuid::to_base32`].
+    ///
+    /// Digits are accepted in either case, and `I`, `L` and `O` are read as
+    /// `1`, `1` and `0`, as the encoding's specification asks for.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `input` isn't 26 characters
+    /// long, contains a character outside of the alphabet, or encodes a
+    /// number that doesn't fit into 128 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(Uuid::from_base32("4KDPG1Z6NX9PER1HR2NY2WG8N8"), Ok(uuid));
+    /// assert_eq!(Uuid::from_base32("4kdpglz6nx9perihr2ny2wg8n8"), Ok(uuid));
+    ///
+    /// assert!(Uuid::from_base32("8KDPG1Z6NX9PER1HR2NY2WG8N8").is_err());
+    /// assert!(Uuid::from_base32("4KDPG1Z6NX9PER1HR2NY2WG8NU").is_err());
+    /// ```
+    ///
+    /// [`Uuid::to_base32`]: #method.to_base32
+    pub fn from_base32(input: &str) -> Result<Uuid, crate::Error> {
+        if input.len() != LENGTH {
+            return Err(parser::Error::InvalidLength {
+                expected: ExpectedLength::Exact(LENGTH),
+                found: input.len(),
+            }
+            .into());
+        }
+
+        let mut value = 0u128;
+        for (index, chr) in input.bytes().enumerate() {
+            let digit = match decode(chr) {
+                Some(digit) if index > 0 || digit < 8 => digit,
+                found => {
+                    let expected = if found.is_some() {
+                        FIRST_CHARS
+                    } else {
+                        ALPHABET
+                    };
+
+                    return Err(parser::Error::InvalidCharacter {
+                        expected,
+                        found: input[index..]
+                            .chars()
+                            .next()
+                            .unwrap_or_default(),
+                        index,
+                        urn: UrnPrefix::Optional,
+                    }
+                    .into());
+                }
+            };
+
+            value = value << 5 | u128::from(digit);
+        }
+
+        Ok(Uuid::from_u128(value))
+    }
+}
+
+/// Returns the value of a Crockford base32 character, in either case.
+fn decode(chr: u8) -> Option<u8> {
+    match chr.to_ascii_uppercase() {
+        b'O' => Some(0),
+        b'I' | b'L' => Some(1),
+        b'U' => None,
+        chr => ALPHABET.bytes().position(|c| c == chr).map(|pos| pos as u8),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_base32_roundtrip() {
+        for uuid in &[
+            test_util::new(),
+            test_util::new2(),
+            Uuid::nil(),
+            Uuid::from_u128(u128::MAX),
+        ] {
+            let mut buffer = [0; 26];
+            let encoded = uuid.encode_base32(&mut buffer);
+
+            assert_eq!(Uuid::from_base32(encoded), Ok(*uuid));
+        }
+
+        assert_eq!(
+            Uuid::nil().encode_base32(&mut [0; 26]),
+            "00000000000000000000000000"
+        );
+        assert_eq!(
+            Uuid::from_u128(u128::MAX).encode_base32(&mut [0; 26]),
+            "7ZZZZZZZZZZZZZZZZZZZZZZZZZ"
+        );
+        assert_eq!(
+            Uuid::from_u128(0x20).encode_base32(&mut [0; 26]),
+            "00000000000000000000000010"
+        );
+    }
+
+    #[test]
+    fn test_base32_sorts() {
+        let mut uuids = [
+            test_util::new(),
+            test_util::new2(),
+            Uuid::nil(),
+            Uuid::from_u128(1),
+            Uuid::from_u128(u128::MAX),
+            Uuid::from_u128(1 << 100),
+        ];
+        uuids.sort();
+
+        for pair in uuids.windows(2) {
+            assert!(
+                pair[0].encode_base32(&mut [0; 26])
+                    < pair[1].encode_base32(&mut [0; 26])
+            );
+        }
+    }
+
+    #[test]
+    fn test_base32_invalid() {
+        assert_eq!(
+            Uuid::from_base32("0000").map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidLength {
+                expected: ExpectedLength::Exact(26),
+                found: 4,
+            })
+        );
+        assert_eq!(
+            Uuid::from_base32("8ZZZZZZZZZZZZZZZZZZZZZZZZZ")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidCharacter {
+                expected: FIRST_CHARS,
+                found: '8',
+                index: 0,
+                urn: UrnPrefix::Optional,
+            })
+        );
+        assert_eq!(
+            Uuid::from_base32("0000000000000u000000000000")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidCharacter {
+                expected: ALPHABET,
+                found: 'u',
+                index: 13,
+                urn: UrnPrefix::Optional,
+            })
+        );
+        assert_eq!(
+            Uuid::from_base32("000000000000000000000000\u{e9}")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidCharacter {
+                expected: ALPHABET,
+                found: '\u{e9}',
+                index: 24,
+                urn: UrnPrefix::Optional,
+            })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_to_base32() {
+        let uuid = test_util::new();
+
+        assert_eq!(Uuid::from_base32(&uuid.to_base32()), Ok(uuid));
+        assert_eq!(
+            Uuid::from_base32(&uuid.to_base32().to_lowercase()),
+            Ok(uuid)
+        );
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::builder;
+use crate::parser::{self, error::UrnPrefix};
+use crate::prelude::*;
+use crate::std::str;
+#[cfg(feature = "std")]
+use crate::std::string::String;
+
+/// The Bitcoin base58 alphabet, which leaves out `0`, `O`, `I` and `l`.
+const ALPHABET: &str =
+    "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The maximum length of a base58 encoded [`Uuid`].
+///
+/// [`Uuid`]: struct.Uuid.html
+const MAX_LENGTH: usize = 22;
+
+impl Uuid {
+    /// Writes the UUID as base58 with the Bitcoin alphabet to `buffer`, and
+    /// returns the subslice of the buffer that contains it.
+    ///
+    /// The 16 bytes of the UUID are encoded as a big-endian number, and
+    /// each leading zero byte as a `1`, so the result is the same as
+    /// encoding [`Uuid::as_bytes`] with any other Bitcoin base58 encoder.
+    /// It is at most 22 characters long, and usually exactly 22.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than the encoded UUID. A buffer of 22
+    /// bytes is always large enough.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(
+    ///     uuid.encode_base58(&mut [0; 22]),
+    ///     "KCtqn2CDK64hXvWfYwhRnP"
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::as_bytes`]: #method.as_bytes
+    pub fn encode_base58<'buf>(&self, buffer: &'buf mut [u8]) -> &'buf mut str {
+        let alphabet = ALPHABET.as_bytes();
+
+        let mut digits = [0; MAX_LENGTH];
+        let mut start = MAX_LENGTH;
+        let mut value = self.as_u128();
+
+        while value > 0 {
+            start -= 1;
+            digits[start] = alphabet[(value % 58) as usize];
+            value /= 58;
+        }
+
+        let zeros = self.as_bytes().iter().take_while(|&&b| b == 0).count();
+        let len = zeros + MAX_LENGTH - start;
+        let buffer = &mut buffer[..len];
+
+        for chr in &mut buffer[..zeros] {
+            *chr = alphabet[0];
+        }
+        buffer[zeros..].copy_from_slice(&digits[start..]);
+
+        str::from_utf8_mut(buffer).expect("found non-ASCII base58 output")
+    }
+
+    /// Returns the UUID as base58 with the Bitcoin alphabet.
+    ///
+    /// See [`Uuid::encode_base58`] for details.
+    ///
+    /// Note that usage of this method requires the `std` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(uuid.to_base58(), "KCtqn2CDK64hXvWfYwhRnP");
+    /// ```
+    ///
+    /// [`Uuid::encode_base58`]: #method.encode_base58
+    #[cfg(feature = "std")]
+    pub fn to_base58(&self) -> String {
+        String::from(&*self.encode_base58(&mut [0; MAX_LENGTH]))
+    }
+
+    /// Parses a UUID from base58 with the Bitcoin alphabet, as produced by
+    /// [`Uuid::to_base58`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `input` contains a character
+    /// outside of the base58 alphabet, or if it doesn't decode to exactly
+    /// 16 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(Uuid::from_base58("KCtqn2CDK64hXvWfYwhRnP"), Ok(uuid));
+    ///
+    /// assert!(Uuid::from_base58("KCtqn2CDK64h").is_err());
+    /// assert!(Uuid::from_base58("KCtqn2CDK64hXvWfYwhRn0").is_err());
+    /// ```
+    ///
+    /// [`Uuid::to_base58`]: #method.to_base58
+    pub fn from_base58(input: &str) -> Result<Uuid, crate::Error> {
+        let zeros = input.bytes().take_while(|&chr| chr == b'1').count();
+        let mut value = 0u128;
+
+        for (index, chr) in input.char_indices().skip(zeros) {
+            let digit = match ALPHABET.find(chr) {
+                Some(digit) => digit as u128,
+                None => {
+                    return Err(parser::Error::InvalidCharacter {
+                        expected: ALPHABET,
+                        found: chr,
+                        index,
+                        urn: UrnPrefix::Optional,
+                    }
+                    .into())
+                }
+            };
+
+            // anything that doesn't fit into a `u128` is too long
+            value = match value
+                .checked_mul(58)
+                .and_then(|v| v.checked_add(digit))
+            {
+                Some(value) => value,
+                None => return Err(builder::Error::new(16, 17).into()),
+            };
+        }
+
+        let len = zeros + 16 - value.leading_zeros() as usize / 8;
+        if len != 16 {
+            return Err(builder::Error::new(16, len).into());
+        }
+
+        Ok(Uuid::from_u128(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_base58_roundtrip() {
+        for uuid in &[
+            test_util::new(),
+            test_util::new2(),
+            Uuid::nil(),
+            Uuid::from_u128(1),
+            Uuid::from_u128(u128::MAX),
+            Uuid::from_u128(u128::MAX >> 8),
+        ] {
+            let mut buffer = [0; 22];
+            let encoded = uuid.encode_base58(&mut buffer);
+
+            assert!(encoded.len() <= 22);
+            assert_eq!(Uuid::from_base58(encoded), Ok(*uuid));
+        }
+
+        assert_eq!(Uuid::nil().encode_base58(&mut [0; 22]), "1111111111111111");
+        assert_eq!(
+            Uuid::from_u128(57).encode_base58(&mut [0; 22]),
+            "111111111111111z"
+        );
+        assert_eq!(
+            Uuid::from_u128(u128::MAX).encode_base58(&mut [0; 22]),
+            "YcVfxkQb6JRzqk5kF2tNLv"
+        );
+    }
+
+    #[test]
+    fn test_base58_invalid() {
+        assert_eq!(
+            Uuid::from_base58("111111111111111l")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidCharacter {
+                expected: ALPHABET,
+                found: 'l',
+                index: 15,
+                urn: UrnPrefix::Optional,
+            })
+        );
+        assert_eq!(
+            Uuid::from_base58("1111111111111\u{e9}11")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidCharacter {
+                expected: ALPHABET,
+                found: '\u{e9}',
+                index: 13,
+                urn: UrnPrefix::Optional,
+            })
+        );
+
+        // too few or too many leading zeros
+        assert!(Uuid::from_base58("").is_err());
+        assert!(Uuid::from_base58("z").is_err());
+        assert!(Uuid::from_base58("11111111111111111").is_err());
+        assert!(Uuid::from_base58("1YcVfxkQb6JRzqk5kF2tNLv").is_err());
+
+        // doesn't fit in 16 bytes
+        assert!(Uuid::from_base58("YcVfxkQb6JRzqk5kF2tNLw").is_err());
+        assert!(Uuid::from_base58("zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz").is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_to_base58() {
+        let uuid = test_util::new();
+
+        assert_eq!(Uuid::from_base58(&uuid.to_base58()), Ok(uuid));
+    }
+}
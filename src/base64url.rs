@@ -0,0 +1,357 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::parser::{
+    self,
+    error::{ExpectedLength, UrnPrefix},
+};
+use crate::prelude::*;
+use crate::std::str;
+#[cfg(feature = "std")]
+use crate::std::string::String;
+
+/// The URL and filename safe alphabet of RFC 4648.
+const ALPHABET: &str =
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The length of an unpadded base64url encoded [`Uuid`].
+///
+/// [`Uuid`]: struct.Uuid.html
+const LENGTH: usize = 22;
+
+/// The characters that can end an encoded `Uuid`, as only the upper two of
+/// the last six bits are used.
+const LAST_CHARS: &str = "AQgw";
+
+impl Uuid {
+    /// Writes the UUID as 22 characters of unpadded base64url to `buffer`,
+    /// and returns the subslice of the buffer that contains it.
+    ///
+    /// The characters are from the URL and filename safe alphabet of
+    /// [RFC 4648], so the result can be used in URLs as is, and as an XML
+    /// `NCName` as long as it doesn't start with a digit, `-` or `_`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than 22 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(
+    ///     uuid.encode_base64url(&mut [0; 22]),
+    ///     "k22gH5q9TZ2AxwKvhcgiqA"
+    /// );
+    /// ```
+    ///
+    /// [RFC 4648]: https://tools.ietf.org/html/rfc4648#section-5
+    pub fn encode_base64url<'buf>(
+        &self,
+        buffer: &'buf mut [u8],
+    ) -> &'buf mut str {
+        encode(self.as_bytes(), &mut buffer[..LENGTH])
+    }
+
+    /// Returns the UUID as 22 characters of unpadded base64url.
+    ///
+    /// See [`Uuid::encode_base64url`] for details.
+    ///
+    /// Note that usage of this method requires the `std` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(uuid.to_base64url(), "k22gH5q9TZ2AxwKvhcgiqA");
+    /// ```
+    ///
+    /// [`Uuid::encode_base64url`]: #method.encode_base64url
+    #[cfg(feature = "std")]
+    pub fn to_base64url(&self) -> String {
+        String::from(&*self.encode_base64url(&mut [0; LENGTH]))
+    }
+
+    /// Parses a UUID from 22 characters of base64url, as produced by
+    /// [`Uuid::to_base64url`].
+    ///
+    /// Two trailing `=` padding characters are accepted, but not required.
+    /// As the last character only carries two bits, it must be one of
+    /// `A`, `Q`, `g` or `w`, so that every UUID has exactly one encoding.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `input` has the wrong length
+    /// or contains a character outside of the base64url alphabet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(Uuid::from_base64url("k22gH5q9TZ2AxwKvhcgiqA"), Ok(uuid));
+    /// assert_eq!(Uuid::from_base64url("k22gH5q9TZ2AxwKvhcgiqA=="), Ok(uuid));
+    ///
+    /// assert!(Uuid::from_base64url("k22gH5q9TZ2AxwKvhcgiqB").is_err());
+    /// assert!(Uuid::from_base64url("k22gH5q9TZ2AxwKvhcgi+A").is_err());
+    /// ```
+    ///
+    /// [`Uuid::to_base64url`]: #method.to_base64url
+    pub fn from_base64url(input: &str) -> Result<Uuid, crate::Error> {
+        let mut bytes = input.as_bytes();
+
+        if bytes.len() == LENGTH + 2 && bytes.ends_with(b"==") {
+            bytes = &bytes[..LENGTH];
+        }
+
+        if bytes.len() != LENGTH {
+            return Err(parser::Error::InvalidLength {
+                expected: ExpectedLength::Any(&[LENGTH, LENGTH + 2]),
+                found: input.len(),
+            }
+            .into());
+        }
+
+        let mut uuid = [0; 16];
+
+        match decode(bytes, &mut uuid) {
+            Ok(()) => Ok(Uuid::from_bytes(uuid)),
+            Err(DecodeError::InvalidCharacter(index)) => {
+                Err(invalid_character(input, index, ALPHABET))
+            }
+            // the length was checked above, so this is the last character
+            Err(_) => Err(invalid_character(input, LENGTH - 1, LAST_CHARS)),
+        }
+    }
+}
+
+/// An error decoding unpadded base64url.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum DecodeError {
+    /// The input doesn't have the length of an encoding of the output,
+    /// which is given.
+    InvalidLength(usize),
+    /// A character outside the alphabet, at the given position.
+    InvalidCharacter(usize),
+    /// The unused low bits of the last character are set, so the input
+    /// isn't the canonical encoding of any output.
+    NonCanonical,
+}
+
+/// Returns the length of the unpadded base64url encoding of `len` bytes.
+const fn encoded_len(len: usize) -> usize {
+    len / 3 * 4 + [0, 2, 3][len % 3]
+}
+
+/// Writes `input` as unpadded base64url to `buffer`, which must be exactly
+/// as long as the encoding.
+pub(crate) fn encode<'buf>(
+    input: &[u8],
+    buffer: &'buf mut [u8],
+) -> &'buf mut str {
+    let alphabet = ALPHABET.as_bytes();
+    let mut bits = 0u32;
+    let mut len = 0;
+    let mut out = buffer.iter_mut();
+
+    for &byte in input {
+        bits = bits << 8 | u32::from(byte);
+        len += 8;
+
+        while len >= 6 {
+            len -= 6;
+            *out.next().unwrap() = alphabet[(bits >> len) as usize & 0x3f];
+        }
+    }
+
+    // the remaining bits are padded with zeros
+    if len > 0 {
+        *out.next().unwrap() = alphabet[(bits << (6 - len)) as usize & 0x3f];
+    }
+
+    str::from_utf8_mut(buffer).expect("found non-ASCII base64url output")
+}
+
+/// Decodes unpadded base64url from `input`, filling all of `output`.
+pub(crate) fn decode(
+    input: &[u8],
+    output: &mut [u8],
+) -> Result<(), DecodeError> {
+    let expected = encoded_len(output.len());
+
+    if input.len() != expected {
+        return Err(DecodeError::InvalidLength(expected));
+    }
+
+    let mut bits = 0u32;
+    let mut len = 0;
+    let mut out = output.iter_mut();
+
+    for (index, &chr) in input.iter().enumerate() {
+        let value = match chr {
+            b'A'..=b'Z' => chr - b'A',
+            b'a'..=b'z' => chr - b'a' + 26,
+            b'0'..=b'9' => chr - b'0' + 52,
+            b'-' => 62,
+            b'_' => 63,
+            _ => return Err(DecodeError::InvalidCharacter(index)),
+        };
+
+        bits = bits << 6 | u32::from(value);
+        len += 6;
+
+        if len >= 8 {
+            len -= 8;
+
+            if let Some(byte) = out.next() {
+                *byte = (bits >> len) as u8;
+            }
+        }
+    }
+
+    // reject set padding bits, so every output has a single encoding
+    if bits & ((1 << len) - 1) != 0 {
+        return Err(DecodeError::NonCanonical);
+    }
+
+    Ok(())
+}
+
+fn invalid_character(
+    input: &str,
+    index: usize,
+    expected: &'static str,
+) -> crate::Error {
+    parser::Error::InvalidCharacter {
+        expected,
+        found: input[index..].chars().next().unwrap_or_default(),
+        index,
+        urn: UrnPrefix::Optional,
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_base64url_roundtrip() {
+        for uuid in &[
+            test_util::new(),
+            test_util::new2(),
+            Uuid::nil(),
+            Uuid::from_u128(u128::MAX),
+        ] {
+            let mut buffer = [0; 22];
+            let encoded = uuid.encode_base64url(&mut buffer);
+
+            assert!(encoded
+                .bytes()
+                .all(|chr| ALPHABET.as_bytes().contains(&chr)));
+            assert_eq!(Uuid::from_base64url(encoded), Ok(*uuid));
+        }
+
+        assert_eq!(
+            Uuid::nil().encode_base64url(&mut [0; 22]),
+            "AAAAAAAAAAAAAAAAAAAAAA"
+        );
+        assert_eq!(
+            Uuid::from_u128(u128::MAX).encode_base64url(&mut [0; 22]),
+            "_____________________w"
+        );
+    }
+
+    #[test]
+    fn test_base64url_invalid() {
+        assert_eq!(
+            Uuid::from_base64url("AAAA").map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidLength {
+                expected: ExpectedLength::Any(&[22, 24]),
+                found: 4,
+            })
+        );
+        assert_eq!(
+            Uuid::from_base64url("AAAAAAAAAAAAAAAAAAAAA\u{e9}")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidLength {
+                expected: ExpectedLength::Any(&[22, 24]),
+                found: 23,
+            })
+        );
+        assert_eq!(
+            Uuid::from_base64url("AAAAAAAAAAA/AAAAAAAAAA")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidCharacter {
+                expected: ALPHABET,
+                found: '/',
+                index: 11,
+                urn: UrnPrefix::Optional,
+            })
+        );
+        assert_eq!(
+            Uuid::from_base64url("AAAAAAAAAAAAAAAAAAAAAB")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidCharacter {
+                expected: LAST_CHARS,
+                found: 'B',
+                index: 21,
+                urn: UrnPrefix::Optional,
+            })
+        );
+        assert!(Uuid::from_base64url("AAAAAAAAAAAAAAAAAAAAAA=").is_err());
+        assert!(Uuid::from_base64url("AAAAAAAAAAAAAAAAAAAAAA=A").is_err());
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let mut buffer = [0; 8];
+
+        assert_eq!(encode(b"\xfb\xff", &mut buffer[..3]), "-_8");
+        assert_eq!(encode(b"uuid", &mut buffer[..6]), "dXVpZA");
+        assert_eq!(encode(b"", &mut buffer[..0]), "");
+
+        let mut output = [0; 4];
+        assert_eq!(decode(b"dXVpZA", &mut output), Ok(()));
+        assert_eq!(&output, b"uuid");
+
+        assert_eq!(
+            decode(b"dXVpZ", &mut output),
+            Err(DecodeError::InvalidLength(6))
+        );
+        assert_eq!(
+            decode(b"dXV+ZA", &mut output),
+            Err(DecodeError::InvalidCharacter(3))
+        );
+        assert_eq!(
+            decode(b"dXVpZB", &mut output),
+            Err(DecodeError::NonCanonical)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_to_base64url() {
+        let uuid = test_util::new();
+
+        assert_eq!(Uuid::from_base64url(&uuid.to_base64url()), Ok(uuid));
+    }
+}
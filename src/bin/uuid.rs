@@ -0,0 +1,283 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A command line tool to generate and inspect UUIDs.
+//!
+//! Note that you need feature `cli` in order to build this binary.
+
+use std::{
+    env,
+    io::{self, Write},
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use uuid::{
+    v1::{Context, Timestamp},
+    Uuid,
+};
+
+const USAGE: &str = "\
+usage: uuid v4 [--count N] [--format simple|hyphenated|urn|braced]
+       uuid v1 [--node XX:XX:XX:XX:XX:XX] [--count N] [--format ...]
+       uuid inspect <UUID>...
+
+Without --node, v1 UUIDs use a random node ID with the multicast bit set.";
+
+/// The string forms `--format` accepts.
+#[derive(Clone, Copy)]
+enum Format {
+    Simple,
+    Hyphenated,
+    Urn,
+    Braced,
+}
+
+impl Format {
+    fn write(self, uuid: Uuid) -> String {
+        match self {
+            Format::Simple => uuid.to_simple().to_string(),
+            Format::Hyphenated => uuid.to_hyphenated().to_string(),
+            Format::Urn => uuid.to_urn().to_string(),
+            Format::Braced => uuid.to_braced().to_string(),
+        }
+    }
+}
+
+/// Why a command failed.
+#[derive(Debug)]
+enum Error {
+    /// The arguments were invalid, or a UUID couldn't be made.
+    Usage(String),
+    /// Writing the output failed.
+    Io(io::Error),
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Usage(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::Usage(message.to_owned())
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// The options shared by the generating commands.
+struct Options {
+    count: usize,
+    format: Format,
+    node: Option<[u8; 6]>,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+
+    match run(&args, &mut out).and_then(|()| Ok(out.flush()?)) {
+        Ok(()) => (),
+        // the reader went away, such as `head` after enough lines
+        Err(Error::Io(ref err)) if err.kind() == io::ErrorKind::BrokenPipe => {}
+        Err(Error::Io(err)) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+        Err(Error::Usage(message)) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+fn run(args: &[String], out: &mut dyn Write) -> Result<(), Error> {
+    let (command, rest) = match args.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => return Err("missing command".into()),
+    };
+
+    match command {
+        "v4" => {
+            let options = parse_options(rest, false)?;
+
+            for _ in 0..options.count {
+                let uuid = Uuid::new_v4().map_err(|err| err.to_string())?;
+                writeln!(out, "{}", options.format.write(uuid))?;
+            }
+        }
+        "v1" => {
+            let options = parse_options(rest, true)?;
+            let random = Uuid::new_v4().map_err(|err| err.to_string())?;
+            let random = random.as_bytes();
+
+            // without a node ID, use a random one with the multicast bit
+            // set, so it can't clash with a real MAC address
+            let node = options.node.unwrap_or([
+                random[0] | 0x01,
+                random[1],
+                random[2],
+                random[3],
+                random[4],
+                random[5],
+            ]);
+            let context =
+                Context::new(u16::from_be_bytes([random[6], random[7]]));
+
+            for _ in 0..options.count {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_err(|_| "the system clock is before 1970")?;
+                let ts = Timestamp::from_unix(
+                    &context,
+                    now.as_secs(),
+                    now.subsec_nanos(),
+                );
+                let uuid =
+                    Uuid::new_v1(ts, &node).map_err(|err| err.to_string())?;
+
+                writeln!(out, "{}", options.format.write(uuid))?;
+            }
+        }
+        "inspect" => {
+            if rest.is_empty() {
+                return Err("missing UUID to inspect".into());
+            }
+
+            for input in rest {
+                let uuid = Uuid::parse_str(input)
+                    .map_err(|err| format!("{}: {}", input, err))?;
+
+                writeln!(out, "{}: {:#?}", uuid, uuid.describe())?;
+            }
+        }
+        "-h" | "--help" | "help" => writeln!(out, "{}", USAGE)?,
+        _ => return Err(format!("unknown command `{}`", command).into()),
+    }
+
+    Ok(())
+}
+
+fn parse_options(args: &[String], allow_node: bool) -> Result<Options, String> {
+    let mut options = Options {
+        count: 1,
+        format: Format::Hyphenated,
+        node: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for `{}`", arg))
+        };
+
+        match arg.as_str() {
+            "-n" | "--count" => {
+                let value = value()?;
+                options.count = value
+                    .parse()
+                    .map_err(|_| format!("invalid count `{}`", value))?;
+            }
+            "-f" | "--format" => {
+                options.format = match value()?.as_str() {
+                    "simple" => Format::Simple,
+                    "hyphenated" => Format::Hyphenated,
+                    "urn" => Format::Urn,
+                    "braced" => Format::Braced,
+                    other => return Err(format!("unknown format `{}`", other)),
+                };
+            }
+            "--node" if allow_node => {
+                options.node = Some(parse_node(value()?)?)
+            }
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Parses a node ID of 12 hex digits, optionally separated by `:` or `-`.
+fn parse_node(input: &str) -> Result<[u8; 6], String> {
+    let invalid = || format!("invalid node ID `{}`", input);
+
+    let digits: String =
+        input.chars().filter(|&c| c != ':' && c != '-').collect();
+    // checked for hex digits first, so the slicing below is on char
+    // boundaries, and `from_str_radix` doesn't see a sign
+    if digits.len() != 12 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+
+    let mut node = [0; 6];
+    for (i, byte) in node.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16)
+            .map_err(|_| invalid())?;
+    }
+
+    Ok(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_node() {
+        let node = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab];
+
+        assert_eq!(parse_node("01:23:45:67:89:ab"), Ok(node));
+        assert_eq!(parse_node("01-23-45-67-89-AB"), Ok(node));
+        assert_eq!(parse_node("0123456789ab"), Ok(node));
+
+        assert!(parse_node("01:23:45:67:89").is_err());
+        assert!(parse_node("01:23:45:67:89:xy").is_err());
+        assert!(parse_node("€€€€").is_err());
+        assert!(parse_node("+1:23:45:67:89:ab").is_err());
+    }
+
+    #[test]
+    fn test_run_closed_pipe() {
+        struct Closed;
+
+        impl Write for Closed {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let args = ["v4", "--count", "3"].map(String::from);
+
+        match run(&args, &mut Closed) {
+            Err(Error::Io(err)) => {
+                assert_eq!(err.kind(), io::ErrorKind::BrokenPipe)
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let mut out = Vec::new();
+        run(&args, &mut out).unwrap();
+
+        assert_eq!(out.iter().filter(|&&b| b == b'\n').count(), 3);
+    }
+}
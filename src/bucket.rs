@@ -0,0 +1,113 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::prelude::*;
+
+impl Uuid {
+    /// Maps the UUID to one of `n` buckets, uniformly and stably.
+    ///
+    /// Taking a byte of the UUID modulo `n` is biased towards the lower
+    /// buckets, and for time-based UUIDs the bytes aren't uniform to begin
+    /// with. Instead, both 64-bit halves of [`Uuid::as_u64_pair`] are
+    /// mixed with the SplitMix64 finalizer:
+    ///
+    /// ```text
+    /// h = mix(high ^ mix(low))
+    /// bucket = (h * n) >> 64
+    /// ```
+    ///
+    /// where the multiplication is done in 128 bits. The result only
+    /// depends on the value of the UUID, so it's the same on every platform
+    /// and won't change between versions of this crate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(uuid.bucket(16), 1);
+    /// assert_eq!(uuid.bucket(1), 0);
+    /// ```
+    ///
+    /// [`Uuid::as_u64_pair`]: #method.as_u64_pair
+    pub fn bucket(&self, n: u32) -> u32 {
+        assert!(n > 0, "can't map a UUID into zero buckets");
+
+        let (high, low) = self.as_u64_pair();
+        let hash = mix(high ^ mix(low));
+
+        ((u128::from(hash) * u128::from(n)) >> 64) as u32
+    }
+}
+
+/// The finalizer of the SplitMix64 generator, which spreads every input bit
+/// over the whole output.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_bucket_range() {
+        for uuid in &[
+            test_util::new(),
+            test_util::new2(),
+            Uuid::nil(),
+            Uuid::max(),
+        ] {
+            assert_eq!(uuid.bucket(1), 0);
+
+            for &n in &[2, 3, 10, 1000, u32::MAX] {
+                assert!(uuid.bucket(n) < n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bucket_stable() {
+        assert_eq!(test_util::new().bucket(1000), 595);
+        assert_eq!(test_util::new2().bucket(1000), 57);
+        assert_eq!(Uuid::nil().bucket(u32::MAX), 0);
+    }
+
+    #[test]
+    fn test_bucket_uniform() {
+        // sequential UUIDs, like version 1 UUIDs generated in a burst,
+        // only differ in a few bits of the first bytes
+        let mut counts = [0u32; 10];
+        for i in 0..10_000u128 {
+            let uuid = Uuid::from_u128((i << 96) | 0x11e7_8000_0102_0304_0506);
+            counts[uuid.bucket(10) as usize] += 1;
+        }
+
+        for &count in &counts {
+            assert!(count > 900 && count < 1100, "{:?}", counts);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bucket_zero() {
+        test_util::new().bucket(0);
+    }
+}
@@ -12,13 +12,13 @@ pub(crate) struct Error {
 impl Error {
     /// The expected number of bytes.
     #[inline]
-    const fn expected(&self) -> usize {
+    pub(crate) const fn expected(&self) -> usize {
         self.expected
     }
 
     /// The number of bytes found.
     #[inline]
-    const fn found(&self) -> usize {
+    pub(crate) const fn found(&self) -> usize {
         self.found
     }
 
@@ -42,11 +42,4 @@ impl fmt::Display for Error {
     }
 }
 
-#[cfg(feature = "std")]
-mod std_support {
-    use super::*;
-
-    use crate::std::error;
-
-    impl error::Error for Error {}
-}
+impl crate::std::error::Error for Error {}
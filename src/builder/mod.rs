@@ -44,6 +44,45 @@ impl Uuid {
         Uuid::from_bytes([0; 16])
     }
 
+    /// The 'max UUID'.
+    ///
+    /// The max UUID is a special form of UUID that is specified to have all
+    /// 128 bits set to one, as defined in [IETF RFC 9562 Section 5.10][RFC].
+    /// It's greater than every other UUID, so it can be used as an
+    /// exclusive upper bound in range scans.
+    ///
+    /// [RFC]: https://www.rfc-editor.org/rfc/rfc9562.html#section-5.10
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::max();
+    ///
+    /// assert_eq!(
+    ///     uuid.to_hyphenated().to_string(),
+    ///     "ffffffff-ffff-ffff-ffff-ffffffffffff"
+    /// );
+    /// ```
+    ///
+    /// Scanning a range of an ordered collection:
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use uuid::Uuid;
+    ///
+    /// let after = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// let set: BTreeSet<_> = vec![Uuid::nil(), after].into_iter().collect();
+    ///
+    /// assert_eq!(set.range(Uuid::nil()..Uuid::max()).count(), 2);
+    /// ```
+    pub const fn max() -> Self {
+        Uuid::from_bytes([0xFF; 16])
+    }
+
     /// Creates a UUID from four field values in big-endian order.
     ///
     /// # Errors
@@ -159,6 +198,29 @@ impl Uuid {
     }
 
     /// Creates a UUID from a 128bit value in big-endian order.
+    ///
+    /// The most significant byte of `v` becomes the first byte of the UUID,
+    /// so this is the inverse of [`Uuid::as_u128`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let v = 0xa1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8u128;
+    ///
+    /// let uuid = Uuid::from_u128(v);
+    ///
+    /// assert_eq!(
+    ///     uuid.to_hyphenated().to_string(),
+    ///     "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8"
+    /// );
+    /// assert_eq!(uuid.as_u128(), v);
+    /// ```
+    ///
+    /// [`Uuid::as_u128`]: struct.Uuid.html#method.as_u128
     pub const fn from_u128(v: u128) -> Self {
         Uuid::from_bytes([
             (v >> 120) as u8,
@@ -181,6 +243,29 @@ impl Uuid {
     }
 
     /// Creates a UUID from a 128bit value in little-endian order.
+    ///
+    /// The least significant byte of `v` becomes the first byte of the
+    /// UUID, so this is the inverse of [`Uuid::to_u128_le`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let v = 0xd8d7d6d5d4d3d2d1c2c1b2b1a4a3a2a1u128;
+    ///
+    /// let uuid = Uuid::from_u128_le(v);
+    ///
+    /// assert_eq!(
+    ///     uuid.to_hyphenated().to_string(),
+    ///     "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8"
+    /// );
+    /// assert_eq!(uuid.to_u128_le(), v);
+    /// ```
+    ///
+    /// [`Uuid::to_u128_le`]: struct.Uuid.html#method.to_u128_le
     pub const fn from_u128_le(v: u128) -> Self {
         Uuid::from_bytes([
             v as u8,
@@ -202,6 +287,100 @@ impl Uuid {
         ])
     }
 
+    /// Creates a UUID from two 64bit values in big-endian order.
+    ///
+    /// The `high_bits` become the first 8 bytes of the UUID and the
+    /// `low_bits` the last 8 bytes, so this is the inverse of
+    /// [`Uuid::as_u64_pair`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::from_u64_pair(0xa1a2a3a4b1b2c1c2, 0xd1d2d3d4d5d6d7d8);
+    ///
+    /// assert_eq!(
+    ///     uuid.to_hyphenated().to_string(),
+    ///     "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8"
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::as_u64_pair`]: struct.Uuid.html#method.as_u64_pair
+    pub const fn from_u64_pair(high_bits: u64, low_bits: u64) -> Self {
+        Uuid::from_u128(((high_bits as u128) << 64) | low_bits as u128)
+    }
+
+    /// Creates a UUID from four 32bit words in big-endian order.
+    ///
+    /// The first word becomes the first 4 bytes of the UUID, and so on,
+    /// so this is the inverse of [`Uuid::as_u32x4`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::from_u32x4([0xa1a2a3a4, 0xb1b2c1c2, 0xd1d2d3d4, 0xd5d6d7d8]);
+    ///
+    /// assert_eq!(
+    ///     uuid.to_hyphenated().to_string(),
+    ///     "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8"
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::as_u32x4`]: struct.Uuid.html#method.as_u32x4
+    pub const fn from_u32x4(words: [u32; 4]) -> Self {
+        let mut bytes = [0; 16];
+
+        let mut i = 0;
+        while i < 16 {
+            bytes[i] = words[i / 4].to_be_bytes()[i % 4];
+            i += 1;
+        }
+
+        Uuid::from_bytes(bytes)
+    }
+
+    /// Creates a UUID from eight 16bit words in big-endian order.
+    ///
+    /// The first word becomes the first 2 bytes of the UUID, and so on,
+    /// so this is the inverse of [`Uuid::as_u16x8`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::from_u16x8([
+    ///     0xa1a2, 0xa3a4, 0xb1b2, 0xc1c2, 0xd1d2, 0xd3d4, 0xd5d6, 0xd7d8,
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     uuid.to_hyphenated().to_string(),
+    ///     "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8"
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::as_u16x8`]: struct.Uuid.html#method.as_u16x8
+    pub const fn from_u16x8(words: [u16; 8]) -> Self {
+        let mut bytes = [0; 16];
+
+        let mut i = 0;
+        while i < 16 {
+            bytes[i] = words[i / 2].to_be_bytes()[i % 2];
+            i += 1;
+        }
+
+        Uuid::from_bytes(bytes)
+    }
+
     /// Creates a UUID using the supplied big-endian bytes.
     ///
     /// # Errors
@@ -255,6 +434,54 @@ impl Uuid {
     pub const fn from_bytes(bytes: Bytes) -> Uuid {
         Uuid(bytes)
     }
+
+    /// Views the supplied big-endian bytes as a UUID, without copying.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let bytes = [
+    ///     0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00,
+    ///     0xc0, 0x4f, 0xd4, 0x30, 0xc8,
+    /// ];
+    ///
+    /// let uuid: &Uuid = Uuid::from_bytes_ref(&bytes);
+    ///
+    /// assert_eq!(uuid, &Uuid::NAMESPACE_DNS);
+    /// ```
+    #[cfg(not(feature = "no-unsafe"))]
+    pub fn from_bytes_ref(bytes: &Bytes) -> &Uuid {
+        // SAFETY: `Uuid` is `repr(transparent)` over `Bytes`
+        unsafe { &*(bytes as *const Bytes as *const Uuid) }
+    }
+
+    /// Views the supplied big-endian bytes as a mutable UUID, without
+    /// copying.
+    ///
+    /// Changes made through the returned reference are made to `bytes`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let mut bytes = [0xff; 16];
+    ///
+    /// *Uuid::from_bytes_mut(&mut bytes) = Uuid::NAMESPACE_DNS;
+    ///
+    /// assert_eq!(&bytes, Uuid::NAMESPACE_DNS.as_bytes());
+    /// ```
+    #[cfg(not(feature = "no-unsafe"))]
+    pub fn from_bytes_mut(bytes: &mut Bytes) -> &mut Uuid {
+        // SAFETY: `Uuid` is `repr(transparent)` over `Bytes`
+        unsafe { &mut *(bytes as *mut Bytes as *mut Uuid) }
+    }
 }
 
 /// A builder struct for creating a UUID.
@@ -403,8 +630,150 @@ impl Builder {
     }
 
     /// Creates a `Builder` from a big-endian 128bit value.
-    pub fn from_u128(v: u128) -> Self {
-        Builder::from_bytes(*Uuid::from_u128(v).as_bytes())
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let v = 0xa1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8u128;
+    ///
+    /// let uuid = uuid::Builder::from_u128(v).build();
+    ///
+    /// assert_eq!(uuid.as_u128(), v);
+    /// ```
+    pub const fn from_u128(v: u128) -> Self {
+        Builder::from_bytes(v.to_be_bytes())
+    }
+
+    /// Creates a `Builder` from a little-endian 128bit value.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// let v = 0xd8d7d6d5d4d3d2d1c2c1b2b1a4a3a2a1u128;
+    ///
+    /// let uuid = uuid::Builder::from_u128_le(v).build();
+    ///
+    /// assert_eq!(uuid.to_u128_le(), v);
+    /// ```
+    pub const fn from_u128_le(v: u128) -> Self {
+        Builder::from_bytes(v.to_le_bytes())
+    }
+
+    /// Creates a `Builder` for a random (version 4) UUID using the supplied
+    /// random bytes.
+    ///
+    /// The version and variant bits are overwritten, so this can be used
+    /// with bytes from any random number generator.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::{Builder, Variant, Version};
+    ///
+    /// # let rng = || [
+    /// #     70, 235, 208, 238, 14, 109, 67, 201, 185, 13, 204, 195, 90,
+    /// # 145, 63, 62,
+    /// # ];
+    /// let uuid = Builder::from_random_bytes(rng()).build();
+    ///
+    /// assert_eq!(uuid.get_version(), Some(Version::Random));
+    /// assert_eq!(uuid.get_variant(), Some(Variant::RFC4122));
+    /// ```
+    pub fn from_random_bytes(b: Bytes) -> Self {
+        let mut builder = Builder::from_bytes(b);
+
+        builder
+            .set_variant(crate::Variant::RFC4122)
+            .set_version(crate::Version::Random);
+
+        builder
+    }
+
+    /// Creates a `Builder` for a sequential "COMB" GUID, using the given
+    /// Unix timestamp in milliseconds and random bytes.
+    ///
+    /// The lower 48 bits of the timestamp replace the last six bytes of a
+    /// random UUID in big-endian order. SQL Server compares
+    /// `uniqueidentifier` values by those bytes first, so GUIDs created
+    /// later sort after earlier ones there, which keeps clustered indexes
+    /// from fragmenting. The version is still set to random, as with
+    /// NHibernate's `guid.comb`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::{Builder, Version};
+    ///
+    /// # let rng = || [
+    /// #     70, 235, 208, 238, 14, 109, 67, 201, 185, 13, 204, 195, 90,
+    /// # 145, 63, 62,
+    /// # ];
+    /// let uuid = Builder::from_comb_bytes(1_600_000_000_000, rng()).build();
+    ///
+    /// assert_eq!(uuid.get_version(), Some(Version::Random));
+    /// assert_eq!(
+    ///     uuid.to_hyphenated().to_string(),
+    ///     "46ebd0ee-0e6d-43c9-b90d-0174876e8000"
+    /// );
+    /// ```
+    pub fn from_comb_bytes(unix_millis: u64, b: Bytes) -> Self {
+        let mut builder = Builder::from_random_bytes(b);
+
+        builder.0[10..].copy_from_slice(&unix_millis.to_be_bytes()[2..]);
+
+        builder
+    }
+
+    /// Creates a `Builder` for a version 7 UUID, using the given Unix
+    /// timestamp in milliseconds and random bytes.
+    ///
+    /// The lower 48 bits of the timestamp are the first six bytes, in
+    /// big-endian order, and the random bytes follow. The version and
+    /// variant overwrite 6 of the 80 random bits, so any source of random
+    /// bytes can be used, including a seeded one for reproducible UUIDs.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::{Builder, Version};
+    ///
+    /// # let rng = || [70, 235, 208, 238, 14, 109, 67, 201, 185, 13];
+    /// let uuid = Builder::from_unix_millis(1_600_000_000_000, rng()).build();
+    ///
+    /// assert_eq!(uuid.get_version(), Some(Version::SortRand));
+    /// assert_eq!(
+    ///     uuid.to_hyphenated().to_string(),
+    ///     "0174876e-8000-76eb-90ee-0e6d43c9b90d"
+    /// );
+    /// assert_eq!(uuid.describe().unix_timestamp(), Some((1_600_000_000, 0)));
+    /// ```
+    pub const fn from_unix_millis(unix_millis: u64, random: [u8; 10]) -> Self {
+        let millis = unix_millis.to_be_bytes();
+        let mut bytes = [0; 16];
+
+        let mut i = 0;
+        while i < 6 {
+            bytes[i] = millis[i + 2];
+            i += 1;
+        }
+        while i < 16 {
+            bytes[i] = random[i - 6];
+            i += 1;
+        }
+
+        Builder(bytes)
+            .with_variant(crate::Variant::RFC4122)
+            .with_version(crate::Version::SortRand)
     }
 
     /// Creates a `Builder` with an initial [`Uuid::nil`].
@@ -429,6 +798,45 @@ impl Builder {
 
     /// Specifies the variant of the UUID.
     pub fn set_variant(&mut self, v: crate::Variant) -> &mut Self {
+        *self = Builder(self.0).with_variant(v);
+
+        self
+    }
+
+    /// Specifies the version number of the UUID.
+    pub fn set_version(&mut self, v: crate::Version) -> &mut Self {
+        *self = Builder(self.0).with_version(v);
+
+        self
+    }
+
+    /// Specifies the variant of the UUID, in a chain of `const` calls.
+    ///
+    /// This is the same as [`Builder::set_variant`], but takes and returns
+    /// the `Builder` by value, so it can be used to define constants.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::{Builder, Uuid, Variant, Version};
+    ///
+    /// const VENDOR_ID: Uuid = Builder::nil()
+    ///     .with_d1(0x1234_5678)
+    ///     .with_d4([0, 0, 0, 0, 0, 0, 0, 1])
+    ///     .with_variant(Variant::RFC4122)
+    ///     .with_version(Version::Custom)
+    ///     .into_uuid();
+    ///
+    /// assert_eq!(
+    ///     VENDOR_ID.to_hyphenated().to_string(),
+    ///     "12345678-0000-8000-8000-000000000001"
+    /// );
+    /// ```
+    ///
+    /// [`Builder::set_variant`]: #method.set_variant
+    pub const fn with_variant(mut self, v: crate::Variant) -> Self {
         let byte = self.0[8];
 
         self.0[8] = match v {
@@ -441,13 +849,138 @@ impl Builder {
         self
     }
 
-    /// Specifies the version number of the UUID.
-    pub fn set_version(&mut self, v: crate::Version) -> &mut Self {
+    /// Specifies the version number of the UUID, in a chain of `const`
+    /// calls.
+    ///
+    /// This is the same as [`Builder::set_version`], but takes and returns
+    /// the `Builder` by value. See [`Builder::with_variant`] for an
+    /// example.
+    ///
+    /// [`Builder::set_version`]: #method.set_version
+    /// [`Builder::with_variant`]: #method.with_variant
+    pub const fn with_version(mut self, v: crate::Version) -> Self {
         self.0[6] = (self.0[6] & 0x0f) | ((v as u8) << 4);
 
         self
     }
 
+    /// Replaces the first field, the first group of 8 hex digits, with a
+    /// big-endian value.
+    pub const fn with_d1(mut self, d1: u32) -> Self {
+        let d1 = d1.to_be_bytes();
+
+        self.0[0] = d1[0];
+        self.0[1] = d1[1];
+        self.0[2] = d1[2];
+        self.0[3] = d1[3];
+
+        self
+    }
+
+    /// Replaces the second field, the second group of 4 hex digits, with a
+    /// big-endian value.
+    pub const fn with_d2(mut self, d2: u16) -> Self {
+        let d2 = d2.to_be_bytes();
+
+        self.0[4] = d2[0];
+        self.0[5] = d2[1];
+
+        self
+    }
+
+    /// Replaces the third field, the third group of 4 hex digits, with a
+    /// big-endian value.
+    ///
+    /// This field holds the version, so call [`Builder::with_version`]
+    /// afterwards to keep it.
+    ///
+    /// [`Builder::with_version`]: #method.with_version
+    pub const fn with_d3(mut self, d3: u16) -> Self {
+        let d3 = d3.to_be_bytes();
+
+        self.0[6] = d3[0];
+        self.0[7] = d3[1];
+
+        self
+    }
+
+    /// Replaces the last 8 bytes, the last two groups of hex digits.
+    ///
+    /// These bytes hold the variant, so call [`Builder::with_variant`]
+    /// afterwards to keep it.
+    ///
+    /// [`Builder::with_variant`]: #method.with_variant
+    pub const fn with_d4(mut self, d4: [u8; 8]) -> Self {
+        let mut i = 0;
+        while i < 8 {
+            self.0[8 + i] = d4[i];
+            i += 1;
+        }
+
+        self
+    }
+
+    /// Returns the bytes of the UUID being built.
+    pub const fn as_bytes(&self) -> &Bytes {
+        &self.0
+    }
+
+    /// Returns the bytes of the UUID being built for modification in
+    /// place.
+    ///
+    /// This is useful for encoders that fill in the fields one at a time,
+    /// such as when writing a timestamp straight into its bytes.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::{Builder, Variant, Version};
+    ///
+    /// let mut builder = Builder::nil();
+    ///
+    /// builder.as_mut_bytes()[..6].copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+    /// builder
+    ///     .set_variant(Variant::RFC4122)
+    ///     .set_version(Version::SortRand);
+    ///
+    /// assert_eq!(
+    ///     builder.build().to_hyphenated().to_string(),
+    ///     "01020304-0506-7000-8000-000000000000"
+    /// );
+    /// ```
+    pub fn as_mut_bytes(&mut self) -> &mut Bytes {
+        &mut self.0
+    }
+
+    /// Returns the bytes of the UUID being built, consuming the `Builder`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::{Builder, Version};
+    ///
+    /// let bytes = Builder::nil().with_version(Version::Random).into_bytes();
+    ///
+    /// assert_eq!(bytes[6], 0x40);
+    /// ```
+    pub const fn into_bytes(self) -> Bytes {
+        self.0
+    }
+
+    /// Hands over the constructed [`Uuid`], consuming the `Builder`.
+    ///
+    /// Unlike [`Builder::build`], this can be used in `const` contexts.
+    ///
+    /// [`Uuid`]: struct.Uuid.html
+    /// [`Builder::build`]: #method.build
+    pub const fn into_uuid(self) -> Uuid {
+        Uuid::from_bytes(self.0)
+    }
+
     /// Hands over the internal constructed [`Uuid`].
     ///
     /// # Examples
@@ -0,0 +1,269 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! MD5 (RFC 1321) and SHA-1 (FIPS 180-4) as `const fn`s, so name-based
+//! version 3 and 5 UUIDs can be computed at compile time.
+//!
+//! Both hash a namespace followed by a name, without copying them into a
+//! single buffer, which a `const fn` couldn't allocate.
+
+#[cfg(feature = "v3")]
+const MD5_K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a,
+    0xa8304613, 0xfd469501, 0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be,
+    0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821, 0xf61e2562, 0xc040b340,
+    0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8,
+    0x676f02d9, 0x8d2a4c8a, 0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c,
+    0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70, 0x289b7ec6, 0xeaa127fa,
+    0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92,
+    0xffeff47d, 0x85845dd1, 0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1,
+    0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// The rotation of each MD5 step, repeating four times within a round.
+#[cfg(feature = "v3")]
+const MD5_S: [[u32; 4]; 4] = [
+    [7, 12, 17, 22],
+    [5, 9, 14, 20],
+    [4, 11, 16, 23],
+    [6, 10, 15, 21],
+];
+
+#[cfg(feature = "v3")]
+const MD5_H: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+#[cfg(feature = "v5")]
+const SHA1_H: [u32; 5] =
+    [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// Returns the MD5 hash of `prefix` followed by `data`.
+#[cfg(feature = "v3")]
+pub(crate) const fn md5(prefix: &[u8], data: &[u8]) -> [u8; 16] {
+    let mut state = MD5_H;
+
+    let mut block = 0;
+    while block < blocks(prefix, data) {
+        let bytes = padded_block(prefix, data, block, false);
+
+        let mut m = [0u32; 16];
+        let mut i = 0;
+        while i < 16 {
+            m[i] = u32::from_le_bytes([
+                bytes[i * 4],
+                bytes[i * 4 + 1],
+                bytes[i * 4 + 2],
+                bytes[i * 4 + 3],
+            ]);
+            i += 1;
+        }
+
+        let [mut a, mut b, mut c, mut d] = state;
+        let mut i = 0;
+        while i < 64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let f = f.wrapping_add(a).wrapping_add(MD5_K[i]).wrapping_add(m[g]);
+
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(f.rotate_left(MD5_S[i / 16][i % 4]));
+            i += 1;
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        block += 1;
+    }
+
+    let mut digest = [0; 16];
+    let mut i = 0;
+    while i < 16 {
+        digest[i] = state[i / 4].to_le_bytes()[i % 4];
+        i += 1;
+    }
+
+    digest
+}
+
+/// Returns the SHA-1 hash of `prefix` followed by `data`.
+#[cfg(feature = "v5")]
+pub(crate) const fn sha1(prefix: &[u8], data: &[u8]) -> [u8; 20] {
+    let mut state = SHA1_H;
+
+    let mut block = 0;
+    while block < blocks(prefix, data) {
+        let bytes = padded_block(prefix, data, block, true);
+
+        let mut w = [0u32; 80];
+        let mut i = 0;
+        while i < 16 {
+            w[i] = u32::from_be_bytes([
+                bytes[i * 4],
+                bytes[i * 4 + 1],
+                bytes[i * 4 + 2],
+                bytes[i * 4 + 3],
+            ]);
+            i += 1;
+        }
+        while i < 80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+            i += 1;
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        let mut i = 0;
+        while i < 80 {
+            let (f, k) = match i / 20 {
+                0 => ((b & c) | (!b & d), 0x5a827999),
+                1 => (b ^ c ^ d, 0x6ed9eba1),
+                2 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(w[i]);
+
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+            i += 1;
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        state[4] = state[4].wrapping_add(e);
+        block += 1;
+    }
+
+    let mut digest = [0; 20];
+    let mut i = 0;
+    while i < 20 {
+        digest[i] = state[i / 4].to_be_bytes()[i % 4];
+        i += 1;
+    }
+
+    digest
+}
+
+/// Returns the number of 64 byte blocks the padded message takes, which
+/// is the message, a one bit, and the length as 8 bytes.
+const fn blocks(prefix: &[u8], data: &[u8]) -> usize {
+    (prefix.len() + data.len() + 8) / 64 + 1
+}
+
+/// Returns the block at `index` of the padded message, with the length in
+/// bits in either byte order.
+const fn padded_block(
+    prefix: &[u8],
+    data: &[u8],
+    index: usize,
+    big_endian: bool,
+) -> [u8; 64] {
+    let len = prefix.len() + data.len();
+    let bits = (len as u64).wrapping_mul(8);
+    let length = if big_endian {
+        bits.to_be_bytes()
+    } else {
+        bits.to_le_bytes()
+    };
+    let length_at = blocks(prefix, data) * 64 - 8;
+
+    let mut block = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        let pos = index * 64 + i;
+
+        block[i] = if pos < prefix.len() {
+            prefix[pos]
+        } else if pos < len {
+            data[pos - prefix.len()]
+        } else if pos == len {
+            0x80
+        } else if pos >= length_at {
+            length[pos - length_at]
+        } else {
+            0
+        };
+        i += 1;
+    }
+
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std::vec::Vec;
+
+    /// Splits of messages of every length up to a few blocks, covering the
+    /// boundaries where the padding spills into another block.
+    fn messages(mut test: impl FnMut(&[u8], &[u8], &[u8])) {
+        let bytes = (0..200).map(|i| (i * 7) as u8).collect::<Vec<u8>>();
+
+        for len in 0..bytes.len() {
+            let message = &bytes[..len];
+            for &split in &[0, len / 2, len] {
+                test(message, &message[..split], &message[split..]);
+            }
+        }
+    }
+
+    #[cfg(feature = "v3")]
+    #[test]
+    fn test_md5() {
+        assert_eq!(
+            md5(b"", b"The quick brown fox jumps over the lazy dog"),
+            [
+                0x9e, 0x10, 0x7d, 0x9d, 0x37, 0x2b, 0xb6, 0x82, 0x6b, 0xd8,
+                0x1d, 0x35, 0x42, 0xa4, 0x19, 0xd6
+            ]
+        );
+
+        messages(|message, prefix, data| {
+            assert_eq!(md5(prefix, data), md5::compute(message).0);
+        });
+    }
+
+    #[cfg(feature = "v5")]
+    #[test]
+    fn test_sha1() {
+        assert_eq!(
+            sha1(b"", b"The quick brown fox jumps over the lazy dog"),
+            [
+                0x2f, 0xd4, 0xe1, 0xc6, 0x7a, 0x2d, 0x28, 0xfc, 0xed, 0x84,
+                0x9e, 0xe1, 0xbb, 0x76, 0xe7, 0x39, 0x1b, 0x93, 0xeb, 0x12
+            ]
+        );
+
+        messages(|message, prefix, data| {
+            assert_eq!(
+                sha1(prefix, data),
+                ::sha1::Sha1::from(message).digest().bytes()
+            );
+        });
+    }
+}
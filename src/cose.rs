@@ -0,0 +1,155 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::prelude::*;
+use crate::std::str;
+
+/// The CBOR initial byte for a definite-length byte string of 16 bytes
+/// (major type 2, additional information 16).
+const CBOR_BSTR_16: u8 = 0x50;
+
+impl Uuid {
+    /// Returns the UUID encoded as a COSE `kid` value.
+    ///
+    /// The `kid` header parameter of [RFC 8152] is a CBOR byte string, so
+    /// the result is the 16 bytes of the UUID prefixed with the CBOR header
+    /// for a byte string of that length.
+    ///
+    /// Note that usage of this method requires the `cose` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// let kid = uuid.to_cose_kid();
+    ///
+    /// assert_eq!(kid[0], 0x50);
+    /// assert_eq!(&kid[1..], uuid.as_bytes());
+    /// ```
+    ///
+    /// [RFC 8152]: https://tools.ietf.org/html/rfc8152#section-3.1
+    pub fn to_cose_kid(&self) -> [u8; 17] {
+        let mut kid = [0; 17];
+
+        kid[0] = CBOR_BSTR_16;
+        kid[1..].copy_from_slice(self.as_bytes());
+
+        kid
+    }
+
+    /// Creates a UUID from a COSE `kid` value.
+    ///
+    /// Both the raw 16 bytes of the `kid` byte string and the CBOR encoded
+    /// form produced by [`Uuid::to_cose_kid`] are accepted.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `kid` is neither of the
+    /// accepted forms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(Uuid::from_cose_kid(&uuid.to_cose_kid()), Ok(uuid));
+    /// assert_eq!(Uuid::from_cose_kid(uuid.as_bytes()), Ok(uuid));
+    /// ```
+    ///
+    /// [`Uuid::to_cose_kid`]: struct.Uuid.html#method.to_cose_kid
+    pub fn from_cose_kid(kid: &[u8]) -> Result<Uuid, crate::Error> {
+        match kid.split_first() {
+            Some((&CBOR_BSTR_16, bytes)) if bytes.len() == 16 => {
+                Uuid::from_slice(bytes)
+            }
+            _ => Uuid::from_slice(kid),
+        }
+    }
+
+    /// Tests if a COSE `kid` value identifies this UUID.
+    ///
+    /// Besides the binary forms accepted by [`Uuid::from_cose_kid`], key
+    /// stores frequently use the textual form of the UUID as the `kid`.
+    /// Any of the string formats accepted by [`Uuid::parse_str`] will
+    /// match, regardless of the case of the hexadecimal digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert!(uuid.matches_cose_kid(&uuid.to_cose_kid()));
+    /// assert!(uuid.matches_cose_kid(b"936DA01F-9ABD-4D9D-80C7-02AF85C822A8"));
+    /// assert!(uuid.matches_cose_kid(b"936da01f-9abd-4d9d-80c7-02af85c822a8"));
+    /// assert!(!uuid.matches_cose_kid(b"key-1"));
+    /// ```
+    ///
+    /// [`Uuid::from_cose_kid`]: struct.Uuid.html#method.from_cose_kid
+    /// [`Uuid::parse_str`]: struct.Uuid.html#method.parse_str
+    pub fn matches_cose_kid(&self, kid: &[u8]) -> bool {
+        if let Ok(uuid) = Uuid::from_cose_kid(kid) {
+            return uuid == *self;
+        }
+
+        str::from_utf8(kid)
+            .ok()
+            .and_then(|kid| Uuid::parse_str(kid).ok())
+            .map(|uuid| uuid == *self)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_cose_kid_roundtrip() {
+        let uuid = test_util::new();
+        let kid = uuid.to_cose_kid();
+
+        assert_eq!(kid[0], 0x50);
+        assert_eq!(Uuid::from_cose_kid(&kid).unwrap(), uuid);
+        assert_eq!(Uuid::from_cose_kid(uuid.as_bytes()).unwrap(), uuid);
+    }
+
+    #[test]
+    fn test_cose_kid_invalid() {
+        assert!(Uuid::from_cose_kid(&[]).is_err());
+        assert!(Uuid::from_cose_kid(&[0x50, 1, 2, 3]).is_err());
+        assert!(Uuid::from_cose_kid(&[0x51; 17]).is_err());
+    }
+
+    #[test]
+    fn test_matches_cose_kid() {
+        let uuid = test_util::new();
+
+        assert!(uuid.matches_cose_kid(&uuid.to_cose_kid()));
+        assert!(uuid.matches_cose_kid(uuid.as_bytes()));
+        assert!(uuid.matches_cose_kid(b"F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4"));
+        assert!(uuid.matches_cose_kid(b"f9168c5eceb24faab6bf329bf39fa1e4"));
+        assert!(uuid.matches_cose_kid(
+            b"urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4"
+        ));
+
+        assert!(!uuid.matches_cose_kid(&test_util::new2().to_cose_kid()));
+        assert!(!uuid.matches_cose_kid(b"F9168C5E-CEB2-4FAB-B6BF-329BF39FA1E4"));
+        assert!(!uuid.matches_cose_kid(&[0xff; 36]));
+    }
+}
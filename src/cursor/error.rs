@@ -0,0 +1,53 @@
+use crate::std::fmt;
+
+/// An error that can occur while decoding a [`PageToken`].
+///
+/// [`PageToken`]: struct.PageToken.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::enum_variant_names)]
+pub(crate) enum Error {
+    /// Invalid length of the token string.
+    InvalidLength {
+        /// The expected length.
+        expected: usize,
+        /// The length found.
+        found: usize,
+    },
+    /// Invalid character in the token string.
+    InvalidCharacter {
+        /// The invalid character found.
+        found: char,
+        /// The invalid character position.
+        index: usize,
+    },
+    /// The decoded token has an unknown format version or direction.
+    InvalidFormat,
+    /// The signature of the token doesn't match its contents.
+    #[cfg(feature = "cursor-hmac")]
+    InvalidSignature,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::InvalidLength { expected, found } => write!(
+                f,
+                "invalid page token length: expected {}, found {}",
+                expected, found
+            ),
+            Error::InvalidCharacter { found, index } => write!(
+                f,
+                "invalid page token character: expected a base64url \
+                 character, found {} at {}",
+                found, index
+            ),
+            Error::InvalidFormat => write!(f, "invalid page token format"),
+            #[cfg(feature = "cursor-hmac")]
+            Error::InvalidSignature => {
+                write!(f, "invalid page token signature")
+            }
+        }
+    }
+}
+
+impl crate::std::error::Error for Error {}
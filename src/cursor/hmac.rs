@@ -0,0 +1,75 @@
+//! HMAC-SHA1, as defined in [RFC 2104].
+//!
+//! [RFC 2104]: https://tools.ietf.org/html/rfc2104
+
+const BLOCK_LEN: usize = 64;
+
+pub(super) const DIGEST_LEN: usize = 20;
+
+pub(super) fn hmac_sha1(key: &[u8], message: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut block = [0; BLOCK_LEN];
+
+    if key.len() > BLOCK_LEN {
+        let mut hash = sha1::Sha1::new();
+        hash.update(key);
+
+        block[..DIGEST_LEN].copy_from_slice(&hash.digest().bytes());
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = sha1::Sha1::new();
+    inner.update(&xor(&block, 0x36));
+    inner.update(message);
+
+    let mut outer = sha1::Sha1::new();
+    outer.update(&xor(&block, 0x5c));
+    outer.update(&inner.digest().bytes());
+
+    outer.digest().bytes()
+}
+
+/// Compares two digests in constant time.
+pub(super) fn verify(a: &[u8; DIGEST_LEN], b: &[u8]) -> bool {
+    b.len() == DIGEST_LEN
+        && a.iter()
+            .zip(b.iter())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn xor(block: &[u8; BLOCK_LEN], pad: u8) -> [u8; BLOCK_LEN] {
+    let mut padded = *block;
+
+    for byte in padded.iter_mut() {
+        *byte ^= pad;
+    }
+
+    padded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rfc2202_vectors() {
+        assert_eq!(
+            hmac_sha1(&[0x0b; 20], b"Hi There"),
+            [
+                0xb6, 0x17, 0x31, 0x86, 0x55, 0x05, 0x72, 0x64, 0xe2, 0x8b,
+                0xc0, 0xb6, 0xfb, 0x37, 0x8c, 0x8e, 0xf1, 0x46, 0xbe, 0x00,
+            ]
+        );
+        assert_eq!(
+            hmac_sha1(
+                &[0xaa; 80],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            [
+                0xaa, 0x4a, 0xe5, 0xe1, 0x52, 0x72, 0xd0, 0x0e, 0x95, 0x70,
+                0x56, 0x37, 0xce, 0x8a, 0x3b, 0x55, 0xed, 0x40, 0x21, 0x12,
+            ]
+        );
+    }
+}
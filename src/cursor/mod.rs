@@ -0,0 +1,353 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Opaque page tokens for keyset pagination over UUID keys.
+//!
+//! A [`PageToken`] records the UUID of the last row of a page together
+//! with the direction and size of the next page, and encodes them as a
+//! short URL-safe string that can be handed to clients.
+//!
+//! Tokens can also be signed with HMAC-SHA1, so that clients can't forge
+//! tokens that point at arbitrary keys. Signing requires the `cursor-hmac`
+//! feature.
+//!
+//! Note that you need feature `cursor` in order to use this module.
+//!
+//! [`PageToken`]: struct.PageToken.html
+
+pub(crate) mod error;
+#[cfg(feature = "cursor-hmac")]
+mod hmac;
+
+pub(crate) use self::error::Error;
+
+use crate::base64url::{self, DecodeError};
+use crate::prelude::*;
+use crate::std::{fmt, str};
+
+/// The version of the binary token format.
+const FORMAT_VERSION: u8 = 1;
+
+/// The length of the binary token: the format version, the direction, the
+/// limit and the UUID.
+const RAW_LEN: usize = 22;
+
+#[cfg(feature = "cursor-hmac")]
+const SIGNED_RAW_LEN: usize = RAW_LEN + hmac::DIGEST_LEN;
+
+/// The direction to read the next page in.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    /// Read rows with keys after the token's UUID.
+    Forward,
+    /// Read rows with keys before the token's UUID.
+    Backward,
+}
+
+/// A keyset pagination cursor.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::cursor::{Direction, PageToken};
+/// use uuid::Uuid;
+///
+/// let last = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+/// let token = PageToken::new(last, Direction::Forward, 50);
+///
+/// let encoded = token.to_string();
+/// assert_eq!(encoded.len(), PageToken::LENGTH);
+///
+/// assert_eq!(PageToken::decode(&encoded), Ok(token));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PageToken {
+    uuid: Uuid,
+    direction: Direction,
+    limit: u32,
+}
+
+impl PageToken {
+    /// The length of an encoded token.
+    pub const LENGTH: usize = 30;
+
+    /// The length of an encoded, signed token.
+    #[cfg(feature = "cursor-hmac")]
+    pub const SIGNED_LENGTH: usize = 56;
+
+    /// Creates a page token.
+    pub const fn new(uuid: Uuid, direction: Direction, limit: u32) -> Self {
+        PageToken {
+            uuid,
+            direction,
+            limit,
+        }
+    }
+
+    /// The UUID of the row the next page starts from.
+    pub const fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    /// The direction to read the next page in.
+    pub const fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// The maximum number of rows in the next page.
+    pub const fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    /// Writes the token to `buffer` as a base64url string, without padding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is not large enough: it must have length at
+    /// least [`LENGTH`].
+    ///
+    /// [`LENGTH`]: #associatedconstant.LENGTH
+    pub fn encode<'buf>(&self, buffer: &'buf mut [u8]) -> &'buf mut str {
+        base64url::encode(&self.to_raw(), &mut buffer[..Self::LENGTH])
+    }
+
+    /// Decodes a token written by [`PageToken::encode`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `token` isn't a valid,
+    /// unsigned page token.
+    ///
+    /// [`PageToken::encode`]: #method.encode
+    pub fn decode(token: &str) -> Result<PageToken, crate::Error> {
+        let mut raw = [0; RAW_LEN];
+        decode_base64(token, &mut raw)?;
+
+        PageToken::from_raw(&raw)
+    }
+
+    /// Writes the token to `buffer` as a base64url string, followed by an
+    /// HMAC-SHA1 signature of its contents using `key`.
+    ///
+    /// Note that usage of this method requires the `cursor-hmac` feature of
+    /// this crate to be enabled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is not large enough: it must have length at
+    /// least [`SIGNED_LENGTH`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::cursor::{Direction, PageToken};
+    /// use uuid::Uuid;
+    ///
+    /// let key = b"server secret";
+    /// let token = PageToken::new(Uuid::nil(), Direction::Backward, 20);
+    ///
+    /// let mut buffer = [0; PageToken::SIGNED_LENGTH];
+    /// let encoded = token.encode_signed(key, &mut buffer);
+    ///
+    /// assert_eq!(PageToken::decode_signed(encoded, key), Ok(token));
+    /// assert!(PageToken::decode_signed(encoded, b"other secret").is_err());
+    /// ```
+    ///
+    /// [`SIGNED_LENGTH`]: #associatedconstant.SIGNED_LENGTH
+    #[cfg(feature = "cursor-hmac")]
+    pub fn encode_signed<'buf>(
+        &self,
+        key: &[u8],
+        buffer: &'buf mut [u8],
+    ) -> &'buf mut str {
+        let mut raw = [0; SIGNED_RAW_LEN];
+
+        raw[..RAW_LEN].copy_from_slice(&self.to_raw());
+        let signature = hmac::hmac_sha1(key, &raw[..RAW_LEN]);
+        raw[RAW_LEN..].copy_from_slice(&signature);
+
+        base64url::encode(&raw, &mut buffer[..Self::SIGNED_LENGTH])
+    }
+
+    /// Decodes a token written by [`PageToken::encode_signed`], checking
+    /// its signature against `key`.
+    ///
+    /// Note that usage of this method requires the `cursor-hmac` feature of
+    /// this crate to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `token` isn't a valid, signed
+    /// page token, or if it wasn't signed with `key`.
+    ///
+    /// [`PageToken::encode_signed`]: #method.encode_signed
+    #[cfg(feature = "cursor-hmac")]
+    pub fn decode_signed(
+        token: &str,
+        key: &[u8],
+    ) -> Result<PageToken, crate::Error> {
+        let mut raw = [0; SIGNED_RAW_LEN];
+        decode_base64(token, &mut raw)?;
+
+        let signature = hmac::hmac_sha1(key, &raw[..RAW_LEN]);
+        if !hmac::verify(&signature, &raw[RAW_LEN..]) {
+            Err(Error::InvalidSignature)?;
+        }
+
+        PageToken::from_raw(&raw[..RAW_LEN])
+    }
+
+    fn to_raw(self) -> [u8; RAW_LEN] {
+        let mut raw = [0; RAW_LEN];
+
+        raw[0] = FORMAT_VERSION;
+        raw[1] = match self.direction {
+            Direction::Forward => 0,
+            Direction::Backward => 1,
+        };
+        raw[2..6].copy_from_slice(&self.limit.to_be_bytes());
+        raw[6..].copy_from_slice(self.uuid.as_bytes());
+
+        raw
+    }
+
+    fn from_raw(raw: &[u8]) -> Result<PageToken, crate::Error> {
+        let direction = match (raw[0], raw[1]) {
+            (FORMAT_VERSION, 0) => Direction::Forward,
+            (FORMAT_VERSION, 1) => Direction::Backward,
+            _ => Err(Error::InvalidFormat)?,
+        };
+
+        let limit = u32::from_be_bytes([raw[2], raw[3], raw[4], raw[5]]);
+
+        Ok(PageToken {
+            uuid: Uuid::from_slice(&raw[6..])?,
+            direction,
+            limit,
+        })
+    }
+}
+
+impl fmt::Display for PageToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.encode(&mut [0; PageToken::LENGTH]))
+    }
+}
+
+impl str::FromStr for PageToken {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PageToken::decode(s)
+    }
+}
+
+fn decode_base64(input: &str, output: &mut [u8]) -> Result<(), Error> {
+    base64url::decode(input.as_bytes(), output).map_err(|err| match err {
+        DecodeError::InvalidLength(expected) => Error::InvalidLength {
+            expected,
+            found: input.len(),
+        },
+        DecodeError::InvalidCharacter(index) => Error::InvalidCharacter {
+            found: input[index..].chars().next().unwrap_or_default(),
+            index,
+        },
+        DecodeError::NonCanonical => Error::InvalidFormat,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std::string::ToString;
+    use crate::test_util;
+
+    #[test]
+    fn test_roundtrip() {
+        let token = PageToken::new(test_util::new(), Direction::Backward, 25);
+        let encoded = token.to_string();
+
+        assert_eq!(encoded.len(), PageToken::LENGTH);
+        assert!(encoded
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_'));
+
+        let decoded = encoded.parse::<PageToken>().unwrap();
+
+        assert_eq!(decoded, token);
+        assert_eq!(decoded.uuid(), test_util::new());
+        assert_eq!(decoded.direction(), Direction::Backward);
+        assert_eq!(decoded.limit(), 25);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let token = PageToken::new(Uuid::max(), Direction::Forward, 1);
+        let encoded = token.to_string();
+
+        assert_eq!(
+            PageToken::decode(&encoded[1..]),
+            Err(Error::InvalidLength {
+                expected: PageToken::LENGTH,
+                found: PageToken::LENGTH - 1,
+            }
+            .into())
+        );
+        assert_eq!(
+            PageToken::decode(&encoded.replace('A', "=")),
+            Err(Error::InvalidCharacter {
+                found: '=',
+                index: 0,
+            }
+            .into())
+        );
+
+        // an unknown format version
+        assert_eq!(
+            PageToken::decode(&encoded.replacen('A', "B", 1)),
+            Err(Error::InvalidFormat.into())
+        );
+
+        // the last character only carries 2 bits of data
+        let mut padded = encoded[..PageToken::LENGTH - 1].to_string();
+        padded.push('_');
+        assert_eq!(
+            PageToken::decode(&padded),
+            Err(Error::InvalidFormat.into())
+        );
+    }
+
+    #[cfg(feature = "cursor-hmac")]
+    #[test]
+    fn test_signed() {
+        let token = PageToken::new(test_util::new(), Direction::Forward, 100);
+
+        let mut buffer = [0; PageToken::SIGNED_LENGTH];
+        let encoded = token.encode_signed(b"key", &mut buffer).to_string();
+
+        assert_eq!(PageToken::decode_signed(&encoded, b"key"), Ok(token));
+        assert_eq!(
+            PageToken::decode_signed(&encoded, b"kex"),
+            Err(Error::InvalidSignature.into())
+        );
+        assert!(PageToken::decode(&encoded).is_err());
+        assert!(PageToken::decode_signed(&token.to_string(), b"key").is_err());
+
+        let mut forged = encoded.into_bytes();
+        forged[10] = if forged[10] == b'A' { b'B' } else { b'A' };
+        let forged = crate::std::string::String::from_utf8(forged).unwrap();
+
+        assert_eq!(
+            PageToken::decode_signed(&forged, b"key"),
+            Err(Error::InvalidSignature.into())
+        );
+    }
+}
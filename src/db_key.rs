@@ -0,0 +1,163 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Key encoding helpers for ordered key-value stores.
+//!
+//! Stores like RocksDB and LMDB compare keys byte-wise, so the key layout
+//! decides both iteration order and locality. [`Uuid::to_db_key`] encodes a
+//! UUID so time-based UUIDs sort by creation time, which keeps recently
+//! written keys close together in an LSM tree or B-tree instead of
+//! scattering them across the whole key space.
+//!
+//! Recommended layouts:
+//!
+//! * Keyed by UUID alone: use the 16 byte key directly.
+//! * Several record types in one keyspace: prefix the key with a fixed
+//!   length tag, such as `[tag] ++ uuid key`, and configure the store's
+//!   prefix extractor with [`prefix`] so each record type can be scanned
+//!   on its own.
+//! * Range scans by time: use time-based UUIDs and bucket keys with
+//!   [`time_prefix`], so a prefix seek only touches keys created around the
+//!   same time.
+//!
+//! Note that you need feature `db-key` in order to use this module.
+//!
+//! [`Uuid::to_db_key`]: ../struct.Uuid.html#method.to_db_key
+//! [`prefix`]: fn.prefix.html
+//! [`time_prefix`]: fn.time_prefix.html
+
+use crate::prelude::*;
+
+/// The length of an encoded key.
+pub const KEY_LEN: usize = 16;
+
+/// The length of the prefix of a key that holds the most significant 48
+/// bits of the timestamp of a time-based UUID.
+///
+/// For version 7 UUIDs this is the Unix timestamp in milliseconds, for
+/// versions 1 and 6 it is the RFC4122 timestamp in units of 2<sup>12</sup>
+/// 100-nanosecond intervals, or roughly 0.4 milliseconds.
+pub const TIME_PREFIX_LEN: usize = 6;
+
+impl Uuid {
+    /// Returns the UUID encoded as a key for an ordered key-value store.
+    ///
+    /// This is the same encoding as [`Uuid::sort_key`], so keys of
+    /// time-based UUIDs sort by creation time.
+    ///
+    /// Note that usage of this method requires the `db-key` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+    /// let key = uuid.to_db_key();
+    ///
+    /// assert_eq!(Uuid::from_db_key(&key), Ok(uuid));
+    /// ```
+    ///
+    /// [`Uuid::sort_key`]: struct.Uuid.html#method.sort_key
+    pub fn to_db_key(&self) -> [u8; KEY_LEN] {
+        self.sort_key()
+    }
+
+    /// Decodes a UUID from a key created with [`Uuid::to_db_key`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `key` is not exactly 16 bytes
+    /// long.
+    ///
+    /// [`Uuid::to_db_key`]: struct.Uuid.html#method.to_db_key
+    pub fn from_db_key(key: &[u8]) -> Result<Uuid, crate::Error> {
+        Uuid::from_slice(key).map(|key| Uuid::from_sort_key(*key.as_bytes()))
+    }
+}
+
+/// Returns the first `len` bytes of `key`, or all of `key` if it is
+/// shorter.
+///
+/// This is suitable as a fixed-length prefix extractor for keys that start
+/// with a tag identifying the record type.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::db_key;
+///
+/// assert_eq!(db_key::prefix(b"user/1234", 5), b"user/");
+/// assert_eq!(db_key::prefix(b"id", 5), b"id");
+/// ```
+pub fn prefix(key: &[u8], len: usize) -> &[u8] {
+    &key[..key.len().min(len)]
+}
+
+/// Returns the timestamp prefix of an encoded key that starts `offset`
+/// bytes into `key`.
+///
+/// Keys of time-based UUIDs created around the same time share this
+/// prefix. Keys that are too short to hold a full prefix are returned
+/// unchanged past `offset`, which keeps the extractor total as required by
+/// most stores.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::{db_key, Uuid};
+///
+/// let a = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+/// let b = Uuid::parse_str("f3b4958d-52a1-11e7-802a-010203040506").unwrap();
+///
+/// assert_eq!(
+///     db_key::time_prefix(&a.to_db_key(), 0),
+///     db_key::time_prefix(&b.to_db_key(), 0),
+/// );
+/// ```
+pub fn time_prefix(key: &[u8], offset: usize) -> &[u8] {
+    prefix(key, offset.saturating_add(TIME_PREFIX_LEN))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_db_key_roundtrip() {
+        let uuid = test_util::new();
+
+        assert_eq!(Uuid::from_db_key(&uuid.to_db_key()).unwrap(), uuid);
+
+        let v1 =
+            Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+
+        assert_ne!(&v1.to_db_key(), v1.as_bytes());
+        assert_eq!(Uuid::from_db_key(&v1.to_db_key()).unwrap(), v1);
+
+        assert!(Uuid::from_db_key(&[0; 15]).is_err());
+        assert!(Uuid::from_db_key(&[0; 17]).is_err());
+    }
+
+    #[test]
+    fn test_prefixes() {
+        let mut key = [0; 17];
+        key[0] = b'u';
+        key[1..].copy_from_slice(&test_util::new().to_db_key());
+
+        assert_eq!(prefix(&key, 1), b"u");
+        assert_eq!(time_prefix(&key, 1), &key[..7]);
+        assert_eq!(time_prefix(&key[..4], 1), &key[..4]);
+        assert_eq!(time_prefix(&key, usize::MAX), &key[..]);
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Short numeric codes derived from [`Uuid`]s.
+//!
+//! [`Uuid`]: ../struct.Uuid.html
+
+use crate::prelude::*;
+use crate::std::{fmt, str};
+
+/// The maximum number of digits in a [`DisplayCode`], including the check
+/// digit.
+///
+/// [`DisplayCode`]: struct.DisplayCode.html
+pub const MAX_LEN: usize = 20;
+
+/// A short numeric code derived from a [`Uuid`], ending in a check digit.
+///
+/// Display codes are meant to be read out loud, such as over the phone
+/// to a support agent, who can then match the code against the UUIDs they
+/// have at hand with [`Uuid::matches_display_code`]. The last digit is a
+/// [Luhn] check digit, so most single digit typos and swapped adjacent
+/// digits can be caught with [`DisplayCode::is_well_formed`] before
+/// looking anything up.
+///
+/// A display code is not unique: with `n` digits there are only
+/// 10<sup>n-1</sup> distinct codes, so a code must only be used to pick
+/// a UUID out of a small set of candidates, such as the orders of a
+/// single customer.
+///
+/// [`Uuid`]: ../struct.Uuid.html
+/// [`Uuid::matches_display_code`]: ../struct.Uuid.html#method.matches_display_code
+/// [`DisplayCode::is_well_formed`]: #method.is_well_formed
+/// [Luhn]: https://en.wikipedia.org/wiki/Luhn_algorithm
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DisplayCode {
+    digits: [u8; MAX_LEN],
+    len: usize,
+}
+
+impl DisplayCode {
+    /// Returns the code as a string of decimal digits.
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.digits[..self.len])
+            .expect("found non-ASCII digits")
+    }
+
+    /// Tests if `code` is a string of at least two decimal digits whose
+    /// last digit is the correct check digit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::display_code::DisplayCode;
+    ///
+    /// assert!(DisplayCode::is_well_formed("79927398713"));
+    /// assert!(!DisplayCode::is_well_formed("79927398710"));
+    /// assert!(!DisplayCode::is_well_formed("79927938713"));
+    /// ```
+    pub fn is_well_formed(code: &str) -> bool {
+        let digits = code.as_bytes();
+
+        digits.len() >= 2
+            && digits.len() <= MAX_LEN
+            && digits.iter().all(u8::is_ascii_digit)
+            && check_digit(&digits[..digits.len() - 1])
+                == digits[digits.len() - 1]
+    }
+}
+
+impl fmt::Display for DisplayCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for DisplayCode {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Uuid {
+    /// Derives a [`DisplayCode`] of `len` digits from the UUID.
+    ///
+    /// The code is a keyed hash of the UUID truncated to `len - 1` decimal
+    /// digits, followed by a check digit. The same UUID, key and length
+    /// always produce the same code. Using an application specific `key`
+    /// keeps codes from being computed by anyone who only knows the UUID,
+    /// but note that the hash isn't meant to resist a determined attacker.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is less than 2 or greater than [`MAX_LEN`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::display_code::DisplayCode;
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// let code = uuid.display_code(b"support", 8);
+    ///
+    /// assert_eq!(code.as_str().len(), 8);
+    /// assert!(DisplayCode::is_well_formed(code.as_str()));
+    /// assert!(uuid.matches_display_code(b"support", code.as_str()));
+    /// ```
+    ///
+    /// [`DisplayCode`]: display_code/struct.DisplayCode.html
+    /// [`MAX_LEN`]: display_code/constant.MAX_LEN.html
+    pub fn display_code(&self, key: &[u8], len: usize) -> DisplayCode {
+        assert!(
+            (2..=MAX_LEN).contains(&len),
+            "display code length must be between 2 and {}",
+            MAX_LEN
+        );
+
+        let mut hash =
+            fnv1a(FNV_OFFSET_BASIS, &(key.len() as u64).to_be_bytes());
+        hash = fnv1a(hash, key);
+        hash = fnv1a(hash, self.as_bytes());
+
+        let mut digits = [0; MAX_LEN];
+
+        for digit in digits[..len - 1].iter_mut().rev() {
+            *digit = b'0' + (hash % 10) as u8;
+            hash /= 10;
+        }
+
+        digits[len - 1] = check_digit(&digits[..len - 1]);
+
+        DisplayCode { digits, len }
+    }
+
+    /// Tests if `code` is the [`DisplayCode`] of the UUID for `key`.
+    ///
+    /// The length of the code is taken from `code` itself.
+    ///
+    /// [`DisplayCode`]: display_code/struct.DisplayCode.html
+    pub fn matches_display_code(&self, key: &[u8], code: &str) -> bool {
+        DisplayCode::is_well_formed(code)
+            && self.display_code(key, code.len()).as_str() == code
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The 64-bit FNV-1a hash, which is fixed so codes stay the same across
+/// releases.
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    hash
+}
+
+/// Computes the Luhn check digit for a string of ASCII digits.
+fn check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &digit)| {
+            let digit = u32::from(digit - b'0');
+
+            if i % 2 == 0 {
+                let doubled = digit * 2;
+                doubled / 10 + doubled % 10
+            } else {
+                digit
+            }
+        })
+        .sum();
+
+    b'0' + ((10 - sum % 10) % 10) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_display_code() {
+        let uuid = test_util::new();
+
+        for len in 2..=MAX_LEN {
+            let code = uuid.display_code(b"key", len);
+
+            assert_eq!(code.as_str().len(), len);
+            assert!(DisplayCode::is_well_formed(code.as_str()));
+            assert!(uuid.matches_display_code(b"key", code.as_str()));
+        }
+
+        let code = uuid.display_code(b"key", 8);
+
+        assert_eq!(code, uuid.display_code(b"key", 8));
+        assert_ne!(code, uuid.display_code(b"other key", 8));
+        assert_ne!(code, test_util::new2().display_code(b"key", 8));
+        assert!(!uuid.matches_display_code(b"other key", code.as_str()));
+    }
+
+    #[test]
+    fn test_display_code_is_stable() {
+        // changing these values changes every code handed out so far
+        assert_eq!(
+            test_util::new().display_code(b"key", 8).as_str(),
+            "39851084"
+        );
+        assert_eq!(
+            Uuid::nil().display_code(b"", 20).as_str(),
+            "93546095686564011577"
+        );
+    }
+
+    #[test]
+    fn test_is_well_formed() {
+        assert!(DisplayCode::is_well_formed("00"));
+        assert!(DisplayCode::is_well_formed("79927398713"));
+
+        assert!(!DisplayCode::is_well_formed(""));
+        assert!(!DisplayCode::is_well_formed("0"));
+        assert!(!DisplayCode::is_well_formed("7992739871a"));
+        assert!(!DisplayCode::is_well_formed("79927398714"));
+        assert!(!DisplayCode::is_well_formed("000000000000000000000"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_display_code_too_long() {
+        test_util::new().display_code(b"key", MAX_LEN + 1);
+    }
+}
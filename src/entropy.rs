@@ -0,0 +1,126 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Custom sources of random bytes for version 4 UUIDs.
+//!
+//! [`Uuid::new_v4`] relies on the [`getrandom`] crate, which doesn't
+//! support bare-metal targets. On those targets, implement
+//! [`EntropySource`] for the hardware random number generator and use
+//! [`Uuid::new_v4_from`] instead. This works without the `v4` feature.
+//!
+//! [`Uuid::new_v4`]: ../struct.Uuid.html#method.new_v4
+//! [`Uuid::new_v4_from`]: ../struct.Uuid.html#method.new_v4_from
+//! [`EntropySource`]: trait.EntropySource.html
+//! [`getrandom`]: https://crates.io/crates/getrandom
+
+use crate::prelude::*;
+
+/// A source of random bytes, such as a hardware TRNG.
+pub trait EntropySource {
+    /// The error returned when random bytes aren't available.
+    type Error;
+
+    /// Fills `dest` with random bytes.
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+impl<S: EntropySource + ?Sized> EntropySource for &mut S {
+    type Error = S::Error;
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Self::Error> {
+        (**self).fill_bytes(dest)
+    }
+}
+
+impl Uuid {
+    /// Creates a random UUID using the supplied source of random bytes.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `source` fails to provide
+    /// random bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::entropy::EntropySource;
+    /// use uuid::{Uuid, Version};
+    ///
+    /// struct Trng;
+    ///
+    /// impl EntropySource for Trng {
+    ///     type Error = ();
+    ///
+    ///     fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ()> {
+    ///         // read from the hardware RNG peripheral here
+    /// #       for b in dest.iter_mut() { *b = 0x42; }
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let uuid = Uuid::new_v4_from(&mut Trng).unwrap();
+    ///
+    /// assert_eq!(uuid.get_version(), Some(Version::Random));
+    /// ```
+    pub fn new_v4_from<S: EntropySource + ?Sized>(
+        source: &mut S,
+    ) -> Result<Uuid, S::Error> {
+        let mut bytes = [0u8; 16];
+        source.fill_bytes(&mut bytes)?;
+
+        Ok(Builder::from_random_bytes(bytes).build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter(u8);
+
+    impl EntropySource for Counter {
+        type Error = ();
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ()> {
+            for byte in dest {
+                *byte = self.0;
+                self.0 = self.0.wrapping_add(1);
+            }
+
+            Ok(())
+        }
+    }
+
+    struct Broken;
+
+    impl EntropySource for Broken {
+        type Error = &'static str;
+
+        fn fill_bytes(&mut self, _: &mut [u8]) -> Result<(), Self::Error> {
+            Err("no entropy")
+        }
+    }
+
+    #[test]
+    fn test_new_v4_from() {
+        let mut source = Counter(0);
+
+        let a = Uuid::new_v4_from(&mut source).unwrap();
+        let b = Uuid::new_v4_from(&mut &mut source).unwrap();
+
+        assert_ne!(a, b);
+        assert_eq!(a.get_version(), Some(Version::Random));
+        assert_eq!(a.get_variant(), Some(Variant::RFC4122));
+        assert_eq!(b.as_bytes()[15], 31);
+
+        assert_eq!(Uuid::new_v4_from(&mut Broken), Err("no entropy"));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::std::fmt;
+use crate::std::{error, fmt};
 use crate::{builder, parser};
 
 /// A general error that can occur when working with UUIDs.
@@ -26,6 +26,36 @@ enum Inner {
     /// [`parser::ParseError`]: parser/enum.ParseError.html
     /// [`Uuid`]: struct.Uuid.html
     Parser(parser::Error),
+
+    /// An error occurred while decoding a [`PageToken`].
+    ///
+    /// [`PageToken`]: cursor/struct.PageToken.html
+    #[cfg(feature = "cursor")]
+    Cursor(crate::cursor::Error),
+
+    /// A number that isn't a known [`Version`].
+    ///
+    /// [`Version`]: enum.Version.html
+    Version(u8),
+
+    /// A number that isn't one of the four [`Variant`]s.
+    ///
+    /// [`Variant`]: enum.Variant.html
+    Variant(u8),
+
+    /// The wrong number of values for the fields of a [`V8Layout`].
+    ///
+    /// [`V8Layout`]: layout/struct.V8Layout.html
+    FieldCount { expected: usize, found: usize },
+
+    /// A value too wide for its field of a [`V8Layout`].
+    ///
+    /// [`V8Layout`]: layout/struct.V8Layout.html
+    FieldOverflow { field: &'static str, bits: u32 },
+
+    /// A timestamp with more than 60 bits of ticks.
+    #[cfg(feature = "v1")]
+    Timestamp(u64),
 }
 
 impl From<builder::Error> for Error {
@@ -40,30 +70,336 @@ impl From<parser::Error> for Error {
     }
 }
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+/// The cause of an [`Error`], with the details that are known about it.
+///
+/// New kinds of errors may be added in the future, so matching on this
+/// needs a wildcard arm.
+///
+/// [`Error`]: struct.Error.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A string contains a character that isn't allowed at its position.
+    InvalidCharacter {
+        /// The invalid character.
+        found: char,
+        /// The byte offset of the invalid character in the string.
+        index: usize,
+    },
+    /// A group of digits in a hyphenated string is followed by a character
+    /// other than a hyphen.
+    InvalidSeparator {
+        /// The character found instead of a hyphen.
+        found: char,
+        /// The byte offset of the character in the string.
+        index: usize,
+    },
+    /// The hyphens in a string don't split the digits into either 1 or 5
+    /// groups.
+    InvalidGroupCount {
+        /// The number of groups found.
+        found: usize,
+        /// The byte offset of the first digit past the expected groups.
+        index: usize,
+    },
+    /// A group between hyphens in a string has the wrong number of digits.
+    InvalidGroupLength {
+        /// The zero-based number of the group.
+        group: usize,
+        /// The number of digits found in the group.
+        found: usize,
+        /// The byte offset of the hyphen or end of input terminating the
+        /// group.
+        index: usize,
+    },
+    /// A string has the wrong overall length.
+    InvalidLength {
+        /// The length found, in bytes.
+        found: usize,
+    },
+    /// A slice doesn't hold exactly as many bytes as expected.
+    InvalidByteLength {
+        /// The expected number of bytes.
+        expected: usize,
+        /// The number of bytes found.
+        found: usize,
+    },
+    /// A number isn't a known [`Version`].
+    ///
+    /// [`Version`]: enum.Version.html
+    InvalidVersion(u8),
+    /// A number isn't one of the four [`Variant`]s.
+    ///
+    /// [`Variant`]: enum.Variant.html
+    InvalidVariant(u8),
+    /// A page token can't be decoded.
+    InvalidPageToken,
+    /// The number of values doesn't match the number of fields of a
+    /// [`V8Layout`].
+    ///
+    /// [`V8Layout`]: layout/struct.V8Layout.html
+    InvalidFieldCount {
+        /// The number of fields.
+        expected: usize,
+        /// The number of values found.
+        found: usize,
+    },
+    /// A value doesn't fit into its field of a [`V8Layout`].
+    ///
+    /// [`V8Layout`]: layout/struct.V8Layout.html
+    FieldOverflow {
+        /// The name of the field.
+        field: &'static str,
+        /// The width of the field in bits.
+        bits: u32,
+    },
+    /// A timestamp doesn't fit into the 60 bits of a time-based UUID.
+    TimestampOutOfRange {
+        /// The number of 100-nanosecond intervals since 15 Oct 1582.
+        ticks: u64,
+    },
+}
+
+impl Error {
+    /// Wraps a [`parser::Error`], for use in `const` contexts where
+    /// `From` isn't available.
+    ///
+    /// [`parser::Error`]: parser/enum.Error.html
+    pub(crate) const fn parser(err: parser::Error) -> Self {
+        Error(Inner::Parser(err))
+    }
+
+    /// Creates an error for a number that isn't a known version.
+    pub(crate) const fn invalid_version(found: u8) -> Self {
+        Error(Inner::Version(found))
+    }
+
+    /// Creates an error for a number that isn't a variant.
+    pub(crate) const fn invalid_variant(found: u8) -> Self {
+        Error(Inner::Variant(found))
+    }
+
+    /// Creates an error for the wrong number of values for a layout.
+    pub(crate) const fn invalid_field_count(
+        expected: usize,
+        found: usize,
+    ) -> Self {
+        Error(Inner::FieldCount { expected, found })
+    }
+
+    /// Creates an error for a value too wide for its field.
+    pub(crate) const fn field_overflow(field: &'static str, bits: u32) -> Self {
+        Error(Inner::FieldOverflow { field, bits })
+    }
+
+    /// Creates an error for a timestamp that doesn't fit into 60 bits.
+    #[cfg(feature = "v1")]
+    pub(crate) const fn timestamp_out_of_range(ticks: u64) -> Self {
+        Error(Inner::Timestamp(ticks))
+    }
+
+    /// Moves the position of a parse error `by` bytes further into the
+    /// input.
+    pub(crate) fn offset(self, by: usize) -> Self {
+        self.map_parser(|err| err.offset(by))
+    }
+
+    /// Transforms a parse error, leaving other errors as they are.
+    pub(crate) fn map_parser(
+        self,
+        f: impl FnOnce(parser::Error) -> parser::Error,
+    ) -> Self {
         match self.0 {
-            Inner::Build(ref err) => fmt::Display::fmt(&err, f),
-            Inner::Parser(ref err) => fmt::Display::fmt(&err, f),
+            Inner::Parser(err) => Error(Inner::Parser(f(err))),
+            _ => self,
+        }
+    }
+
+    /// Returns the cause of the error.
+    ///
+    /// This is a `const fn`, so the position of an error in a string parsed
+    /// by [`Uuid::try_parse`] is available at compile time too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{ErrorKind, Uuid};
+    ///
+    /// let err = Uuid::parse_str("936DA01F-9ABD-4D9D-80C7-02AF85C822AX").unwrap_err();
+    ///
+    /// match err.kind() {
+    ///     ErrorKind::InvalidCharacter { found, index } => {
+    ///         assert_eq!((found, index), ('X', 35));
+    ///     }
+    ///     _ => panic!("unexpected error: {}", err),
+    /// }
+    ///
+    /// let err = Uuid::from_slice(&[0; 4]).unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     err.kind(),
+    ///     ErrorKind::InvalidByteLength { expected: 16, found: 4 }
+    /// );
+    ///
+    /// const INDEX: usize = match Uuid::try_parse("936DA01F-9ABD-4D9D-80C7-02AF85C822AX") {
+    ///     Err(err) => match err.kind() {
+    ///         ErrorKind::InvalidCharacter { index, .. } => index,
+    ///         _ => panic!("unexpected error"),
+    ///     },
+    ///     Ok(_) => panic!("unexpected UUID"),
+    /// };
+    ///
+    /// assert_eq!(INDEX, 35);
+    /// ```
+    ///
+    /// [`Uuid::try_parse`]: struct.Uuid.html#method.try_parse
+    pub const fn kind(&self) -> ErrorKind {
+        match self.0 {
+            Inner::Build(ref err) => ErrorKind::InvalidByteLength {
+                expected: err.expected(),
+                found: err.found(),
+            },
+            Inner::Parser(parser::Error::InvalidCharacter {
+                found,
+                index,
+                ..
+            }) => ErrorKind::InvalidCharacter { found, index },
+            Inner::Parser(parser::Error::InvalidSeparator { found, index }) => {
+                ErrorKind::InvalidSeparator { found, index }
+            }
+            Inner::Parser(parser::Error::InvalidGroupCount {
+                found,
+                index,
+                ..
+            }) => ErrorKind::InvalidGroupCount { found, index },
+            Inner::Parser(parser::Error::InvalidGroupLength {
+                found,
+                group,
+                index,
+                ..
+            }) => ErrorKind::InvalidGroupLength {
+                group,
+                found,
+                index,
+            },
+            Inner::Parser(parser::Error::InvalidLength { found, .. }) => {
+                ErrorKind::InvalidLength { found }
+            }
+            #[cfg(feature = "cursor")]
+            Inner::Cursor(_) => ErrorKind::InvalidPageToken,
+            Inner::Version(found) => ErrorKind::InvalidVersion(found),
+            Inner::Variant(found) => ErrorKind::InvalidVariant(found),
+            Inner::FieldCount { expected, found } => {
+                ErrorKind::InvalidFieldCount { expected, found }
+            }
+            Inner::FieldOverflow { field, bits } => {
+                ErrorKind::FieldOverflow { field, bits }
+            }
+            #[cfg(feature = "v1")]
+            Inner::Timestamp(ticks) => ErrorKind::TimestampOutOfRange { ticks },
+        }
+    }
+
+    /// Returns the byte offset in the input string that the parse error
+    /// points at.
+    ///
+    /// This is the `index` of the [`ErrorKind`] of the error: the offending
+    /// character for [`ErrorKind::InvalidCharacter`] and
+    /// [`ErrorKind::InvalidSeparator`], the first digit past the expected
+    /// groups for [`ErrorKind::InvalidGroupCount`], and the hyphen or end
+    /// of input terminating the group for [`ErrorKind::InvalidGroupLength`].
+    /// It's `None` for other errors, including [`ErrorKind::InvalidLength`],
+    /// which concerns the whole string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let input = "936DA01F-9ABD-4D9D-80C7-02AF85C822AX";
+    /// let err = Uuid::parse_str(input).unwrap_err();
+    ///
+    /// assert_eq!(err.parse_error_index(), Some(35));
+    ///
+    /// let err = Uuid::parse_str("936DA01F-9ABD4-D9D-80C7-02AF85C822A8").unwrap_err();
+    ///
+    /// assert_eq!(err.parse_error_index(), Some(14));
+    ///
+    /// let err = Uuid::parse_str("936DA01F").unwrap_err();
+    ///
+    /// assert_eq!(err.parse_error_index(), None);
+    /// ```
+    ///
+    /// [`ErrorKind`]: enum.ErrorKind.html
+    /// [`ErrorKind::InvalidCharacter`]: enum.ErrorKind.html#variant.InvalidCharacter
+    /// [`ErrorKind::InvalidSeparator`]: enum.ErrorKind.html#variant.InvalidSeparator
+    /// [`ErrorKind::InvalidGroupCount`]: enum.ErrorKind.html#variant.InvalidGroupCount
+    /// [`ErrorKind::InvalidGroupLength`]: enum.ErrorKind.html#variant.InvalidGroupLength
+    /// [`ErrorKind::InvalidLength`]: enum.ErrorKind.html#variant.InvalidLength
+    pub fn parse_error_index(&self) -> Option<usize> {
+        match self.0 {
+            Inner::Parser(parser::Error::InvalidCharacter {
+                index, ..
+            })
+            | Inner::Parser(parser::Error::InvalidSeparator {
+                index, ..
+            })
+            | Inner::Parser(parser::Error::InvalidGroupCount {
+                index, ..
+            })
+            | Inner::Parser(parser::Error::InvalidGroupLength {
+                index, ..
+            }) => Some(index),
+            _ => None,
         }
     }
 }
 
-#[cfg(feature = "std")]
-mod std_support {
-    use super::*;
-    use crate::std::error;
+#[cfg(feature = "cursor")]
+impl From<crate::cursor::Error> for Error {
+    fn from(err: crate::cursor::Error) -> Self {
+        Error(Inner::Cursor(err))
+    }
+}
 
-    impl error::Error for Error {
-        fn source(&self) -> Option<&(dyn error::Error + 'static)> {
-            match self.0 {
-                Inner::Build(ref err) => Some(err),
-                Inner::Parser(ref err) => Some(err),
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Inner::Build(ref err) => fmt::Display::fmt(&err, f),
+            Inner::Parser(ref err) => fmt::Display::fmt(&err, f),
+            #[cfg(feature = "cursor")]
+            Inner::Cursor(ref err) => fmt::Display::fmt(&err, f),
+            Inner::Version(found) => {
+                write!(f, "invalid UUID version number: found {}", found)
             }
+            Inner::Variant(found) => {
+                write!(f, "invalid UUID variant number: found {}", found)
+            }
+            Inner::FieldCount { expected, found } => write!(
+                f,
+                "invalid number of field values: expected {}, found {}",
+                expected, found
+            ),
+            Inner::FieldOverflow { field, bits } => write!(
+                f,
+                "value too wide for field `{}`: expected at most {} bits",
+                field, bits
+            ),
+            #[cfg(feature = "v1")]
+            Inner::Timestamp(ticks) => write!(
+                f,
+                "timestamp out of range: found {} ticks, expected at most {}",
+                ticks,
+                crate::v1::Timestamp::MAX_TICKS
+            ),
         }
     }
 }
 
+// The details of the inner error are already part of the message, so it's
+// not exposed as a source as well.
+impl error::Error for Error {}
+
 #[cfg(test)]
 mod test_util {
     use super::*;
@@ -77,3 +413,122 @@ mod test_util {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_parse_error_index() {
+        let cases: &[(&str, Option<usize>)] = &[
+            ("F9168C5E-CEB2-4faa-BGBF-329BF39FA1E4", Some(20)),
+            ("urn:uuid:F9168C5E-CEB2-4faa-BGBF-329BF39FA1E4", Some(29)),
+            ("F9168C5E-CEB2-4faa:B6BF-329BF39FA1E4", Some(18)),
+            ("F9168C5E-CEB2-4faaFB6BFF329BF39FA1E4", Some(34)),
+            ("F9168C5E-CEB-24fa-eB6BFF32-BF39FA1E4", Some(12)),
+            ("01020304-1112-2122-3132-41424344", Some(32)),
+            ("F9168C5E", None),
+        ];
+
+        for &(input, index) in cases {
+            let err = Uuid::parse_str(input).unwrap_err();
+
+            assert_eq!(err.parse_error_index(), index, "{}", input);
+        }
+
+        let err = Uuid::from_slice(&[0; 15]).unwrap_err();
+
+        assert_eq!(err.parse_error_index(), None);
+    }
+
+    #[test]
+    fn test_kind() {
+        use crate::std::convert::TryFrom;
+
+        let kind = |input| Uuid::parse_str(input).unwrap_err().kind();
+
+        assert_eq!(
+            kind("F9168C5E-CEB2-4faa-BGBF-329BF39FA1E4"),
+            ErrorKind::InvalidCharacter {
+                found: 'G',
+                index: 20
+            }
+        );
+        assert_eq!(
+            kind("F9168C5E-CEB2-4faa:B6BF-329BF39FA1E4"),
+            ErrorKind::InvalidSeparator {
+                found: ':',
+                index: 18
+            }
+        );
+        assert_eq!(
+            kind("F9168C5E-CEB2-4faaFB6BFF329BF39FA1E4"),
+            ErrorKind::InvalidGroupCount {
+                found: 3,
+                index: 34
+            }
+        );
+        assert_eq!(
+            kind("F9168C5E-CEB-24fa-eB6BFF32-BF39FA1E4"),
+            ErrorKind::InvalidGroupLength {
+                group: 1,
+                found: 3,
+                index: 12
+            }
+        );
+        assert_eq!(kind("F9168C5E"), ErrorKind::InvalidLength { found: 8 });
+
+        assert_eq!(
+            Uuid::from_slice(&[0; 15]).unwrap_err().kind(),
+            ErrorKind::InvalidByteLength {
+                expected: 16,
+                found: 15
+            }
+        );
+        assert_eq!(
+            Version::try_from(9).unwrap_err().kind(),
+            ErrorKind::InvalidVersion(9)
+        );
+        assert_eq!(
+            Variant::try_from(4).unwrap_err().kind(),
+            ErrorKind::InvalidVariant(4)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_error_display() {
+        use crate::std::string::ToString;
+
+        let message = |input| Uuid::parse_str(input).unwrap_err().to_string();
+
+        assert_eq!(
+            message("F9168C5E-CEB2-4faaFB6BFF329BF39FA1E4"),
+            "invalid number of groups: expected one of [1, 5], found 3"
+        );
+        assert_eq!(
+            message("F9168C5E-CEB-24fa-eB6BFF32-BF39FA1E4"),
+            "invalid group length: expected 4, found 3 in group 1"
+        );
+        assert_eq!(
+            message("F9168C5E-CEB2-4faa:B6BF-329BF39FA1E4"),
+            "invalid group separator: expected -, found : at 18"
+        );
+    }
+
+    #[test]
+    fn test_error_source() {
+        use crate::std::{convert::TryFrom, error::Error as _};
+
+        // also without `std`, through `core::error::Error`
+        let err = Uuid::parse_str("F9168C5E").unwrap_err();
+        assert!(err.source().is_none());
+
+        let err = Uuid::from_slice(&[0; 15]).unwrap_err();
+        assert!(err.source().is_none());
+
+        let err = Version::try_from(9).unwrap_err();
+        assert!(err.source().is_none());
+    }
+}
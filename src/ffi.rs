@@ -0,0 +1,215 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A C interface modelled on libuuid.
+//!
+//! UUIDs are passed as `uint8_t[16]` buffers holding the bytes in RFC4122
+//! order, and strings as NUL-terminated `char` buffers. The functions are
+//! exported unmangled, so building this crate as a C library provides a
+//! drop-in replacement for the corresponding libuuid functions:
+//!
+//! ```sh
+//! cargo rustc --release --features ffi,v4 --crate-type cdylib
+//! ```
+//!
+//! with declarations such as:
+//!
+//! ```c
+//! int uuid_generate_v4(uint8_t out[16]);
+//! int uuid_parse(const char *in, uint8_t out[16]);
+//! void uuid_unparse(const uint8_t uu[16], char out[37]);
+//! ```
+//!
+//! Note that you need feature `ffi` in order to use this module, and that
+//! it isn't available with the `no-unsafe` feature.
+
+use crate::adapter::Hyphenated;
+use crate::prelude::*;
+use crate::std::slice;
+
+/// The longest string accepted by [`uuid_parse`], the length of a URN.
+///
+/// [`uuid_parse`]: fn.uuid_parse.html
+const MAX_INPUT_LEN: usize = crate::adapter::Urn::LENGTH;
+
+/// Generates a random (version 4) UUID into `out`.
+///
+/// Returns `0` on success, or `-1` if the system's random number generator
+/// failed, in which case `out` is left unchanged.
+///
+/// Note that usage of this function requires the `v4` feature of this crate
+/// to be enabled.
+///
+/// # Safety
+///
+/// `out` must be valid for writing 16 bytes.
+#[cfg(feature = "v4")]
+#[no_mangle]
+pub unsafe extern "C" fn uuid_generate_v4(out: *mut u8) -> i32 {
+    match Uuid::new_v4() {
+        Ok(uuid) => {
+            write_uuid(&uuid, out);
+            0
+        }
+        Err(_) => -1,
+    }
+}
+
+/// Parses the NUL-terminated string `input` into `out`.
+///
+/// Any of the formats accepted by [`Uuid::parse_str`] are supported.
+///
+/// Returns `0` on success, or `-1` if `input` isn't a valid UUID, in which
+/// case `out` is left unchanged.
+///
+/// # Safety
+///
+/// `input` must point to a NUL-terminated string, or to at least 46
+/// readable bytes, and `out` must be valid for writing 16 bytes.
+///
+/// [`Uuid::parse_str`]: ../struct.Uuid.html#method.parse_str
+#[no_mangle]
+pub unsafe extern "C" fn uuid_parse(input: *const u8, out: *mut u8) -> i32 {
+    let mut len = 0;
+
+    while len <= MAX_INPUT_LEN && *input.add(len) != 0 {
+        len += 1;
+    }
+
+    if len > MAX_INPUT_LEN {
+        return -1;
+    }
+
+    let input = slice::from_raw_parts(input, len);
+
+    match crate::std::str::from_utf8(input).map(Uuid::parse_str) {
+        Ok(Ok(uuid)) => {
+            write_uuid(&uuid, out);
+            0
+        }
+        _ => -1,
+    }
+}
+
+/// Writes `uuid` into `out` as a NUL-terminated, lower case hyphenated
+/// string.
+///
+/// # Safety
+///
+/// `uuid` must be valid for reading 16 bytes, and `out` must be valid for
+/// writing 37 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn uuid_unparse(uuid: *const u8, out: *mut u8) {
+    unparse(uuid, out, false)
+}
+
+/// Writes `uuid` into `out` as a NUL-terminated, upper case hyphenated
+/// string.
+///
+/// # Safety
+///
+/// `uuid` must be valid for reading 16 bytes, and `out` must be valid for
+/// writing 37 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn uuid_unparse_upper(uuid: *const u8, out: *mut u8) {
+    unparse(uuid, out, true)
+}
+
+/// Returns `1` if `uuid` is the nil UUID, or `0` otherwise.
+///
+/// # Safety
+///
+/// `uuid` must be valid for reading 16 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn uuid_is_null(uuid: *const u8) -> i32 {
+    read_uuid(uuid).is_nil() as i32
+}
+
+unsafe fn read_uuid(uuid: *const u8) -> Uuid {
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(slice::from_raw_parts(uuid, 16));
+
+    Uuid::from_bytes(bytes)
+}
+
+unsafe fn write_uuid(uuid: &Uuid, out: *mut u8) {
+    slice::from_raw_parts_mut(out, 16).copy_from_slice(uuid.as_bytes());
+}
+
+unsafe fn unparse(uuid: *const u8, out: *mut u8, upper: bool) {
+    let out = slice::from_raw_parts_mut(out, Hyphenated::LENGTH + 1);
+    let hyphenated = read_uuid(uuid).to_hyphenated();
+
+    if upper {
+        hyphenated.encode_upper(&mut out[..Hyphenated::LENGTH]);
+    } else {
+        hyphenated.encode_lower(&mut out[..Hyphenated::LENGTH]);
+    }
+
+    out[Hyphenated::LENGTH] = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_parse_unparse() {
+        let uuid = test_util::new();
+        let mut out = [0; 16];
+
+        let input = b"F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4\0";
+        assert_eq!(unsafe { uuid_parse(input.as_ptr(), out.as_mut_ptr()) }, 0);
+        assert_eq!(&out, uuid.as_bytes());
+
+        let mut s = [0xff; 37];
+        unsafe { uuid_unparse(out.as_ptr(), s.as_mut_ptr()) };
+        assert_eq!(&s[..], &b"f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4\0"[..]);
+
+        unsafe { uuid_unparse_upper(out.as_ptr(), s.as_mut_ptr()) };
+        assert_eq!(&s[..], &input[..]);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let mut out = [0; 16];
+
+        for input in &[
+            &b"\0"[..],
+            &b"F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E\0"[..],
+            &b"urn:uuid:F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4-\0"[..],
+            &[0xff; 64][..],
+        ] {
+            assert_eq!(
+                unsafe { uuid_parse(input.as_ptr(), out.as_mut_ptr()) },
+                -1
+            );
+        }
+
+        assert_eq!(out, [0; 16]);
+    }
+
+    #[test]
+    fn test_is_null() {
+        assert_eq!(unsafe { uuid_is_null(Uuid::nil().as_ptr()) }, 1);
+        assert_eq!(unsafe { uuid_is_null(test_util::new().as_ptr()) }, 0);
+    }
+
+    #[cfg(feature = "v4")]
+    #[test]
+    fn test_generate_v4() {
+        let mut out = [0; 16];
+
+        assert_eq!(unsafe { uuid_generate_v4(out.as_mut_ptr()) }, 0);
+        assert_eq!(Uuid::from_bytes(out).get_version(), Some(Version::Random));
+    }
+}
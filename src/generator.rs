@@ -0,0 +1,364 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A policy for how new UUIDs are made.
+//!
+//! Code that creates IDs can take a [`UuidGenerator`] instead of calling
+//! [`Uuid::new_v4`] directly, so the kind of UUID is chosen by whoever
+//! constructs it, and tests can swap in a generator whose output is known
+//! ahead of time. The generators provided here are:
+//!
+//! * [`V4Generator`], random UUIDs (requires features `v4` and `std`),
+//! * [`V7Generator`], time-ordered UUIDs that never go backwards, even
+//!   within a millisecond (requires features `v4` and `std`),
+//! * [`V1Generator`], time-based UUIDs from a clock sequence and a node ID
+//!   (requires features `v1` and `std`), and
+//! * [`SequentialGenerator`], UUIDs counting up from a given number, for
+//!   tests.
+//!
+//! # Examples
+//!
+//! ```
+//! use uuid::{
+//!     generator::{SequentialGenerator, UuidGenerator},
+//!     Uuid,
+//! };
+//!
+//! struct Orders<G> {
+//!     ids: G,
+//! }
+//!
+//! impl<G: UuidGenerator> Orders<G> {
+//!     fn place(&self) -> Uuid {
+//!         self.ids.generate()
+//!     }
+//! }
+//!
+//! let orders = Orders { ids: SequentialGenerator::new(1) };
+//!
+//! assert_eq!(
+//!     orders.place().to_string(),
+//!     "00000000-0000-0000-0000-000000000001"
+//! );
+//! ```
+//!
+//! [`UuidGenerator`]: trait.UuidGenerator.html
+//! [`V4Generator`]: struct.V4Generator.html
+//! [`V7Generator`]: struct.V7Generator.html
+//! [`V1Generator`]: struct.V1Generator.html
+//! [`SequentialGenerator`]: struct.SequentialGenerator.html
+//! [`Uuid::new_v4`]: ../struct.Uuid.html#method.new_v4
+
+use crate::prelude::*;
+#[cfg(target_has_atomic = "64")]
+use crate::std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(all(any(feature = "v1", feature = "v4"), feature = "std"))]
+use crate::std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(feature = "std")]
+use crate::std::{boxed::Box, sync::Arc};
+#[cfg(all(feature = "v4", feature = "std"))]
+use crate::{packed, std::sync::Mutex};
+
+/// A source of new UUIDs.
+///
+/// Implementations are expected to return a different UUID every time,
+/// except where documented otherwise.
+pub trait UuidGenerator {
+    /// Returns a new UUID.
+    fn generate(&self) -> Uuid;
+}
+
+impl<T: UuidGenerator + ?Sized> UuidGenerator for &T {
+    fn generate(&self) -> Uuid {
+        (**self).generate()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: UuidGenerator + ?Sized> UuidGenerator for Box<T> {
+    fn generate(&self) -> Uuid {
+        (**self).generate()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: UuidGenerator + ?Sized> UuidGenerator for Arc<T> {
+    fn generate(&self) -> Uuid {
+        (**self).generate()
+    }
+}
+
+/// Generates random (version 4) UUIDs with [`Uuid::new_v4`].
+///
+/// # Panics
+///
+/// [`UuidGenerator::generate`] panics if random bytes can't be read from
+/// the operating system.
+///
+/// [`Uuid::new_v4`]: ../struct.Uuid.html#method.new_v4
+/// [`UuidGenerator::generate`]: trait.UuidGenerator.html#tymethod.generate
+#[cfg(all(feature = "v4", feature = "std"))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct V4Generator;
+
+#[cfg(all(feature = "v4", feature = "std"))]
+impl UuidGenerator for V4Generator {
+    fn generate(&self) -> Uuid {
+        Uuid::new_v4().expect("failed to read random bytes")
+    }
+}
+
+/// Generates time-ordered (version 7) UUIDs that never go backwards.
+///
+/// Each UUID holds the current Unix time in milliseconds followed by
+/// random bits. If the clock hasn't moved on since the last UUID, or has
+/// gone back, the last UUID's random bits plus one are used instead, so
+/// every UUID from the same generator sorts after the ones before it.
+///
+/// # Panics
+///
+/// [`UuidGenerator::generate`] panics if random bytes can't be read from
+/// the operating system.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::generator::{UuidGenerator, V7Generator};
+///
+/// let ids = V7Generator::new();
+/// let (a, b) = (ids.generate(), ids.generate());
+///
+/// assert!(a < b);
+/// ```
+///
+/// [`UuidGenerator::generate`]: trait.UuidGenerator.html#tymethod.generate
+#[cfg(all(feature = "v4", feature = "std"))]
+#[derive(Debug, Default)]
+pub struct V7Generator {
+    last: Mutex<u128>,
+}
+
+#[cfg(all(feature = "v4", feature = "std"))]
+impl V7Generator {
+    /// Creates a generator.
+    pub const fn new() -> Self {
+        V7Generator {
+            last: Mutex::new(0),
+        }
+    }
+
+    fn next(&self, unix_millis: u64, random: [u8; 10]) -> Uuid {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+
+        let uuid = Builder::from_unix_millis(unix_millis, random).build();
+        let packed = packed::pack(uuid.as_u128()).max(*last + 1);
+        *last = packed;
+
+        Uuid::from_u128(packed::unpack(packed) | 0x7 << 76 | 0b10 << 62)
+    }
+}
+
+#[cfg(all(feature = "v4", feature = "std"))]
+impl UuidGenerator for V7Generator {
+    fn generate(&self) -> Uuid {
+        let mut random = [0; 10];
+        getrandom::getrandom(&mut random).expect("failed to read random bytes");
+
+        let unix_millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+
+        self.next(unix_millis, random)
+    }
+}
+
+/// Generates time-based (version 1) UUIDs from the system clock.
+///
+/// # Panics
+///
+/// [`UuidGenerator::generate`] panics if the system clock is past the
+/// range of version 1 timestamps.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::{
+///     generator::{UuidGenerator, V1Generator},
+///     v1::Context,
+///     Version,
+/// };
+///
+/// let ids = V1Generator::new(Context::new(0), [1, 2, 3, 4, 5, 6]);
+/// let uuid = ids.generate();
+///
+/// assert_eq!(uuid.get_version(), Some(Version::Mac));
+/// ```
+///
+/// [`UuidGenerator::generate`]: trait.UuidGenerator.html#tymethod.generate
+#[cfg(all(feature = "v1", feature = "std"))]
+#[derive(Debug)]
+pub struct V1Generator<C> {
+    context: C,
+    node: crate::node::NodeId,
+}
+
+#[cfg(all(feature = "v1", feature = "std"))]
+impl<C: crate::v1::ClockSequence> V1Generator<C> {
+    /// Creates a generator that uses `context` for the clock sequence and
+    /// `node` as the node ID.
+    pub const fn new(context: C, node: crate::node::NodeId) -> Self {
+        V1Generator { context, node }
+    }
+}
+
+#[cfg(all(feature = "v1", feature = "std"))]
+impl<C: crate::v1::ClockSequence> UuidGenerator for V1Generator<C> {
+    fn generate(&self) -> Uuid {
+        let elapsed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let ts = crate::v1::Timestamp::from_unix(
+            &self.context,
+            elapsed.as_secs(),
+            elapsed.subsec_nanos(),
+        );
+
+        Uuid::new_v1(ts, &self.node)
+            .expect("the system clock is past the range of v1 timestamps")
+    }
+}
+
+/// Generates UUIDs counting up from a given number, for tests.
+///
+/// The UUIDs are the number as a `u128`, so they're easy to tell apart in
+/// assertions and logs. They have no version and aren't unique across
+/// generators.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::generator::{SequentialGenerator, UuidGenerator};
+///
+/// let ids = SequentialGenerator::new(0x10);
+///
+/// assert_eq!(ids.generate().to_string(), "00000000-0000-0000-0000-000000000010");
+/// assert_eq!(ids.generate().to_string(), "00000000-0000-0000-0000-000000000011");
+/// ```
+#[cfg(target_has_atomic = "64")]
+#[derive(Debug, Default)]
+pub struct SequentialGenerator {
+    next: AtomicU64,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl SequentialGenerator {
+    /// Creates a generator whose first UUID is `start`.
+    pub const fn new(start: u64) -> Self {
+        SequentialGenerator {
+            next: AtomicU64::new(start),
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl UuidGenerator for SequentialGenerator {
+    fn generate(&self) -> Uuid {
+        Uuid::from_u64_pair(0, self.next.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn by_ref<G: UuidGenerator>(ids: G) -> Uuid {
+        ids.generate()
+    }
+
+    #[cfg(target_has_atomic = "64")]
+    #[test]
+    fn test_sequential() {
+        let ids = SequentialGenerator::new(u64::MAX - 1);
+
+        assert_eq!(ids.generate(), Uuid::from_u128(u128::from(u64::MAX - 1)));
+        assert_eq!(by_ref(&ids), Uuid::from_u128(u128::from(u64::MAX)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_dyn() {
+        let ids: Arc<dyn UuidGenerator> = Arc::new(SequentialGenerator::new(7));
+
+        assert_eq!(ids.generate(), Uuid::from_u128(7));
+        assert_eq!(Box::new(ids).generate(), Uuid::from_u128(8));
+    }
+
+    #[cfg(all(feature = "v4", feature = "std"))]
+    #[test]
+    fn test_v4() {
+        let uuid = V4Generator.generate();
+
+        assert_eq!(uuid.get_version(), Some(crate::Version::Random));
+        assert_ne!(uuid, V4Generator.generate());
+    }
+
+    #[cfg(all(feature = "v4", feature = "std"))]
+    #[test]
+    fn test_v7_monotonic() {
+        let ids = V7Generator::new();
+
+        let a = ids.next(1_600_000_000_000, [0xff; 10]);
+        // same millisecond, lower random bits
+        let b = ids.next(1_600_000_000_000, [0; 10]);
+        // the clock went back
+        let c = ids.next(1_500_000_000_000, [0; 10]);
+        let d = ids.next(1_600_000_000_001, [0; 10]);
+
+        assert!(a < b && b < c && c < d);
+        for uuid in &[a, b, c, d] {
+            assert_eq!(uuid.get_version(), Some(crate::Version::SortRand));
+            assert_eq!(uuid.get_variant(), Some(crate::Variant::RFC4122));
+        }
+
+        // the random bits overflow into the timestamp
+        assert_eq!(
+            b,
+            Uuid::parse_str("0174876e-8001-7000-8000-000000000000").unwrap()
+        );
+        assert_eq!(
+            d,
+            Uuid::parse_str("0174876e-8001-7000-8000-000000000002").unwrap()
+        );
+    }
+
+    #[cfg(all(feature = "v4", feature = "std"))]
+    #[test]
+    fn test_v7_now() {
+        let ids = V7Generator::new();
+        let uuids = (0..100)
+            .map(|_| ids.generate())
+            .collect::<crate::std::vec::Vec<_>>();
+
+        assert!(uuids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[cfg(all(feature = "v1", feature = "std"))]
+    #[test]
+    fn test_v1() {
+        let ids =
+            V1Generator::new(crate::v1::Context::new(0), [1, 2, 3, 4, 5, 6]);
+        let (a, b) = (ids.generate(), ids.generate());
+
+        assert_ne!(a, b);
+        assert_eq!(a.get_version(), Some(crate::Version::Mac));
+        assert_eq!(&a.as_bytes()[10..], &[1, 2, 3, 4, 5, 6]);
+    }
+}
@@ -0,0 +1,637 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A [`Uuid`] with .NET and COM `Guid` semantics.
+//!
+//! [`Uuid`]: ../struct.Uuid.html
+
+use crate::parser::error::{ExpectedLength, UrnPrefix};
+use crate::prelude::*;
+use crate::std::{fmt, str};
+use crate::{adapter, parser};
+
+/// A UUID in the mixed-endian binary layout used by .NET and COM.
+///
+/// The value of a `Guid` is the same as the value of the [`Uuid`] it was
+/// created from, but its binary form stores the first three fields in
+/// little-endian order, matching `Guid.ToByteArray()` and the in-memory
+/// layout of a `GUID` on little-endian platforms. Keeping it as a separate
+/// type makes it impossible to mix up the two byte orders by accident.
+///
+/// `Guid` is formatted and parsed in the styles supported by .NET's
+/// `Guid.ToString(format)`, see [`Format`].
+///
+/// # Examples
+///
+/// ```
+/// use uuid::{guid::Guid, Uuid};
+///
+/// let uuid = Uuid::parse_str("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+/// let guid = Guid::from(uuid);
+///
+/// assert_eq!(
+///     guid.to_bytes_le(),
+///     [
+///         0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66,
+///         0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+///     ],
+/// );
+/// assert_eq!(guid.to_string(), "00112233-4455-6677-8899-aabbccddeeff");
+/// ```
+///
+/// [`Uuid`]: ../struct.Uuid.html
+/// [`Format`]: enum.Format.html
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Guid(Uuid);
+
+/// The string formats of a [`Guid`], named after the .NET format
+/// specifiers.
+///
+/// [`Guid`]: struct.Guid.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Format {
+    /// 32 digits: `00112233445566778899aabbccddeeff`.
+    N,
+    /// 32 digits separated by hyphens:
+    /// `00112233-4455-6677-8899-aabbccddeeff`.
+    D,
+    /// Hyphenated digits enclosed in braces:
+    /// `{00112233-4455-6677-8899-aabbccddeeff}`.
+    B,
+    /// Hyphenated digits enclosed in parentheses:
+    /// `(00112233-4455-6677-8899-aabbccddeeff)`.
+    P,
+}
+
+/// A [`Guid`] formatted in one of the [`Format`]s.
+///
+/// [`Guid`]: struct.Guid.html
+/// [`Format`]: enum.Format.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Formatted {
+    guid: Guid,
+    format: Format,
+}
+
+impl Guid {
+    /// Creates a `Guid` with the same value as a [`Uuid`].
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    pub const fn from_uuid(uuid: Uuid) -> Self {
+        Guid(uuid)
+    }
+
+    /// Returns the [`Uuid`] with the same value as this `Guid`.
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    pub const fn to_uuid(self) -> Uuid {
+        self.0
+    }
+
+    /// Creates a `Guid` from its mixed-endian binary form, as produced by
+    /// .NET's `Guid.ToByteArray()`.
+    pub fn from_bytes_le(bytes: Bytes) -> Self {
+        Guid(Uuid::from_bytes(swap_fields(bytes)))
+    }
+
+    /// Returns the mixed-endian binary form of the `Guid`, as expected by
+    /// .NET's `new Guid(byte[])`.
+    pub fn to_bytes_le(&self) -> Bytes {
+        swap_fields(*self.0.as_bytes())
+    }
+
+    /// Parses a `Guid` from any of the string [`Format`]s.
+    ///
+    /// Upper and lower case hexadecimal digits are both accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::guid::Guid;
+    ///
+    /// let d = Guid::parse_str("00112233-4455-6677-8899-AABBCCDDEEFF").unwrap();
+    /// let b = Guid::parse_str("{00112233-4455-6677-8899-aabbccddeeff}").unwrap();
+    /// let p = Guid::parse_str("(00112233-4455-6677-8899-aabbccddeeff)").unwrap();
+    /// let n = Guid::parse_str("00112233445566778899aabbccddeeff").unwrap();
+    ///
+    /// assert!(d == b && b == p && p == n);
+    /// ```
+    ///
+    /// [`Format`]: enum.Format.html
+    pub fn parse_str(input: &str) -> Result<Guid, crate::Error> {
+        let len = input.len();
+
+        if len == adapter::Braced::LENGTH {
+            let close = match input.as_bytes()[0] {
+                b'{' => b'}',
+                b'(' => b')',
+                _ => {
+                    let found = input.chars().next().unwrap_or_default();
+
+                    return Err(invalid_character(found, 0, "{(").into());
+                }
+            };
+
+            if input.as_bytes()[len - 1] != close {
+                let expected = if close == b'}' { "}" } else { ")" };
+                let found = input.chars().next_back().unwrap_or_default();
+                let index = len - found.len_utf8();
+
+                return Err(invalid_character(found, index, expected).into());
+            }
+
+            return Uuid::parse_str(&input[1..len - 1])
+                .map(Guid)
+                .map_err(|err| err.offset(1));
+        }
+
+        if len != adapter::Hyphenated::LENGTH && len != adapter::Simple::LENGTH
+        {
+            return Err(parser::Error::InvalidLength {
+                expected: ExpectedLength::Any(&[
+                    adapter::Simple::LENGTH,
+                    adapter::Hyphenated::LENGTH,
+                    adapter::Braced::LENGTH,
+                ]),
+                found: len,
+            }
+            .into());
+        }
+
+        Uuid::parse_str(input).map(Guid)
+    }
+
+    /// Parses a `Guid` from the C struct initializer style used in Windows
+    /// headers and registry exports, such as
+    /// `{0x00112233,0x4455,0x6677,{0x88,0x99,0xaa,0xbb,0xcc,0xdd,0xee,0xff}}`.
+    ///
+    /// Whitespace is allowed between the tokens, and leading zeros of the
+    /// numbers may be omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::guid::Guid;
+    ///
+    /// let guid = Guid::parse_ms_struct(
+    ///     "{ 0x112233, 0x4455, 0x6677, { 0x88, 0x99, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF } }",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(guid.to_string(), "00112233-4455-6677-8899-aabbccddeeff");
+    /// ```
+    pub fn parse_ms_struct(input: &str) -> Result<Guid, crate::Error> {
+        let mut parser = StructParser {
+            input,
+            index: 0,
+            fields: 0,
+        };
+
+        parser.token(b'{', "{")?;
+        let d1 = parser.field(8)?;
+        parser.token(b',', ",")?;
+        let d2 = parser.field(4)? as u16;
+        parser.token(b',', ",")?;
+        let d3 = parser.field(4)? as u16;
+        parser.token(b',', ",")?;
+
+        parser.token(b'{', "{")?;
+        let mut d4 = [0; 8];
+        for (i, byte) in d4.iter_mut().enumerate() {
+            if i > 0 {
+                parser.token(b',', ",")?;
+            }
+
+            *byte = parser.field(2)? as u8;
+        }
+        parser.token(b'}', "}")?;
+        parser.token(b'}', "}")?;
+
+        parser.end()?;
+
+        Uuid::from_fields(d1, d2, d3, &d4).map(Guid)
+    }
+
+    /// Returns an adapter that formats the `Guid` in the given [`Format`].
+    ///
+    /// Digits are lower case, as in .NET.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::guid::{Format, Guid};
+    ///
+    /// let guid = Guid::parse_str("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+    ///
+    /// assert_eq!(
+    ///     guid.format(Format::B).to_string(),
+    ///     "{00112233-4455-6677-8899-aabbccddeeff}",
+    /// );
+    /// ```
+    ///
+    /// [`Format`]: enum.Format.html
+    pub const fn format(self, format: Format) -> Formatted {
+        Formatted { guid: self, format }
+    }
+}
+
+impl Uuid {
+    /// Returns the binary form of a SQL Server `uniqueidentifier` with the
+    /// same value as this UUID.
+    ///
+    /// SQL Server stores the first three fields in little-endian order, the
+    /// same layout as [`Guid::to_bytes_le`]. Passing [`Uuid::as_bytes`] to a
+    /// driver instead silently reorders the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+    ///
+    /// assert_eq!(
+    ///     uuid.to_mssql_bytes(),
+    ///     [
+    ///         0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66,
+    ///         0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+    ///     ],
+    /// );
+    /// assert_eq!(Uuid::from_mssql_bytes(uuid.to_mssql_bytes()), uuid);
+    /// ```
+    ///
+    /// [`Guid::to_bytes_le`]: guid/struct.Guid.html#method.to_bytes_le
+    /// [`Uuid::as_bytes`]: struct.Uuid.html#method.as_bytes
+    pub fn to_mssql_bytes(&self) -> Bytes {
+        Guid(*self).to_bytes_le()
+    }
+
+    /// Creates a UUID from the binary form of a SQL Server
+    /// `uniqueidentifier`.
+    ///
+    /// This is the inverse of [`Uuid::to_mssql_bytes`].
+    ///
+    /// [`Uuid::to_mssql_bytes`]: struct.Uuid.html#method.to_mssql_bytes
+    pub fn from_mssql_bytes(bytes: Bytes) -> Uuid {
+        Guid::from_bytes_le(bytes).to_uuid()
+    }
+}
+
+/// Swaps between the RFC4122 and mixed-endian byte orders.
+fn swap_fields(mut bytes: Bytes) -> Bytes {
+    bytes[0..4].reverse();
+    bytes[4..6].reverse();
+    bytes[6..8].reverse();
+
+    bytes
+}
+
+fn invalid_character(
+    found: char,
+    index: usize,
+    expected: &'static str,
+) -> parser::Error {
+    parser::Error::InvalidCharacter {
+        expected,
+        found,
+        index,
+        urn: UrnPrefix::Optional,
+    }
+}
+
+/// The number of numeric fields in a `GUID` struct initializer.
+const STRUCT_FIELDS: usize = 11;
+
+/// A cursor over a `GUID` struct initializer.
+struct StructParser<'a> {
+    input: &'a str,
+    index: usize,
+    fields: usize,
+}
+
+impl<'a> StructParser<'a> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.index..];
+
+        self.index += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.index..].chars().next()
+    }
+
+    fn unexpected(&self, expected: &'static str) -> parser::Error {
+        match self.peek() {
+            Some(found) => invalid_character(found, self.index, expected),
+            None => parser::Error::InvalidGroupCount {
+                expected: ExpectedLength::Exact(STRUCT_FIELDS),
+                found: self.fields,
+                index: self.index,
+            },
+        }
+    }
+
+    fn token(
+        &mut self,
+        token: u8,
+        expected: &'static str,
+    ) -> Result<(), parser::Error> {
+        self.skip_whitespace();
+
+        if self.input.as_bytes().get(self.index) != Some(&token) {
+            return Err(self.unexpected(expected));
+        }
+
+        self.index += 1;
+
+        Ok(())
+    }
+
+    /// Parses a `0x` prefixed number of at most `max_digits` digits.
+    fn field(&mut self, max_digits: usize) -> Result<u32, parser::Error> {
+        self.skip_whitespace();
+
+        let bytes = self.input.as_bytes();
+
+        if bytes.get(self.index) != Some(&b'0') {
+            return Err(self.unexpected("0x"));
+        }
+        self.index += 1;
+
+        match bytes.get(self.index) {
+            Some(b'x') | Some(b'X') => self.index += 1,
+            _ => return Err(self.unexpected("xX")),
+        }
+
+        let start = self.index;
+        let mut value = 0;
+
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(16)) {
+            value = value << 4 | digit;
+            self.index += 1;
+
+            if self.index - start > max_digits {
+                return Err(parser::Error::InvalidGroupLength {
+                    expected: ExpectedLength::Exact(max_digits),
+                    found: self.index - start,
+                    group: self.fields,
+                    index: start,
+                });
+            }
+        }
+
+        if self.index == start {
+            return Err(self.unexpected("0123456789abcdefABCDEF"));
+        }
+
+        self.fields += 1;
+
+        Ok(value)
+    }
+
+    fn end(&mut self) -> Result<(), parser::Error> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(found) => Err(invalid_character(found, self.index, "")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl From<Uuid> for Guid {
+    fn from(uuid: Uuid) -> Self {
+        Guid(uuid)
+    }
+}
+
+impl From<Guid> for Uuid {
+    fn from(guid: Guid) -> Self {
+        guid.0
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.format(Format::D), f)
+    }
+}
+
+impl str::FromStr for Guid {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Guid::parse_str(s)
+    }
+}
+
+impl fmt::Display for Formatted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let uuid = &self.guid.0;
+
+        match self.format {
+            Format::N => write!(f, "{}", uuid.to_simple_ref()),
+            Format::D => write!(f, "{}", uuid.to_hyphenated_ref()),
+            Format::B => write!(f, "{{{}}}", uuid.to_hyphenated_ref()),
+            Format::P => write!(f, "({})", uuid.to_hyphenated_ref()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std::string::ToString;
+    use crate::test_util;
+
+    #[test]
+    fn test_bytes_le() {
+        let uuid = test_util::new();
+        let guid = Guid::from(uuid);
+
+        let (d1, d2, d3, d4) = uuid.as_fields();
+        let bytes = guid.to_bytes_le();
+
+        assert_eq!(bytes[..4], d1.to_le_bytes());
+        assert_eq!(bytes[4..6], d2.to_le_bytes());
+        assert_eq!(bytes[6..8], d3.to_le_bytes());
+        assert_eq!(bytes[8..], d4[..]);
+
+        assert_eq!(Guid::from_bytes_le(bytes), guid);
+        assert_eq!(Uuid::from(guid), uuid);
+    }
+
+    #[test]
+    fn test_mssql_bytes() {
+        let uuid = test_util::new();
+        let bytes = uuid.to_mssql_bytes();
+
+        assert_eq!(bytes, Guid::from(uuid).to_bytes_le());
+        assert_eq!(
+            bytes[..8],
+            [0x5e, 0x8c, 0x16, 0xf9, 0xb2, 0xce, 0xaa, 0x4f]
+        );
+        assert_eq!(bytes[8..], uuid.as_bytes()[8..]);
+        assert_eq!(Uuid::from_mssql_bytes(bytes), uuid);
+    }
+
+    #[test]
+    fn test_format() {
+        let guid = Guid::from(test_util::new());
+
+        assert_eq!(
+            guid.format(Format::N).to_string(),
+            "f9168c5eceb24faab6bf329bf39fa1e4"
+        );
+        assert_eq!(
+            guid.format(Format::D).to_string(),
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4"
+        );
+        assert_eq!(
+            guid.format(Format::B).to_string(),
+            "{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}"
+        );
+        assert_eq!(
+            guid.format(Format::P).to_string(),
+            "(f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4)"
+        );
+        assert_eq!(guid.to_string(), guid.format(Format::D).to_string());
+    }
+
+    #[test]
+    fn test_parse_roundtrip() {
+        let guid = Guid::from(test_util::new());
+
+        for &format in &[Format::N, Format::D, Format::B, Format::P] {
+            let s = guid.format(format).to_string();
+
+            assert_eq!(Guid::parse_str(&s).unwrap(), guid);
+            assert_eq!(s.to_uppercase().parse::<Guid>().unwrap(), guid);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(
+            Guid::parse_str("{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4)")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidCharacter {
+                expected: "}",
+                found: ')',
+                index: 37,
+                urn: UrnPrefix::Optional,
+            })
+        );
+        assert_eq!(
+            Guid::parse_str("[f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4]")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidCharacter {
+                expected: "{(",
+                found: '[',
+                index: 0,
+                urn: UrnPrefix::Optional,
+            })
+        );
+        assert!(Guid::parse_str(
+            "urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4"
+        )
+        .is_err());
+        assert!(Guid::parse_str("{f9168c5eceb24faab6bf329bf39fa1e4}").is_err());
+        assert!(Guid::parse_str("").is_err());
+        assert!(Guid::parse_str("{f9168c5e-ceb2-4faa-b6bf-329bf39fa1\u{e9}")
+            .is_err());
+
+        // positions inside the brackets are relative to the whole input
+        for input in &[
+            "{f9168c5e-ceb2-4faa-b6bf-329bf39fa1eX}",
+            "(f9168c5e-ceb2-4faa-b6bf-329bf39fa1eX)",
+        ] {
+            assert!(matches!(
+                Guid::parse_str(input).map_err(crate::Error::expect_parser),
+                Err(parser::Error::InvalidCharacter {
+                    found: 'X',
+                    index: 36,
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_ms_struct() {
+        let expected = Guid::from(test_util::new());
+
+        for input in &[
+            "{0xf9168c5e,0xceb2,0x4faa,{0xb6,0xbf,0x32,0x9b,0xf3,0x9f,0xa1,0xe4}}",
+            "{ 0xF9168C5E, 0xCEB2, 0x4FAA, { 0xB6, 0xBF, 0x32, 0x9B, 0xF3, 0x9F, 0xA1, 0xE4 } }",
+            "\t{0Xf9168c5e,\n 0xceb2,\n 0x4faa,\n {0xb6, 0xbf, 0x32, 0x9b, 0xf3, 0x9f, 0xa1, 0xe4}}\n",
+        ] {
+            assert_eq!(Guid::parse_ms_struct(input), Ok(expected), "{}", input);
+        }
+
+        assert_eq!(
+            Guid::parse_ms_struct(
+                "{0x1,0x2,0x3,{0x4,0x5,0x6,0x7,0x8,0x9,0xa,0xb}}"
+            )
+            .unwrap()
+            .to_string(),
+            "00000001-0002-0003-0405-060708090a0b"
+        );
+    }
+
+    #[test]
+    fn test_parse_ms_struct_invalid() {
+        assert_eq!(
+            Guid::parse_ms_struct("{0xf9168c5e,0xceb2,0x4faa}")
+                .map_err(crate::Error::expect_parser),
+            Err(invalid_character('}', 25, ","))
+        );
+        assert_eq!(
+            Guid::parse_ms_struct("{0xf9168c5e,0xceb2,0x4faa,{0xb6,0xbf")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidGroupCount {
+                expected: ExpectedLength::Exact(11),
+                found: 5,
+                index: 36,
+            })
+        );
+        assert_eq!(
+            Guid::parse_ms_struct("{0xf9168c5e,0xceb21,0x4faa}")
+                .map_err(crate::Error::expect_parser),
+            Err(parser::Error::InvalidGroupLength {
+                expected: ExpectedLength::Exact(4),
+                found: 5,
+                group: 1,
+                index: 14,
+            })
+        );
+        assert_eq!(
+            Guid::parse_ms_struct("{f9168c5e,0xceb2,0x4faa}")
+                .map_err(crate::Error::expect_parser),
+            Err(invalid_character('f', 1, "0x"))
+        );
+        assert_eq!(
+            Guid::parse_ms_struct("{0xg9168c5e,0xceb2,0x4faa}")
+                .map_err(crate::Error::expect_parser),
+            Err(invalid_character('g', 3, "0123456789abcdefABCDEF"))
+        );
+
+        let valid = "{0x1,0x2,0x3,{0x4,0x5,0x6,0x7,0x8,0x9,0xa,0xb}}";
+
+        assert_eq!(
+            Guid::parse_ms_struct(&format!("{};", valid))
+                .map_err(crate::Error::expect_parser),
+            Err(invalid_character(';', valid.len(), ""))
+        );
+        assert!(Guid::parse_ms_struct("").is_err());
+        assert!(Guid::parse_ms_struct("{0x\u{e9}}").is_err());
+    }
+}
@@ -0,0 +1,447 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A breakdown of the fields of a [`Uuid`].
+//!
+//! [`Uuid`]: ../struct.Uuid.html
+
+use crate::prelude::*;
+use crate::std::{fmt, time::Duration};
+
+/// The number of 100 ns ticks between the UUID epoch
+/// `1582-10-15 00:00:00` and the Unix epoch `1970-01-01 00:00:00`.
+pub(crate) const UUID_TICKS_BETWEEN_EPOCHS: u64 = 0x01B2_1DD2_1381_4000;
+
+/// The decoded fields of a [`Uuid`], as returned by [`Uuid::describe`].
+///
+/// The `Debug` format prints the fields that are present, with the
+/// timestamp in ISO-8601 format. It is meant for people and may change
+/// between releases.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::Uuid;
+///
+/// let uuid = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+///
+/// assert_eq!(
+///     format!("{:#?}", uuid.describe()),
+///     "\
+/// UuidInfo {
+///     version: 1,
+///     variant: Some(
+///         RFC4122,
+///     ),
+///     timestamp: 2017-06-16T14:41:59.000001200Z,
+///     clock_seq: 0x002a,
+///     node_id: 0x010203040506,
+/// }"
+/// );
+/// ```
+///
+/// [`Uuid`]: ../struct.Uuid.html
+/// [`Uuid::describe`]: ../struct.Uuid.html#method.describe
+#[derive(Clone, Copy, PartialEq)]
+pub struct UuidInfo {
+    version: Option<Version>,
+    version_num: usize,
+    variant: Option<Variant>,
+    timestamp: Option<(u64, u32)>,
+    clock_seq: Option<u16>,
+    node_id: Option<[u8; 6]>,
+}
+
+impl UuidInfo {
+    /// Returns the version, as given by [`Uuid::get_version`].
+    ///
+    /// [`Uuid::get_version`]: ../struct.Uuid.html#method.get_version
+    pub const fn version(&self) -> Option<Version> {
+        self.version
+    }
+
+    /// Returns the version number, as given by [`Uuid::get_version_num`].
+    ///
+    /// [`Uuid::get_version_num`]: ../struct.Uuid.html#method.get_version_num
+    pub const fn version_num(&self) -> usize {
+        self.version_num
+    }
+
+    /// Returns the variant, as given by [`Uuid::get_variant`].
+    ///
+    /// [`Uuid::get_variant`]: ../struct.Uuid.html#method.get_variant
+    pub const fn variant(&self) -> Option<Variant> {
+        self.variant
+    }
+
+    /// Returns the time the UUID was generated at, as seconds and
+    /// fractional nanoseconds since the Unix epoch.
+    ///
+    /// This is only available for version 1, 6 and 7 UUIDs with the
+    /// RFC4122 variant, and only if the time isn't before the Unix epoch.
+    pub const fn unix_timestamp(&self) -> Option<(u64, u32)> {
+        self.timestamp
+    }
+
+    /// Returns the 14-bit clock sequence of a version 1 or 6 UUID.
+    pub const fn clock_seq(&self) -> Option<u16> {
+        self.clock_seq
+    }
+
+    /// Returns the node ID, the last 6 bytes, of a version 1 or 6 UUID.
+    pub const fn node_id(&self) -> Option<[u8; 6]> {
+        self.node_id
+    }
+}
+
+impl fmt::Debug for UuidInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("UuidInfo");
+        debug
+            .field("version", &self.version_num)
+            .field("variant", &self.variant);
+
+        if let Some(timestamp) = self.timestamp {
+            debug.field("timestamp", &format_args!("{}", Iso8601(timestamp)));
+        }
+
+        if let Some(clock_seq) = self.clock_seq {
+            debug.field("clock_seq", &format_args!("{:#06x}", clock_seq));
+        }
+
+        if let Some(node_id) = self.node_id {
+            let node_id = node_id
+                .iter()
+                .fold(0u64, |node, &byte| node << 8 | u64::from(byte));
+
+            debug.field("node_id", &format_args!("{:#014x}", node_id));
+        }
+
+        debug.finish()
+    }
+}
+
+impl Uuid {
+    /// Decodes the version, variant, and, for time-based UUIDs, the
+    /// timestamp, clock sequence and node ID of the UUID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{Uuid, Variant, Version};
+    ///
+    /// let uuid = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+    /// let info = uuid.describe();
+    ///
+    /// assert_eq!(info.version(), Some(Version::Mac));
+    /// assert_eq!(info.variant(), Some(Variant::RFC4122));
+    /// assert_eq!(info.unix_timestamp(), Some((1_497_624_119, 1_200)));
+    /// assert_eq!(info.clock_seq(), Some(0x002a));
+    /// assert_eq!(info.node_id(), Some([1, 2, 3, 4, 5, 6]));
+    ///
+    /// let info = Uuid::nil().describe();
+    ///
+    /// assert_eq!(info.version(), Some(Version::Nil));
+    /// assert_eq!(info.unix_timestamp(), None);
+    /// ```
+    pub fn describe(&self) -> UuidInfo {
+        let b = self.as_bytes();
+        let variant = self.get_variant();
+        let version_num = self.get_version_num();
+
+        let (timestamp, clock_seq, node_id) = match self.time() {
+            Some(Time::Ticks(ticks)) => {
+                let mut node_id = [0; 6];
+                node_id.copy_from_slice(&b[10..]);
+
+                let clock_seq = u16::from(b[8] & 0x3f) << 8 | u16::from(b[9]);
+
+                (unix_from_ticks(ticks), Some(clock_seq), Some(node_id))
+            }
+            Some(Time::UnixMillis(millis)) => {
+                let timestamp =
+                    (millis / 1000, (millis % 1000) as u32 * 1_000_000);
+
+                (Some(timestamp), None, None)
+            }
+            None => (None, None, None),
+        };
+
+        UuidInfo {
+            version: self.get_version(),
+            version_num,
+            variant,
+            timestamp,
+            clock_seq,
+            node_id,
+        }
+    }
+
+    /// Returns the time between the timestamps of two time-based UUIDs,
+    /// regardless of which is earlier.
+    ///
+    /// Version 1, 6 and 7 UUIDs can be mixed, though the timestamps of
+    /// version 7 UUIDs only have millisecond precision.
+    ///
+    /// Returns `None` if either UUID isn't a time-based UUID with the
+    /// RFC4122 variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use uuid::Uuid;
+    ///
+    /// let request = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+    /// let response = Uuid::parse_str("f4e5c28c-52a1-11e7-802a-010203040506").unwrap();
+    ///
+    /// assert_eq!(request.time_between(&response), Some(Duration::from_secs(2)));
+    /// assert_eq!(response.time_between(&request), Some(Duration::from_secs(2)));
+    ///
+    /// assert_eq!(request.time_between(&Uuid::nil()), None);
+    /// ```
+    pub fn time_between(&self, other: &Uuid) -> Option<Duration> {
+        let nanos = self.time()?.nanos().abs_diff(other.time()?.nanos());
+
+        Some(Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        ))
+    }
+
+    /// Decodes the timestamp of a version 1, 6 or 7 UUID with the RFC4122
+    /// variant.
+    fn time(&self) -> Option<Time> {
+        if self.get_variant() != Some(Variant::RFC4122) {
+            return None;
+        }
+
+        let b = self.as_bytes();
+        let high = b[..6]
+            .iter()
+            .fold(0u64, |high, &byte| high << 8 | u64::from(byte));
+
+        match self.get_version_num() {
+            1 => Some(Time::Ticks(
+                u64::from(b[6] & 0x0f) << 56
+                    | u64::from(b[7]) << 48
+                    | (high & 0xffff) << 32
+                    | high >> 16,
+            )),
+            6 => Some(Time::Ticks(
+                high << 12 | u64::from(b[6] & 0x0f) << 8 | u64::from(b[7]),
+            )),
+            7 => Some(Time::UnixMillis(high)),
+            _ => None,
+        }
+    }
+}
+
+/// The timestamp of a time-based UUID.
+enum Time {
+    /// 100 ns ticks since the UUID epoch, of versions 1 and 6.
+    Ticks(u64),
+    /// Milliseconds since the Unix epoch, of version 7.
+    UnixMillis(u64),
+}
+
+impl Time {
+    /// Returns the nanoseconds since the UUID epoch.
+    fn nanos(&self) -> u128 {
+        match *self {
+            Time::Ticks(ticks) => u128::from(ticks) * 100,
+            Time::UnixMillis(millis) => {
+                u128::from(UUID_TICKS_BETWEEN_EPOCHS) * 100
+                    + u128::from(millis) * 1_000_000
+            }
+        }
+    }
+}
+
+/// Formats seconds and fractional nanoseconds since the Unix epoch as an
+/// ISO-8601 date and time in UTC, like `2017-06-16T14:41:59.000001200Z`.
+struct Iso8601((u64, u32));
+
+impl fmt::Display for Iso8601 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (secs, nanos) = self.0;
+        let days = secs / 86_400;
+        let secs = secs % 86_400;
+
+        // Howard Hinnant's `civil_from_days`, which counts years from
+        // March so that leap days fall at the end of the year
+        let days = days + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days % 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460
+            + day_of_era / 36_524
+            - day_of_era / 146_096)
+            / 365;
+        let day_of_year = day_of_era
+            - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month + 2) / 5 + 1;
+        let month = if month < 10 { month + 3 } else { month - 9 };
+        let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}Z",
+            year,
+            month,
+            day,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60,
+            nanos
+        )
+    }
+}
+
+/// Converts 100 ns ticks since the UUID epoch into seconds and fractional
+/// nanoseconds since the Unix epoch.
+fn unix_from_ticks(ticks: u64) -> Option<(u64, u32)> {
+    let ticks = ticks.checked_sub(UUID_TICKS_BETWEEN_EPOCHS)?;
+
+    Some((ticks / 10_000_000, (ticks % 10_000_000) as u32 * 100))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_util;
+
+    fn parse(s: &str) -> Uuid {
+        Uuid::parse_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_describe_v1() {
+        let info = parse("f3b4958c-52a1-11e7-802a-010203040506").describe();
+
+        assert_eq!(info.version(), Some(Version::Mac));
+        assert_eq!(info.version_num(), 1);
+        assert_eq!(info.unix_timestamp(), Some((1_497_624_119, 1_200)));
+        assert_eq!(info.clock_seq(), Some(0x2a));
+        assert_eq!(info.node_id(), Some([1, 2, 3, 4, 5, 6]));
+
+        // before the Unix epoch
+        let info = parse("00000000-0000-1000-8000-010203040506").describe();
+        assert_eq!(info.unix_timestamp(), None);
+        assert_eq!(info.clock_seq(), Some(0));
+    }
+
+    #[test]
+    fn test_describe_v6() {
+        // the same time as the version 1 UUID above
+        let info = parse("1e752a1f-3b49-658c-802a-010203040506").describe();
+
+        assert_eq!(info.version(), Some(Version::SortMac));
+        assert_eq!(info.unix_timestamp(), Some((1_497_624_119, 1_200)));
+        assert_eq!(info.clock_seq(), Some(0x2a));
+        assert_eq!(info.node_id(), Some([1, 2, 3, 4, 5, 6]));
+    }
+
+    #[test]
+    fn test_describe_v7() {
+        let info = parse("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").describe();
+
+        assert_eq!(info.version(), Some(Version::SortRand));
+        assert_eq!(info.unix_timestamp(), Some((1_645_557_742, 0)));
+        assert_eq!(info.clock_seq(), None);
+        assert_eq!(info.node_id(), None);
+    }
+
+    #[test]
+    fn test_time_between() {
+        use crate::std::time::Duration;
+
+        let v1 = parse("f3b4958c-52a1-11e7-802a-010203040506");
+        let v6 = parse("1e752a1f-3b49-658c-802a-010203040506");
+        let v7 = Builder::from_unix_millis(1_497_624_119_000, [0; 10]).build();
+
+        assert_eq!(v1.time_between(&v1), Some(Duration::from_secs(0)));
+        assert_eq!(v1.time_between(&v6), Some(Duration::from_secs(0)));
+        assert_eq!(v1.time_between(&v7), Some(Duration::from_nanos(1_200)));
+        assert_eq!(v7.time_between(&v6), Some(Duration::from_nanos(1_200)));
+
+        // versions 1 and 6 can hold times before the Unix epoch
+        let epoch = parse("00000000-0000-1000-8000-010203040506");
+        assert_eq!(
+            epoch.time_between(&Builder::from_unix_millis(0, [0; 10]).build()),
+            Some(Duration::from_secs(12_219_292_800))
+        );
+
+        assert_eq!(v1.time_between(&test_util::new()), None);
+        assert_eq!(Uuid::nil().time_between(&v1), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_iso8601() {
+        use super::Iso8601;
+        use crate::std::string::ToString;
+
+        assert_eq!(
+            Iso8601((0, 0)).to_string(),
+            "1970-01-01T00:00:00.000000000Z"
+        );
+        assert_eq!(
+            Iso8601((1_497_624_119, 1_200)).to_string(),
+            "2017-06-16T14:41:59.000001200Z"
+        );
+        assert_eq!(
+            Iso8601((951_782_400, 5)).to_string(),
+            "2000-02-29T00:00:00.000000005Z"
+        );
+        assert_eq!(
+            Iso8601((253_402_300_799, 999_999_999)).to_string(),
+            "9999-12-31T23:59:59.999999999Z"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_debug() {
+        let debug = format!(
+            "{:?}",
+            parse("f3b4958c-52a1-11e7-802a-010203040506").describe()
+        );
+
+        assert!(debug.starts_with("UuidInfo { version: 1, "));
+        assert!(debug.contains("timestamp: 2017-06-16T14:41:59.000001200Z, "));
+        assert!(debug.ends_with("node_id: 0x010203040506 }"));
+
+        let debug = format!("{:?}", test_util::new().describe());
+
+        assert_eq!(debug, "UuidInfo { version: 4, variant: Some(RFC4122) }");
+    }
+
+    #[test]
+    fn test_describe_other() {
+        let info = test_util::new().describe();
+
+        assert_eq!(info.version(), Some(Version::Random));
+        assert_eq!(info.variant(), Some(Variant::RFC4122));
+        assert_eq!(info.unix_timestamp(), None);
+        assert_eq!(info.clock_seq(), None);
+        assert_eq!(info.node_id(), None);
+
+        // a version 1 layout without the RFC4122 variant
+        let info = parse("f3b4958c-52a1-11e7-c02a-010203040506").describe();
+        assert_eq!(info.variant(), Some(Variant::Microsoft));
+        assert_eq!(info.unix_timestamp(), None);
+
+        let info = Uuid::max().describe();
+        assert_eq!(info.version(), Some(Version::Max));
+        assert_eq!(info.version_num(), 15);
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::adapter::{Hyphenated, Simple};
+use crate::prelude::*;
+use crate::std::io;
+
+impl Uuid {
+    /// Reads a UUID from the next 16 bytes of `reader`.
+    ///
+    /// The bytes are read in the same big-endian order as
+    /// [`Uuid::as_bytes`] returns them.
+    ///
+    /// Note that usage of this method requires the `std` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if reading fails, including with
+    /// [`io::ErrorKind::UnexpectedEof`] if there are fewer than 16 bytes
+    /// left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// let mut input = &uuid.as_bytes()[..];
+    ///
+    /// assert_eq!(Uuid::read_from(&mut input).unwrap(), uuid);
+    /// assert!(input.is_empty());
+    /// ```
+    ///
+    /// [`Uuid::as_bytes`]: struct.Uuid.html#method.as_bytes
+    /// [`io::ErrorKind::UnexpectedEof`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.UnexpectedEof
+    pub fn read_from<R: io::Read>(mut reader: R) -> io::Result<Uuid> {
+        let mut bytes = [0; 16];
+
+        reader.read_exact(&mut bytes)?;
+
+        Ok(Uuid::from_bytes(bytes))
+    }
+
+    /// Writes the 16 bytes of the UUID to `writer`.
+    ///
+    /// The bytes are written in the same big-endian order as
+    /// [`Uuid::as_bytes`] returns them, so they can be read back with
+    /// [`Uuid::read_from`].
+    ///
+    /// Note that usage of this method requires the `std` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// let mut output = Vec::new();
+    ///
+    /// uuid.write_to(&mut output).unwrap();
+    ///
+    /// assert_eq!(output, uuid.as_bytes());
+    /// ```
+    ///
+    /// [`Uuid::as_bytes`]: struct.Uuid.html#method.as_bytes
+    /// [`Uuid::read_from`]: struct.Uuid.html#method.read_from
+    pub fn write_to<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.as_bytes())
+    }
+
+    /// Writes the UUID to `writer` as a lower case hyphenated string, like
+    /// `936da01f-9abd-4d9d-80c7-02af85c822a8`.
+    ///
+    /// The string is encoded on the stack and written with a single call
+    /// to [`io::Write::write_all`], without going through `fmt` or
+    /// allocating. Wrap unbuffered writers like files and sockets in an
+    /// [`io::BufWriter`] when writing many UUIDs.
+    ///
+    /// Note that usage of this method requires the `std` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// let mut output = Vec::new();
+    ///
+    /// uuid.write_hyphenated_to(&mut output).unwrap();
+    ///
+    /// assert_eq!(output, b"936da01f-9abd-4d9d-80c7-02af85c822a8");
+    /// ```
+    ///
+    /// [`io::Write::write_all`]: https://doc.rust-lang.org/std/io/trait.Write.html#method.write_all
+    /// [`io::BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+    pub fn write_hyphenated_to<W: io::Write>(
+        &self,
+        mut writer: W,
+    ) -> io::Result<()> {
+        let mut buffer = [0; Hyphenated::LENGTH];
+        let encoded = self.to_hyphenated_ref().encode_lower(&mut buffer);
+
+        writer.write_all(encoded.as_bytes())
+    }
+
+    /// Writes the UUID to `writer` as a lower case string of 32 hex digits,
+    /// like `936da01f9abd4d9d80c702af85c822a8`.
+    ///
+    /// See [`Uuid::write_hyphenated_to`] for details.
+    ///
+    /// Note that usage of this method requires the `std` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// let mut output = Vec::new();
+    ///
+    /// uuid.write_simple_to(&mut output).unwrap();
+    ///
+    /// assert_eq!(output, b"936da01f9abd4d9d80c702af85c822a8");
+    /// ```
+    ///
+    /// [`Uuid::write_hyphenated_to`]: #method.write_hyphenated_to
+    pub fn write_simple_to<W: io::Write>(
+        &self,
+        mut writer: W,
+    ) -> io::Result<()> {
+        let mut buffer = [0; Simple::LENGTH];
+        let encoded = self.to_simple_ref().encode_lower(&mut buffer);
+
+        writer.write_all(encoded.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::std::{io, vec::Vec};
+    use crate::test_util;
+
+    #[test]
+    fn test_read_write_roundtrip() {
+        let mut buffer = Vec::new();
+
+        test_util::new().write_to(&mut buffer).unwrap();
+        test_util::new2().write_to(&mut buffer).unwrap();
+
+        assert_eq!(buffer.len(), 32);
+        assert_eq!(&buffer[..16], test_util::new().as_bytes());
+
+        let mut input = &buffer[..];
+
+        assert_eq!(Uuid::read_from(&mut input).unwrap(), test_util::new());
+        assert_eq!(Uuid::read_from(&mut input).unwrap(), test_util::new2());
+
+        let err = Uuid::read_from(&mut input).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_write_text() {
+        use crate::std::string::ToString;
+
+        let mut output = Vec::new();
+
+        test_util::new().write_hyphenated_to(&mut output).unwrap();
+        output.push(b'\n');
+        test_util::new2().write_simple_to(&mut output).unwrap();
+
+        let expected = test_util::new().to_hyphenated().to_string()
+            + "\n"
+            + &test_util::new2().to_simple().to_string();
+        assert_eq!(output, expected.as_bytes());
+
+        // a full writer reports the error
+        let mut full = [0u8; 10];
+        let err = test_util::new()
+            .write_hyphenated_to(&mut full[..])
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn test_read_short() {
+        let err = Uuid::read_from(&[0u8; 15][..]).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
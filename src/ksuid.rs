@@ -0,0 +1,154 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::prelude::*;
+use crate::v8::V8_BITS;
+
+/// The Unix timestamp in seconds that KSUID timestamps count from.
+const KSUID_EPOCH: u64 = 1_400_000_000;
+
+/// The number of payload bits that fit next to the 32-bit timestamp in a
+/// custom (version 8) UUID.
+const PAYLOAD_BITS: u32 = V8_BITS - 32;
+
+impl Uuid {
+    /// Creates a custom (version 8) UUID from the 20 bytes of a [KSUID].
+    ///
+    /// The UUID holds the 32-bit timestamp of the KSUID followed by the
+    /// first 90 bits of its 128-bit payload. The remaining 38 bits of the
+    /// payload don't fit and are dropped, so different KSUIDs can map to
+    /// the same UUID, but the timestamp always survives and UUIDs sort in
+    /// the same order as the KSUIDs they were created from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let mut ksuid = [0; 20];
+    /// ksuid[..4].copy_from_slice(&[0x0e, 0x6c, 0xe8, 0xa3]);
+    /// ksuid[4..].copy_from_slice(&[0xa5; 16]);
+    ///
+    /// let uuid = Uuid::from_ksuid(&ksuid);
+    ///
+    /// assert_eq!(uuid.get_version_num(), 8);
+    /// assert_eq!(uuid.get_ksuid_timestamp(), Some(1_642_018_467));
+    /// ```
+    ///
+    /// [KSUID]: https://github.com/segmentio/ksuid
+    pub fn from_ksuid(ksuid: &[u8; 20]) -> Uuid {
+        let mut timestamp = [0; 4];
+        let mut payload = [0; 16];
+        timestamp.copy_from_slice(&ksuid[..4]);
+        payload.copy_from_slice(&ksuid[4..]);
+
+        let timestamp = u128::from(u32::from_be_bytes(timestamp));
+        let payload = u128::from_be_bytes(payload) >> (128 - PAYLOAD_BITS);
+
+        Uuid::from_v8_bits(timestamp << PAYLOAD_BITS | payload)
+    }
+
+    /// Returns the KSUID a UUID was created from with
+    /// [`Uuid::from_ksuid`].
+    ///
+    /// The last 38 bits of the payload, which the UUID doesn't hold, are
+    /// zero. Returns `None` if this isn't a custom (version 8) UUID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let mut ksuid = [0; 20];
+    /// ksuid[..4].copy_from_slice(&[0x0e, 0x6c, 0xe8, 0xa3]);
+    /// ksuid[4..15].copy_from_slice(&[0xa5; 11]);
+    /// ksuid[15] = 0x80;
+    ///
+    /// assert_eq!(Uuid::from_ksuid(&ksuid).to_ksuid(), Some(ksuid));
+    /// ```
+    ///
+    /// [`Uuid::from_ksuid`]: #method.from_ksuid
+    pub fn to_ksuid(&self) -> Option<[u8; 20]> {
+        let bits = self.v8_bits()?;
+
+        let timestamp = (bits >> PAYLOAD_BITS) as u32;
+        let payload = bits << (128 - PAYLOAD_BITS);
+
+        let mut ksuid = [0; 20];
+        ksuid[..4].copy_from_slice(&timestamp.to_be_bytes());
+        ksuid[4..].copy_from_slice(&payload.to_be_bytes());
+
+        Some(ksuid)
+    }
+
+    /// Returns the Unix timestamp in seconds of the KSUID a UUID was
+    /// created from with [`Uuid::from_ksuid`].
+    ///
+    /// Returns `None` if this isn't a custom (version 8) UUID.
+    ///
+    /// [`Uuid::from_ksuid`]: #method.from_ksuid
+    pub fn get_ksuid_timestamp(&self) -> Option<u64> {
+        self.v8_bits()
+            .map(|bits| KSUID_EPOCH + (bits >> PAYLOAD_BITS) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_util;
+
+    fn ksuid(timestamp: u32, payload: u128) -> [u8; 20] {
+        let mut ksuid = [0; 20];
+        ksuid[..4].copy_from_slice(&timestamp.to_be_bytes());
+        ksuid[4..].copy_from_slice(&payload.to_be_bytes());
+        ksuid
+    }
+
+    #[test]
+    fn test_ksuid_roundtrip() {
+        let payload = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210;
+        let truncated = payload & !((1 << 38) - 1);
+
+        for &timestamp in &[0, 1, 0x0e71_26a3, u32::MAX] {
+            let uuid = Uuid::from_ksuid(&ksuid(timestamp, payload));
+
+            assert_eq!(uuid.get_version_num(), 8);
+            assert_eq!(uuid.get_variant(), Some(Variant::RFC4122));
+            assert_eq!(
+                uuid.get_ksuid_timestamp(),
+                Some(1_400_000_000 + u64::from(timestamp))
+            );
+            assert_eq!(uuid.to_ksuid(), Some(ksuid(timestamp, truncated)));
+        }
+    }
+
+    #[test]
+    fn test_ksuid_order() {
+        let ksuids = [
+            ksuid(0, u128::MAX),
+            ksuid(1, 0),
+            ksuid(1, 1 << 38),
+            ksuid(1, u128::MAX),
+            ksuid(u32::MAX, 0),
+        ];
+
+        for pair in ksuids.windows(2) {
+            assert!(Uuid::from_ksuid(&pair[0]) < Uuid::from_ksuid(&pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_not_a_ksuid() {
+        assert_eq!(test_util::new().to_ksuid(), None);
+        assert_eq!(Uuid::nil().get_ksuid_timestamp(), None);
+    }
+}
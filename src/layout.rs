@@ -0,0 +1,342 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Custom (version 8) UUIDs made of named fields.
+//!
+//! A version 8 UUID has 122 bits for the application to use, around the
+//! version and variant. [`V8Layout`] describes how they're split into
+//! fields, so packing values into a UUID and reading them back out uses
+//! the same definition.
+//!
+//! [`V8Layout`]: struct.V8Layout.html
+
+use crate::prelude::*;
+use crate::v8::V8_BITS;
+
+/// The largest number of fields in a [`V8Layout`].
+///
+/// [`V8Layout`]: struct.V8Layout.html
+pub const MAX_FIELDS: usize = 16;
+
+/// A named field of a [`V8Layout`].
+///
+/// [`V8Layout`]: struct.V8Layout.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Field {
+    name: &'static str,
+    bits: u32,
+    shift: u32,
+}
+
+impl Field {
+    const EMPTY: Field = Field {
+        name: "",
+        bits: 0,
+        shift: 0,
+    };
+
+    /// Returns the name of the field.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the width of the field in bits.
+    pub const fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    const fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.bits)
+    }
+}
+
+/// The layout of the 122 free bits of a custom (version 8) UUID.
+///
+/// Fields are declared most significant first, so UUIDs sort by the first
+/// field, then by the second, and so on. The bits after the last field are
+/// filled with random bits, or whatever else is passed to
+/// [`V8Layout::build`].
+///
+/// Layouts are built with `const fn`s, so they can be declared as
+/// constants, and a layout that doesn't fit fails to compile.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::{layout::V8Layout, Version};
+///
+/// const ORDER_ID: V8Layout = V8Layout::new()
+///     .field("tenant", 16)
+///     .field("kind", 8)
+///     .field("millis", 48);
+///
+/// # let random = 0x1234_5678_9abc_def0;
+/// let uuid = ORDER_ID.build(&[42, 3, 1_600_000_000_000], random)?;
+///
+/// assert_eq!(uuid.get_version(), Some(Version::Custom));
+/// assert_eq!(ORDER_ID.get(&uuid, "tenant"), Some(42));
+/// assert_eq!(ORDER_ID.get(&uuid, "millis"), Some(1_600_000_000_000));
+/// assert_eq!(ORDER_ID.random_bits(), 50);
+/// # Ok::<(), uuid::Error>(())
+/// ```
+///
+/// [`V8Layout::build`]: #method.build
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct V8Layout {
+    fields: [Field; MAX_FIELDS],
+    len: usize,
+    used: u32,
+}
+
+impl V8Layout {
+    /// Creates a layout without any fields, where all 122 bits are random.
+    pub const fn new() -> Self {
+        V8Layout {
+            fields: [Field::EMPTY; MAX_FIELDS],
+            len: 0,
+            used: 0,
+        }
+    }
+
+    /// Adds a field of `bits` bits after the fields declared so far.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is 0 or more than 64, if the fields would take more
+    /// than 122 bits, if the layout already has [`MAX_FIELDS`] fields, or
+    /// if it already has a field called `name`. In a constant, these are
+    /// compile errors.
+    ///
+    /// [`MAX_FIELDS`]: constant.MAX_FIELDS.html
+    pub const fn field(mut self, name: &'static str, bits: u32) -> Self {
+        assert!(bits > 0 && bits <= 64, "fields must be 1 to 64 bits wide");
+        assert!(
+            self.used + bits <= V8_BITS,
+            "the fields don't fit into the 122 bits of a UUID"
+        );
+        assert!(self.len < MAX_FIELDS, "the layout has too many fields");
+
+        let mut i = 0;
+        while i < self.len {
+            assert!(
+                !str_eq(self.fields[i].name, name),
+                "the layout already has a field with this name"
+            );
+            i += 1;
+        }
+
+        self.used += bits;
+        self.fields[self.len] = Field {
+            name,
+            bits,
+            shift: V8_BITS - self.used,
+        };
+        self.len += 1;
+
+        self
+    }
+
+    /// Returns the fields of the layout, in the order they were declared.
+    pub fn fields(&self) -> &[Field] {
+        &self.fields[..self.len]
+    }
+
+    /// Returns the number of bits after the last field.
+    pub const fn random_bits(&self) -> u32 {
+        V8_BITS - self.used
+    }
+
+    /// Creates a UUID from the values of the fields, in the order they were
+    /// declared, followed by the lower [`V8Layout::random_bits`] bits of
+    /// `random`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `values` doesn't have one
+    /// value for each field, or if a value doesn't fit into its field.
+    ///
+    /// [`V8Layout::random_bits`]: #method.random_bits
+    pub fn build(
+        &self,
+        values: &[u64],
+        random: u128,
+    ) -> Result<Uuid, crate::Error> {
+        if values.len() != self.len {
+            return Err(crate::Error::invalid_field_count(
+                self.len,
+                values.len(),
+            ));
+        }
+
+        let random_bits = self.random_bits();
+        let mut bits = if random_bits == 0 {
+            0
+        } else {
+            random & (u128::MAX >> (128 - random_bits))
+        };
+
+        for (field, &value) in self.fields().iter().zip(values) {
+            if value & !field.mask() != 0 {
+                return Err(crate::Error::field_overflow(
+                    field.name, field.bits,
+                ));
+            }
+
+            bits |= u128::from(value) << field.shift;
+        }
+
+        Ok(Uuid::from_v8_bits(bits))
+    }
+
+    /// Returns the value of the field called `name`.
+    ///
+    /// Returns `None` if the layout has no such field, or if `uuid` isn't a
+    /// custom (version 8) UUID.
+    pub fn get(&self, uuid: &Uuid, name: &str) -> Option<u64> {
+        let field = self.fields().iter().find(|field| field.name == name)?;
+
+        Some((uuid.v8_bits()? >> field.shift) as u64 & field.mask())
+    }
+
+    /// Returns the bits after the last field.
+    ///
+    /// Returns `None` if `uuid` isn't a custom (version 8) UUID.
+    pub fn random(&self, uuid: &Uuid) -> Option<u128> {
+        let random_bits = self.random_bits();
+        if random_bits == 0 {
+            return uuid.v8_bits().map(|_| 0);
+        }
+
+        Some(uuid.v8_bits()? & (u128::MAX >> (128 - random_bits)))
+    }
+}
+
+impl Default for V8Layout {
+    fn default() -> Self {
+        V8Layout::new()
+    }
+}
+
+/// Compares two strings in a `const fn`.
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util, ErrorKind};
+
+    const LAYOUT: V8Layout = V8Layout::new()
+        .field("tenant", 16)
+        .field("kind", 8)
+        .field("millis", 48);
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(LAYOUT.fields().len(), 3);
+        assert_eq!(LAYOUT.fields()[1].name(), "kind");
+        assert_eq!(LAYOUT.fields()[1].bits(), 8);
+        assert_eq!(LAYOUT.random_bits(), 50);
+
+        assert_eq!(V8Layout::default().random_bits(), 122);
+    }
+
+    #[test]
+    fn test_build_and_get() {
+        let uuid = LAYOUT.build(&[0xabcd, 0xef, 0x0123_4567_89ab], !0).unwrap();
+
+        assert_eq!(
+            uuid,
+            Uuid::parse_str("abcdef01-2345-8678-a6af-ffffffffffff").unwrap()
+        );
+        assert_eq!(LAYOUT.get(&uuid, "tenant"), Some(0xabcd));
+        assert_eq!(LAYOUT.get(&uuid, "kind"), Some(0xef));
+        assert_eq!(LAYOUT.get(&uuid, "millis"), Some(0x0123_4567_89ab));
+        assert_eq!(LAYOUT.get(&uuid, "missing"), None);
+        assert_eq!(LAYOUT.random(&uuid), Some((1 << 50) - 1));
+
+        assert_eq!(LAYOUT.get(&test_util::new(), "tenant"), None);
+        assert_eq!(LAYOUT.random(&test_util::new()), None);
+    }
+
+    #[test]
+    fn test_sorts_by_fields() {
+        let a = LAYOUT.build(&[1, u64::from(u8::MAX), 0], !0).unwrap();
+        let b = LAYOUT.build(&[2, 0, 0], 0).unwrap();
+
+        assert!(a < b);
+    }
+
+    #[test]
+    fn test_full_layout() {
+        let layout = V8Layout::new().field("a", 64).field("b", 58);
+        let uuid = layout.build(&[u64::MAX, (1 << 58) - 1], 0).unwrap();
+
+        assert_eq!(
+            uuid,
+            Uuid::parse_str("ffffffff-ffff-8fff-bfff-ffffffffffff").unwrap()
+        );
+        assert_eq!(layout.get(&uuid, "a"), Some(u64::MAX));
+        assert_eq!(layout.random(&uuid), Some(0));
+    }
+
+    #[test]
+    fn test_build_invalid() {
+        assert_eq!(
+            LAYOUT.build(&[1, 2], 0).unwrap_err().kind(),
+            ErrorKind::InvalidFieldCount {
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            LAYOUT.build(&[1, 256, 3], 0).unwrap_err().kind(),
+            ErrorKind::FieldOverflow {
+                field: "kind",
+                bits: 8
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_field_too_wide() {
+        let _ = V8Layout::new().field("a", 64).field("b", 59);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_field() {
+        let _ = V8Layout::new().field("tenant", 16).field("tenant", 8);
+    }
+
+    #[test]
+    fn test_str_eq() {
+        assert!(str_eq("kind", "kind"));
+        assert!(str_eq("", ""));
+        assert!(!str_eq("kind", "kinds"));
+        assert!(!str_eq("kind", "mind"));
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A lazily parsed [`Uuid`].
+//!
+//! [`Uuid`]: ../struct.Uuid.html
+
+use crate::prelude::*;
+use crate::std::{cell::Cell, fmt};
+
+/// A [`Uuid`] that is only parsed when it's first needed.
+///
+/// Request handlers often receive IDs as strings and only log them, so
+/// parsing every ID up front is wasted work. A `LazyUuid` holds on to the
+/// borrowed string and parses it on the first call to [`LazyUuid::get`],
+/// caching the result for later calls. Formatting a `LazyUuid` that hasn't
+/// been parsed writes the original string without parsing it.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::lazy::LazyUuid;
+/// use uuid::Uuid;
+///
+/// let id = LazyUuid::new("936DA01F9ABD4d9d80C702AF85C822A8");
+///
+/// // formatting doesn't parse
+/// assert_eq!(id.to_string(), "936DA01F9ABD4d9d80C702AF85C822A8");
+/// assert!(!id.is_parsed());
+///
+/// let uuid = id.get().unwrap();
+///
+/// assert!(id.is_parsed());
+/// assert_eq!(uuid, Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap());
+/// ```
+///
+/// [`Uuid`]: ../struct.Uuid.html
+/// [`LazyUuid::get`]: #method.get
+#[derive(Clone, Debug)]
+pub struct LazyUuid<'a> {
+    input: &'a str,
+    state: Cell<State>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum State {
+    Unparsed,
+    Valid(Uuid),
+    Invalid,
+}
+
+impl<'a> LazyUuid<'a> {
+    /// Creates a `LazyUuid` that will parse `input` when it's needed.
+    ///
+    /// Any of the formats accepted by [`Uuid::parse_str`] are supported.
+    ///
+    /// [`Uuid::parse_str`]: ../struct.Uuid.html#method.parse_str
+    pub const fn new(input: &'a str) -> Self {
+        LazyUuid {
+            input,
+            state: Cell::new(State::Unparsed),
+        }
+    }
+
+    /// Creates a `LazyUuid` from an already parsed [`Uuid`].
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    pub const fn from_uuid(uuid: Uuid) -> Self {
+        LazyUuid {
+            input: "",
+            state: Cell::new(State::Valid(uuid)),
+        }
+    }
+
+    /// Returns the parsed [`Uuid`], parsing it first if needed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the string isn't a valid
+    /// UUID. Invalid strings are parsed again on every call to produce the
+    /// error, since errors are expected to be rare.
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    pub fn get(&self) -> Result<Uuid, crate::Error> {
+        match self.state.get() {
+            State::Valid(uuid) => Ok(uuid),
+            State::Unparsed | State::Invalid => {
+                let result = Uuid::parse_str(self.input);
+
+                self.state.set(match result {
+                    Ok(uuid) => State::Valid(uuid),
+                    Err(_) => State::Invalid,
+                });
+
+                result
+            }
+        }
+    }
+
+    /// Tests if the UUID has been parsed, successfully or not.
+    pub fn is_parsed(&self) -> bool {
+        self.state.get() != State::Unparsed
+    }
+
+    /// Returns the string the `LazyUuid` was created from, if any.
+    pub fn as_input(&self) -> Option<&'a str> {
+        match self.state.get() {
+            State::Valid(_) if self.input.is_empty() => None,
+            _ => Some(self.input),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for LazyUuid<'a> {
+    fn from(input: &'a str) -> Self {
+        LazyUuid::new(input)
+    }
+}
+
+impl From<Uuid> for LazyUuid<'_> {
+    fn from(uuid: Uuid) -> Self {
+        LazyUuid::from_uuid(uuid)
+    }
+}
+
+impl fmt::Display for LazyUuid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.as_input() {
+            Some(input) => f.write_str(input),
+            None => match self.state.get() {
+                State::Valid(uuid) => fmt::Display::fmt(&uuid, f),
+                _ => Ok(()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std::string::ToString;
+    use crate::test_util;
+
+    #[test]
+    fn test_lazy_parse() {
+        let lazy = LazyUuid::new("F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4");
+
+        assert!(!lazy.is_parsed());
+        assert_eq!(lazy.to_string(), "F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4");
+        assert!(!lazy.is_parsed());
+
+        assert_eq!(lazy.get().unwrap(), test_util::new());
+        assert!(lazy.is_parsed());
+        assert_eq!(lazy.get().unwrap(), test_util::new());
+        assert_eq!(
+            lazy.as_input(),
+            Some("F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4")
+        );
+    }
+
+    #[test]
+    fn test_lazy_invalid() {
+        let lazy = LazyUuid::from("not a uuid");
+
+        assert!(lazy.get().is_err());
+        assert!(lazy.is_parsed());
+        assert_eq!(lazy.get(), Uuid::parse_str("not a uuid"));
+        assert_eq!(lazy.to_string(), "not a uuid");
+    }
+
+    #[test]
+    fn test_from_uuid() {
+        let lazy = LazyUuid::from(test_util::new());
+
+        assert!(lazy.is_parsed());
+        assert_eq!(lazy.get().unwrap(), test_util::new());
+        assert_eq!(lazy.as_input(), None);
+        assert_eq!(lazy.to_string(), test_util::new().to_string());
+    }
+}
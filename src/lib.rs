@@ -33,15 +33,51 @@
 //!
 //! * `v1` - adds the [`Uuid::new_v1`] function and the ability to create a V1
 //!   using an implementation of [`v1::ClockSequence`] (usually
-//! [`v1::Context`]) and a timestamp from `time::timespec`.
+//!   [`v1::Context`]) and a timestamp from `time::timespec`.
 //! * `v3` - adds the [`Uuid::new_v3`] function and the ability to create a V3
 //!   UUID based on the MD5 hash of some data.
 //! * `v4` - adds the [`Uuid::new_v4`] function and the ability to randomly
 //!   generate a UUID.
 //! * `v5` - adds the [`Uuid::new_v5`] function and the ability to create a V5
 //!   UUID based on the SHA1 hash of some data.
+//!
+//!   Versions 3 and 5 can also be generated with another implementation of
+//!   MD5 or SHA-1, without these features, see [`namespace::Digest`].
+//! * `v8-sha256` - adds the [`Uuid::new_v8_sha256`] function and the ability
+//!   to create a custom V8 UUID based on the SHA-256 hash of some data, for
+//!   name-based UUIDs where SHA-1 can't be used.
 //! * `serde` - adds the ability to serialize and deserialize a UUID using the
-//!   `serde` crate.
+//!   `serde` crate. Human-readable formats, such as JSON, use the hyphenated
+//!   string, while binary formats, such as bincode, use the 16 raw bytes.
+//!   Neither direction allocates, so this feature can be used without `std`
+//!   or `alloc`, with formats like [`postcard`].
+//! * `cli` - builds a `uuid` binary that generates version 1 and 4 UUIDs
+//!   and prints the fields of existing ones, as in `uuid v4 --count 3` or
+//!   `uuid inspect 936da01f-9abd-4d9d-80c7-02af85c822a8`.
+//! * `cose` - adds helpers for using a UUID as a COSE `kid` value.
+//! * `cursor` - adds opaque page tokens for keyset pagination over UUID
+//!   keys. The `cursor-hmac` feature adds support for signing them.
+//! * `db-key` - adds helpers for using a UUID as a key in ordered key-value
+//!   stores.
+//! * `ffi` - adds a libuuid-style C interface.
+//! * `privacy` - adds encryption of UUIDs that are shown to third parties,
+//!   so version 1 and 6 UUIDs don't reveal when and where they were
+//!   created.
+//! * `simd` - parses and formats simple and hyphenated strings with SSE2
+//!   or AVX2 on x86 and x86_64 targets, and with NEON on aarch64 targets.
+//!   On x86, with `std`, the fastest backend the CPU supports is picked at
+//!   runtime, otherwise the target features enabled at compile time decide.
+//!   Other targets and the `no-unsafe` feature use the scalar
+//!   implementation.
+//! * `ulid` - adds bit for bit conversions between UUIDs and ULIDs.
+//! * `test-util` - adds a mock clock and a seeded random number generator
+//!   for asserting on exact UUID values in tests.
+//! * `no-unsafe` - builds the crate with `#![forbid(unsafe_code)]`, using
+//!   safe equivalents of the internal fast paths. The APIs that can't be
+//!   written without `unsafe` are left out: the functions working with raw
+//!   pointers ([`Uuid::from_ptr`] and [`Uuid::slice_from_raw_parts`]), the
+//!   zero-copy views ([`Uuid::from_bytes_ref`] and [`Uuid::from_bytes_mut`]),
+//!   `NonNilUuid::new_unchecked`, `v1::FileContext` and the `ffi` module.
 //!
 //! You need to enable one of the following Cargo features together with
 //! `v3`, `v4` or `v5` feature if you're targeting `wasm32-unknown-unknown`
@@ -115,6 +151,7 @@
 //! * [RFC4122: A Universally Unique IDentifier (UUID) URN
 //!   Namespace](http://tools.ietf.org/html/rfc4122)
 //!
+//! [`postcard`]: https://crates.io/crates/postcard
 //! [`wasm-bindgen`]: https://crates.io/crates/wasm-bindgen
 //! [`cargo-web`]: https://crates.io/crates/cargo-web
 //! [`stdweb`]: https://crates.io/crates/stdweb
@@ -123,11 +160,18 @@
 //! [`Uuid::new_v3`]: struct.Uuid.html#method.new_v3
 //! [`Uuid::new_v4`]: struct.Uuid.html#method.new_v4
 //! [`Uuid::new_v5`]: struct.Uuid.html#method.new_v5
+//! [`namespace::Digest`]: namespace/trait.Digest.html
+//! [`Uuid::new_v8_sha256`]: struct.Uuid.html#method.new_v8_sha256
+//! [`Uuid::from_ptr`]: struct.Uuid.html#method.from_ptr
+//! [`Uuid::slice_from_raw_parts`]: struct.Uuid.html#method.slice_from_raw_parts
+//! [`Uuid::from_bytes_ref`]: struct.Uuid.html#method.from_bytes_ref
+//! [`Uuid::from_bytes_mut`]: struct.Uuid.html#method.from_bytes_mut
 //! [`v1::ClockSequence`]: v1/trait.ClockSequence.html
 //! [`v1::Context`]: v1/struct.Context.html
 
 #![no_std]
 #![deny(missing_debug_implementations, missing_docs)]
+#![cfg_attr(feature = "no-unsafe", forbid(unsafe_code))]
 #![doc(
     html_logo_url = "https://www.rust-lang.org/logos/rust-logo-128x128-blk-v2.png",
     html_favicon_url = "https://www.rust-lang.org/favicon.ico",
@@ -142,21 +186,109 @@ extern crate std;
 #[macro_use]
 extern crate core as std;
 
+#[macro_use]
+mod macros;
+
 mod builder;
 mod error;
 mod parser;
 mod prelude;
 
 pub mod adapter;
+#[cfg(feature = "std")]
+pub mod arena;
+#[cfg(feature = "cursor")]
+pub mod cursor;
+#[cfg(feature = "db-key")]
+pub mod db_key;
+pub mod display_code;
+pub mod entropy;
+#[cfg(all(feature = "ffi", not(feature = "no-unsafe")))]
+pub mod ffi;
+pub mod generator;
+pub mod guid;
+pub mod info;
+pub mod layout;
+pub mod lazy;
+#[cfg(feature = "test-util")]
+pub mod mock;
+pub mod mongodb;
+pub mod namespace;
+#[cfg(all(feature = "v1", feature = "std"))]
+pub mod node;
+#[cfg(all(feature = "v1", feature = "std"))]
+pub mod node_probe;
+pub mod non_nil;
+#[cfg(all(feature = "v4", feature = "std", target_has_atomic = "64"))]
+pub mod pool;
+#[cfg(feature = "privacy")]
+pub mod privacy;
+pub mod range;
+pub mod time_ordered;
+pub mod typed;
 #[cfg(feature = "v1")]
 pub mod v1;
 
+/// Modules for use with `#[serde(with = "...")]` to pick the
+/// representation of a [`Uuid`] field.
+///
+/// Note that you need feature `serde` in order to use this module.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_derive::{Deserialize, Serialize};
+/// use uuid::Uuid;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Order {
+///     #[serde(with = "uuid::serde::simple")]
+///     id: Uuid,
+///     #[serde(with = "uuid::serde::braced")]
+///     customer: Uuid,
+/// }
+///
+/// let order = Order {
+///     id: Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap(),
+///     customer: Uuid::nil(),
+/// };
+///
+/// assert_eq!(
+///     serde_json::to_string(&order).unwrap(),
+///     r#"{"id":"936da01f9abd4d9d80c702af85c822a8","customer":"{00000000-0000-0000-0000-000000000000}"}"#,
+/// );
+/// ```
+///
+/// [`Uuid`]: ../struct.Uuid.html
+#[cfg(feature = "serde")]
+pub mod serde {
+    pub use crate::adapter::compact;
+    pub use crate::serde_support::{braced, simple, uppercase, urn};
+}
+
+mod base32;
+mod base58;
+mod base64url;
+mod bucket;
+#[cfg(any(feature = "v3", feature = "v5"))]
+mod const_hash;
+#[cfg(feature = "cose")]
+mod cose;
+#[cfg(feature = "std")]
+mod io_support;
+mod ksuid;
+mod ops;
+mod packed;
 #[cfg(feature = "serde")]
 mod serde_support;
 #[cfg(feature = "slog")]
 mod slog_support;
+mod snowflake;
 #[cfg(test)]
 mod test_util;
+mod tombstone;
+#[cfg(feature = "ulid")]
+mod ulid;
 #[cfg(all(
     feature = "v3",
     any(
@@ -193,18 +325,36 @@ mod v4;
     )
 ))]
 mod v5;
+mod v8;
 #[cfg(all(windows, feature = "winapi"))]
 mod winapi_support;
 
-use crate::std::{fmt, str};
+use crate::std::{borrow::Borrow, cmp::Ordering, convert::TryFrom, fmt, str};
 
-pub use crate::{builder::Builder, error::Error};
+pub use crate::{
+    builder::Builder,
+    error::{Error, ErrorKind},
+};
+
+#[doc(hidden)]
+pub use crate::macros::InvalidLiteral as __InvalidLiteral;
 
 /// A 128-bit (16 byte) buffer containing the ID.
 pub type Bytes = [u8; 16];
 
+/// A buffer that's long enough for any of the `encode_...` calls.
+///
+/// See [`Uuid::encode_buffer`].
+///
+/// [`Uuid::encode_buffer`]: struct.Uuid.html#method.encode_buffer
+pub type EncodeBuffer = [u8; adapter::Urn::LENGTH];
+
 /// The version of the UUID, denoting the generating algorithm.
+///
+/// New versions may be added in the future, so code matching on a
+/// `Version` needs a wildcard arm.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Version {
     /// Special case for `nil` UUID.
     Nil = 0,
@@ -218,6 +368,15 @@ pub enum Version {
     Random,
     /// Version 5: SHA-1 hash.
     Sha1,
+    /// Version 6: Sortable MAC address, the fields of version 1 reordered.
+    SortMac,
+    /// Version 7: Sortable random, a Unix timestamp followed by random
+    /// bits.
+    SortRand,
+    /// Version 8: Custom, a layout defined by the application.
+    Custom,
+    /// Special case for `max` UUID.
+    Max = 0xff,
 }
 
 /// The reserved variants of UUIDs.
@@ -234,10 +393,26 @@ pub enum Variant {
 }
 
 /// A Universally Unique Identifier (UUID).
-#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+///
+/// # Layout
+///
+/// `Uuid` is guaranteed to have the same size, alignment and ABI as
+/// [`Bytes`] (`[u8; 16]`), so a `uint8_t[16]` from C can be used in its
+/// place and a buffer of UUIDs can be reinterpreted without copying. See
+/// [`Uuid::from_ptr`] and [`Uuid::slice_from_raw_parts`].
+///
+/// [`Bytes`]: type.Bytes.html
+/// [`Uuid::from_ptr`]: #method.from_ptr
+/// [`Uuid::slice_from_raw_parts`]: #method.slice_from_raw_parts
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
 #[repr(transparent)]
 pub struct Uuid(Bytes);
 
+// The raw pointer casts on `Uuid` rely on it being layout-compatible with
+// `Bytes`, so make sure that can't change without a compile error.
+const _: [(); 16] = [(); crate::std::mem::size_of::<Uuid>()];
+const _: [(); 1] = [(); crate::std::mem::align_of::<Uuid>()];
+
 impl Uuid {
     /// UUID namespace for Domain Name System (DNS).
     pub const NAMESPACE_DNS: Self = Uuid([
@@ -268,20 +443,53 @@ impl Uuid {
     /// This determines the interpretation of the structure of the UUID.
     /// Currently only the RFC4122 variant is generated by this module.
     ///
+    /// Every UUID has one of the four variants, so this never returns
+    /// `None`. The max UUID, for example, has the [`Variant::Future`]
+    /// variant.
+    ///
     /// * [Variant Reference](http://tools.ietf.org/html/rfc4122#section-4.1.1)
+    ///
+    /// [`Variant::Future`]: enum.Variant.html#variant.Future
     pub fn get_variant(&self) -> Option<Variant> {
-        match self.as_bytes()[8] {
-            x if x & 0x80 == 0x00 => Some(Variant::NCS),
-            x if x & 0xc0 == 0x80 => Some(Variant::RFC4122),
-            x if x & 0xe0 == 0xc0 => Some(Variant::Microsoft),
-            x if x & 0xe0 == 0xe0 => Some(Variant::Future),
-            _ => None,
-        }
+        Some(match self.as_bytes()[8] >> 5 {
+            0b000..=0b011 => Variant::NCS,
+            0b100 | 0b101 => Variant::RFC4122,
+            0b110 => Variant::Microsoft,
+            _ => Variant::Future,
+        })
+    }
+
+    /// Returns the three most significant bits of the `clock_seq_hi`
+    /// field, which hold the variant of the UUID.
+    ///
+    /// Only as many of the bits as the variant needs are significant:
+    /// `0b0xx` is the NCS variant, `0b10x` the RFC4122 variant, `0b110`
+    /// Microsoft's and `0b111` is reserved for the future. Unlike
+    /// [`Uuid::get_variant`], the bits that are free for other uses are
+    /// returned as they are, which is useful for classifying malformed
+    /// UUIDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9dC0C702AF85C822A8").unwrap();
+    ///
+    /// assert_eq!(uuid.get_variant_bits(), 0b110);
+    /// assert_eq!(Uuid::max().get_variant_bits(), 0b111);
+    /// ```
+    ///
+    /// [`Uuid::get_variant`]: #method.get_variant
+    pub const fn get_variant_bits(&self) -> u8 {
+        self.as_bytes()[8] >> 5
     }
 
     /// Returns the version number of the UUID.
     ///
     /// This represents the algorithm used to generate the contents.
+    /// Unlike [`Uuid::get_version`], this returns the four version bits as
+    /// they are, even for reserved version numbers.
     ///
     /// Currently only the Random (V4) algorithm is supported by this
     /// module.  There are security and privacy implications for using
@@ -290,13 +498,29 @@ impl Uuid {
     /// details.
     ///
     /// * [Version Reference](http://tools.ietf.org/html/rfc4122#section-4.1.3)
+    ///
+    /// [`Uuid::get_version`]: #method.get_version
     pub const fn get_version_num(&self) -> usize {
         (self.as_bytes()[6] >> 4) as usize
     }
 
     /// Returns the version of the UUID.
     ///
-    /// This represents the algorithm used to generate the contents
+    /// This represents the algorithm used to generate the contents. The
+    /// version numbers 1 to 8 are recognized, as well as the nil and max
+    /// UUIDs. Other version numbers are reserved, so `None` is returned
+    /// for them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{Uuid, Version};
+    ///
+    /// let uuid = Uuid::parse_str("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").unwrap();
+    ///
+    /// assert_eq!(uuid.get_version(), Some(Version::SortRand));
+    /// assert_eq!(Uuid::max().get_version(), Some(Version::Max));
+    /// ```
     pub fn get_version(&self) -> Option<Version> {
         let v = self.as_bytes()[6] >> 4;
         match v {
@@ -306,6 +530,10 @@ impl Uuid {
             3 => Some(Version::Md5),
             4 => Some(Version::Random),
             5 => Some(Version::Sha1),
+            6 => Some(Version::SortMac),
+            7 => Some(Version::SortRand),
+            8 => Some(Version::Custom),
+            0xf if self.is_max() => Some(Version::Max),
             _ => None,
         }
     }
@@ -361,9 +589,7 @@ impl Uuid {
         let d3 =
             u16::from(self.as_bytes()[6]) << 8 | u16::from(self.as_bytes()[7]);
 
-        let d4: &[u8; 8] =
-            unsafe { &*(self.as_bytes()[8..16].as_ptr() as *const [u8; 8]) };
-        (d1, d2, d3, d4)
+        (d1, d2, d3, self.d4())
     }
 
     /// Returns the four field values of the UUID in little-endian order.
@@ -399,9 +625,18 @@ impl Uuid {
         let d3 =
             u16::from(self.as_bytes()[6]) | u16::from(self.as_bytes()[7]) << 8;
 
-        let d4: &[u8; 8] =
-            unsafe { &*(self.as_bytes()[8..16].as_ptr() as *const [u8; 8]) };
-        (d1, d2, d3, d4)
+        (d1, d2, d3, self.d4())
+    }
+
+    #[cfg(not(feature = "no-unsafe"))]
+    fn d4(&self) -> &[u8; 8] {
+        unsafe { &*(self.as_bytes()[8..16].as_ptr() as *const [u8; 8]) }
+    }
+
+    #[cfg(feature = "no-unsafe")]
+    fn d4(&self) -> &[u8; 8] {
+        let [_, _, _, _, _, _, _, _, ref d4 @ ..] = self.0;
+        d4
     }
 
     /// Returns a 128bit value containing the UUID data.
@@ -477,168 +712,1126 @@ impl Uuid {
             | u128::from(self.as_bytes()[15]) << 120
     }
 
-    /// Returns an array of 16 octets containing the UUID data.
-    pub const fn as_bytes(&self) -> &Bytes {
-        &self.0
-    }
+    /// Returns two 64bit values containing the UUID data.
+    ///
+    /// The first value holds the first 8 bytes of the UUID and the second
+    /// value the last 8 bytes, each packed in big-endian order like
+    /// [`Uuid::as_u128`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F-9ABD-4D9D-80C7-02AF85C822A8").unwrap();
+    /// assert_eq!(
+    ///     uuid.as_u64_pair(),
+    ///     (0x936DA01F9ABD4D9D, 0x80C702AF85C822A8),
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::as_u128`]: #method.as_u128
+    pub fn as_u64_pair(&self) -> (u64, u64) {
+        let value = self.as_u128();
 
-    /// Tests if the UUID is nil.
-    pub fn is_nil(&self) -> bool {
-        self.as_bytes().iter().all(|&b| b == 0)
+        ((value >> 64) as u64, value as u64)
     }
 
-    /// A buffer that can be used for `encode_...` calls, that is
-    /// guaranteed to be long enough for any of the adapters.
+    /// Returns four 32bit words containing the UUID data.
+    ///
+    /// Each word holds 4 bytes of the UUID in big-endian order, and the
+    /// words are in the order of the bytes, so the first word is the first
+    /// group of the hyphenated string. This doesn't depend on the
+    /// endianness of the target. [`Uuid::from_u32x4`] is the inverse.
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```
     /// use uuid::Uuid;
     ///
-    /// let uuid = Uuid::nil();
+    /// let uuid = Uuid::parse_str("936DA01F-9ABD-4D9D-80C7-02AF85C822A8").unwrap();
     ///
     /// assert_eq!(
-    ///     uuid.to_simple().encode_lower(&mut Uuid::encode_buffer()),
-    ///     "00000000000000000000000000000000"
+    ///     uuid.as_u32x4(),
+    ///     [0x936DA01F, 0x9ABD4D9D, 0x80C702AF, 0x85C822A8],
     /// );
+    /// ```
     ///
-    /// assert_eq!(
-    ///     uuid.to_hyphenated()
-    ///         .encode_lower(&mut Uuid::encode_buffer()),
-    ///     "00000000-0000-0000-0000-000000000000"
-    /// );
+    /// [`Uuid::from_u32x4`]: #method.from_u32x4
+    pub const fn as_u32x4(&self) -> [u32; 4] {
+        let b = &self.0;
+        let mut words = [0; 4];
+
+        let mut i = 0;
+        while i < 4 {
+            words[i] = u32::from_be_bytes([
+                b[i * 4],
+                b[i * 4 + 1],
+                b[i * 4 + 2],
+                b[i * 4 + 3],
+            ]);
+            i += 1;
+        }
+
+        words
+    }
+
+    /// Returns eight 16bit words containing the UUID data.
+    ///
+    /// Like [`Uuid::as_u32x4`], each word holds 2 bytes of the UUID in
+    /// big-endian order, and the words are in the order of the bytes.
+    /// [`Uuid::from_u16x8`] is the inverse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F-9ABD-4D9D-80C7-02AF85C822A8").unwrap();
     ///
     /// assert_eq!(
-    ///     uuid.to_urn().encode_lower(&mut Uuid::encode_buffer()),
-    ///     "urn:uuid:00000000-0000-0000-0000-000000000000"
+    ///     uuid.as_u16x8(),
+    ///     [0x936D, 0xA01F, 0x9ABD, 0x4D9D, 0x80C7, 0x02AF, 0x85C8, 0x22A8],
     /// );
     /// ```
-    pub const fn encode_buffer() -> [u8; adapter::Urn::LENGTH] {
-        [0; adapter::Urn::LENGTH]
-    }
-}
+    ///
+    /// [`Uuid::as_u32x4`]: #method.as_u32x4
+    /// [`Uuid::from_u16x8`]: #method.from_u16x8
+    pub const fn as_u16x8(&self) -> [u16; 8] {
+        let b = &self.0;
+        let mut words = [0; 8];
+
+        let mut i = 0;
+        while i < 8 {
+            words[i] = u16::from_be_bytes([b[i * 2], b[i * 2 + 1]]);
+            i += 1;
+        }
 
-impl fmt::Debug for Uuid {
-    #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::LowerHex::fmt(self, f)
+        words
     }
-}
 
-impl fmt::Display for Uuid {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::LowerHex::fmt(self, f)
+    /// Returns a key whose lexicographic byte order matches the time order of
+    /// time-based UUIDs.
+    ///
+    /// Version 1 UUIDs store the low bits of their timestamp first, so
+    /// sorting their bytes doesn't sort them by creation time. For these,
+    /// the timestamp fields are rearranged most significant first, the way
+    /// version 6 UUIDs lay them out, keeping the version number in its
+    /// usual place. Versions 6 and 7 are already time-ordered, and all other
+    /// UUIDs are returned unchanged, so the key can be used with external
+    /// sorters and byte-wise database comparators directly.
+    ///
+    /// The original UUID can be recovered with [`Uuid::from_sort_key`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let earlier = Uuid::parse_str("ffffffff-0000-11e7-8000-010203040506").unwrap();
+    /// let later = Uuid::parse_str("00000000-0001-11e7-8000-010203040506").unwrap();
+    ///
+    /// assert!(earlier.as_bytes() > later.as_bytes());
+    /// assert!(earlier.sort_key() < later.sort_key());
+    /// ```
+    ///
+    /// [`Uuid::from_sort_key`]: #method.from_sort_key
+    pub fn sort_key(&self) -> Bytes {
+        if self.get_version_num() != 1 {
+            return self.0;
+        }
+
+        let b = self.as_bytes();
+        let ticks = u64::from(b[6] & 0x0F) << 56
+            | u64::from(b[7]) << 48
+            | u64::from(b[4]) << 40
+            | u64::from(b[5]) << 32
+            | u64::from(b[0]) << 24
+            | u64::from(b[1]) << 16
+            | u64::from(b[2]) << 8
+            | u64::from(b[3]);
+
+        let mut key = *b;
+
+        key[0] = (ticks >> 52) as u8;
+        key[1] = (ticks >> 44) as u8;
+        key[2] = (ticks >> 36) as u8;
+        key[3] = (ticks >> 28) as u8;
+        key[4] = (ticks >> 20) as u8;
+        key[5] = (ticks >> 12) as u8;
+        key[6] = (b[6] & 0xF0) | ((ticks >> 8) & 0x0F) as u8;
+        key[7] = ticks as u8;
+
+        key
     }
-}
 
-impl fmt::Display for Variant {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self {
-            Variant::NCS => write!(f, "NCS"),
-            Variant::RFC4122 => write!(f, "RFC4122"),
-            Variant::Microsoft => write!(f, "Microsoft"),
-            Variant::Future => write!(f, "Future"),
+    /// Recovers a UUID from a key returned by [`Uuid::sort_key`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+    ///
+    /// assert_eq!(Uuid::from_sort_key(uuid.sort_key()), uuid);
+    /// ```
+    ///
+    /// [`Uuid::sort_key`]: #method.sort_key
+    pub fn from_sort_key(key: Bytes) -> Uuid {
+        if key[6] >> 4 != 1 {
+            return Uuid(key);
         }
-    }
-}
 
-impl fmt::LowerHex for Uuid {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::LowerHex::fmt(&self.to_hyphenated_ref(), f)
+        let ticks = u64::from(key[0]) << 52
+            | u64::from(key[1]) << 44
+            | u64::from(key[2]) << 36
+            | u64::from(key[3]) << 28
+            | u64::from(key[4]) << 20
+            | u64::from(key[5]) << 12
+            | u64::from(key[6] & 0x0F) << 8
+            | u64::from(key[7]);
+
+        let mut bytes = key;
+
+        bytes[0] = (ticks >> 24) as u8;
+        bytes[1] = (ticks >> 16) as u8;
+        bytes[2] = (ticks >> 8) as u8;
+        bytes[3] = ticks as u8;
+        bytes[4] = (ticks >> 40) as u8;
+        bytes[5] = (ticks >> 32) as u8;
+        bytes[6] = (key[6] & 0xF0) | ((ticks >> 56) & 0x0F) as u8;
+        bytes[7] = (ticks >> 48) as u8;
+
+        Uuid(bytes)
     }
-}
 
-impl fmt::UpperHex for Uuid {
-    #[inline]
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::UpperHex::fmt(&self.to_hyphenated_ref(), f)
+    /// Returns an array of 16 octets containing the UUID data.
+    pub const fn as_bytes(&self) -> &Bytes {
+        &self.0
     }
-}
 
-impl str::FromStr for Uuid {
-    type Err = Error;
-
-    fn from_str(uuid_str: &str) -> Result<Self, Self::Err> {
-        Uuid::parse_str(uuid_str)
+    /// Returns a raw pointer to the 16 octets of the UUID.
+    ///
+    /// The pointer is valid for as long as the UUID is, and can be passed
+    /// to C code expecting a `const uint8_t[16]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::NAMESPACE_DNS;
+    /// let ptr = uuid.as_ptr();
+    ///
+    /// assert_eq!(unsafe { *ptr }, uuid.as_bytes()[0]);
+    /// ```
+    pub const fn as_ptr(&self) -> *const u8 {
+        self as *const Uuid as *const u8
     }
-}
 
-impl Default for Uuid {
-    #[inline]
-    fn default() -> Self {
-        Uuid::nil()
+    /// Returns a raw mutable pointer to the 16 octets of the UUID.
+    ///
+    /// The pointer is valid for as long as the UUID is, and can be passed
+    /// to C code expecting a `uint8_t[16]` to fill in.
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self as *mut Uuid as *mut u8
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{
-        prelude::*,
-        std::string::{String, ToString},
-        test_util,
-    };
 
-    macro_rules! check {
-        ($buf:ident, $format:expr, $target:expr, $len:expr, $cond:expr) => {
-            $buf.clear();
-            write!($buf, $format, $target).unwrap();
-            assert!($buf.len() == $len);
-            assert!($buf.chars().all($cond), "{}", $buf);
-        };
+    /// Reinterprets a pointer to 16 octets as a UUID reference.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null and valid for reads of 16 bytes for the whole
+    /// lifetime `'a`, and the memory must not be mutated during that
+    /// lifetime. Since `Uuid` has an alignment of 1, `ptr` doesn't need to
+    /// be aligned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let bytes: [u8; 16] = *Uuid::NAMESPACE_DNS.as_bytes();
+    ///
+    /// let uuid = unsafe { Uuid::from_ptr(bytes.as_ptr()) };
+    ///
+    /// assert_eq!(*uuid, Uuid::NAMESPACE_DNS);
+    /// ```
+    #[cfg(not(feature = "no-unsafe"))]
+    pub unsafe fn from_ptr<'a>(ptr: *const u8) -> &'a Uuid {
+        &*(ptr as *const Uuid)
     }
 
-    #[test]
-    fn test_uuid_compare() {
-        let uuid1 = test_util::new();
-        let uuid2 = test_util::new2();
-
-        assert_eq!(uuid1, uuid1);
-        assert_eq!(uuid2, uuid2);
-
-        assert_ne!(uuid1, uuid2);
-        assert_ne!(uuid2, uuid1);
+    /// Reinterprets a buffer of `len` consecutive 16 octet values as a slice
+    /// of UUIDs, without copying.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null and valid for reads of `len * 16` bytes for
+    /// the whole lifetime `'a`, and the memory must not be mutated during
+    /// that lifetime. The total size must not exceed `isize::MAX`. See
+    /// [`slice::from_raw_parts`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let buffer: [u8; 32] = [
+    ///     0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00,
+    ///     0xc0, 0x4f, 0xd4, 0x30, 0xc8, 0x6b, 0xa7, 0xb8, 0x11, 0x9d, 0xad,
+    ///     0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8,
+    /// ];
+    ///
+    /// let uuids = unsafe { Uuid::slice_from_raw_parts(buffer.as_ptr(), 2) };
+    ///
+    /// assert_eq!(uuids, &[Uuid::NAMESPACE_DNS, Uuid::NAMESPACE_URL]);
+    /// ```
+    ///
+    /// [`slice::from_raw_parts`]: https://doc.rust-lang.org/std/slice/fn.from_raw_parts.html
+    #[cfg(not(feature = "no-unsafe"))]
+    pub unsafe fn slice_from_raw_parts<'a>(
+        ptr: *const u8,
+        len: usize,
+    ) -> &'a [Uuid] {
+        crate::std::slice::from_raw_parts(ptr as *const Uuid, len)
     }
 
-    #[test]
-    fn test_uuid_default() {
-        let default_uuid = Uuid::default();
-        let nil_uuid = Uuid::nil();
-
-        assert_eq!(default_uuid, nil_uuid);
+    /// Tests if the UUID is nil.
+    ///
+    /// This is usable in `const` contexts, so sentinel checks don't need to
+    /// compare against a hand-built array of zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// const UNSET: Uuid = Uuid::nil();
+    /// const _: () = assert!(UNSET.is_nil());
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    ///
+    /// assert!(!uuid.is_nil());
+    /// ```
+    pub const fn is_nil(&self) -> bool {
+        u128::from_be_bytes(self.0) == 0
     }
 
-    #[test]
-    fn test_uuid_display() {
-        use super::fmt::Write;
+    /// Tests if the UUID is max.
+    ///
+    /// Like [`Uuid::is_nil`], this is usable in `const` contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("ffffffff-ffff-ffff-ffff-ffffffffffff").unwrap();
+    ///
+    /// assert!(uuid.is_max());
+    /// assert!(!Uuid::nil().is_max());
+    /// ```
+    ///
+    /// [`Uuid::is_nil`]: #method.is_nil
+    pub const fn is_max(&self) -> bool {
+        u128::from_be_bytes(self.0) == u128::MAX
+    }
 
-        let uuid = test_util::new();
-        let s = uuid.to_string();
-        let mut buffer = String::new();
+    /// Tests if the UUID is one of the sentinel values, nil or max.
+    ///
+    /// Sentinels are commonly used as open range boundaries, such as the
+    /// initial cursor when paginating over UUID keys.
+    pub fn is_sentinel(&self) -> bool {
+        self.is_nil() || self.is_max()
+    }
 
-        assert_eq!(s, uuid.to_hyphenated().to_string());
+    /// Tests if the UUID has the variant defined by RFC4122 and RFC 9562,
+    /// with `10` as the two most significant bits of the ninth byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// let guid = Uuid::parse_str("936DA01F9ABD4d9dC0C702AF85C822A8").unwrap();
+    ///
+    /// assert!(uuid.is_rfc4122_variant());
+    /// assert!(!guid.is_rfc4122_variant());
+    /// ```
+    pub const fn is_rfc4122_variant(&self) -> bool {
+        self.0[8] & 0xc0 == 0x80
+    }
 
-        check!(buffer, "{}", uuid, 36, |c| c.is_lowercase()
-            || c.is_digit(10)
-            || c == '-');
+    /// Tests if the UUID has one of the versions 1 to 8 defined by RFC
+    /// 9562.
+    ///
+    /// The other version numbers are reserved. The nil and max UUIDs don't
+    /// have a version number, even though [`Uuid::get_version`] returns
+    /// [`Version::Nil`] and [`Version::Max`] for them, so this returns
+    /// `false` for them.
+    ///
+    /// [`Uuid::get_version`]: #method.get_version
+    /// [`Version::Nil`]: enum.Version.html#variant.Nil
+    /// [`Version::Max`]: enum.Version.html#variant.Max
+    pub const fn has_known_version(&self) -> bool {
+        matches!(self.get_version_num(), 1..=8)
     }
 
-    #[test]
+    /// Tests if the UUID is valid according to RFC 9562: either the nil
+    /// or max UUID, or a UUID with the RFC4122 variant and one of the
+    /// versions 1 to 8.
+    ///
+    /// This is meant for rejecting UUIDs from broken generators, such as
+    /// ones that don't set the version and variant bits at all. UUIDs of
+    /// the other variants, such as Microsoft's GUIDs with the legacy
+    /// variant, aren't conformant either.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+    ///
+    /// assert!(uuid.is_rfc_conformant());
+    /// assert!(Uuid::nil().is_rfc_conformant());
+    ///
+    /// // version 9 is reserved
+    /// let uuid = Uuid::parse_str("f3b4958c-52a1-91e7-802a-010203040506").unwrap();
+    ///
+    /// assert!(!uuid.is_rfc_conformant());
+    /// ```
+    pub const fn is_rfc_conformant(&self) -> bool {
+        self.is_nil()
+            || self.is_max()
+            || (self.is_rfc4122_variant() && self.has_known_version())
+    }
+
+    /// Returns the UUID, replacing the sentinel values with the nearest
+    /// value that isn't a sentinel.
+    ///
+    /// The nil UUID becomes `00000000-0000-0000-0000-000000000001` and the
+    /// max UUID becomes `ffffffff-ffff-ffff-ffff-fffffffffffe`, so the
+    /// result can be stored where sentinels are reserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// assert_eq!(Uuid::nil().clamp_to_valid_range().as_u128(), 1);
+    /// assert_eq!(Uuid::max().clamp_to_valid_range().as_u128(), u128::MAX - 1);
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// assert_eq!(uuid.clamp_to_valid_range(), uuid);
+    /// ```
+    pub fn clamp_to_valid_range(&self) -> Uuid {
+        if self.is_nil() {
+            Uuid::from_u128(1)
+        } else if self.is_max() {
+            Uuid::from_u128(u128::MAX - 1)
+        } else {
+            *self
+        }
+    }
+
+    /// Compares two UUIDs in time order, treating nil as smaller and max as
+    /// larger than any other UUID.
+    ///
+    /// UUIDs are compared by their [`Uuid::sort_key`], so time-based UUIDs
+    /// are ordered by creation time. This is useful for keyset pagination,
+    /// where nil and max stand in for the open ends of a range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+    ///
+    /// assert_eq!(Uuid::nil().cmp_with_sentinels(&uuid), Ordering::Less);
+    /// assert_eq!(Uuid::max().cmp_with_sentinels(&uuid), Ordering::Greater);
+    /// assert_eq!(uuid.cmp_with_sentinels(&uuid), Ordering::Equal);
+    /// ```
+    ///
+    /// [`Uuid::sort_key`]: #method.sort_key
+    pub fn cmp_with_sentinels(&self, other: &Uuid) -> Ordering {
+        match (self.sentinel_rank(), other.sentinel_rank()) {
+            (0, 0) => self.sort_key().cmp(&other.sort_key()),
+            (a, b) => a.cmp(&b),
+        }
+    }
+
+    fn sentinel_rank(&self) -> i8 {
+        if self.is_nil() {
+            -1
+        } else if self.is_max() {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Compares two UUIDs the way Cassandra and Scylla order `timeuuid`
+    /// columns.
+    ///
+    /// The timestamps are compared first, then the clock sequence and node
+    /// bytes, each as a *signed* byte. Sorting with this method agrees with
+    /// the order rows are read back in. It's only meaningful for version 1
+    /// UUIDs, which are the only ones a `timeuuid` column accepts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use uuid::Uuid;
+    ///
+    /// let a = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+    /// let b = Uuid::parse_str("f3b4958c-52a1-11e7-7f2a-010203040506").unwrap();
+    ///
+    /// // 0x80 is negative as a signed byte
+    /// assert_eq!(a.timeuuid_cmp(&b), Ordering::Less);
+    /// assert_eq!(a.cmp(&b), Ordering::Greater);
+    /// ```
+    pub fn timeuuid_cmp(&self, other: &Uuid) -> Ordering {
+        let (a_low, a_mid, a_high, a_rest) = self.as_fields();
+        let (b_low, b_mid, b_high, b_rest) = other.as_fields();
+
+        (a_high, a_mid, a_low)
+            .cmp(&(b_high, b_mid, b_low))
+            .then_with(|| {
+                let signed = |b: &u8| *b as i8;
+
+                a_rest.iter().map(signed).cmp(b_rest.iter().map(signed))
+            })
+    }
+
+    /// Compares two UUIDs the way SQL Server orders `uniqueidentifier`
+    /// columns.
+    ///
+    /// SQL Server compares the last six bytes first, then the two bytes
+    /// before them, and only then the first three fields, from the last
+    /// to the first and each in little-endian order. Sorting with this
+    /// method agrees with `ORDER BY` on the column, which keeps keyset
+    /// pagination consistent between Rust and the database.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use uuid::Uuid;
+    ///
+    /// let a = Uuid::parse_str("ffffffff-ffff-ffff-ffff-000000000000").unwrap();
+    /// let b = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    ///
+    /// assert_eq!(a.cmp_sqlserver(&b), Ordering::Less);
+    /// assert_eq!(a.cmp(&b), Ordering::Greater);
+    /// ```
+    pub fn cmp_sqlserver(&self, other: &Uuid) -> Ordering {
+        const ORDER: [usize; 16] =
+            [10, 11, 12, 13, 14, 15, 8, 9, 7, 6, 5, 4, 3, 2, 1, 0];
+
+        ORDER
+            .iter()
+            .map(|&i| self.as_bytes()[i])
+            .cmp(ORDER.iter().map(|&i| other.as_bytes()[i]))
+    }
+
+    /// Compares two UUIDs the way Oracle orders `RAW(16)` columns.
+    ///
+    /// Oracle compares `RAW` values byte by byte, so this is the same as
+    /// [`Ord`] for UUIDs stored with [`Uuid::as_bytes`], for example with
+    /// `HEXTORAW` of [`Uuid::to_simple`]. It's provided so that code that
+    /// picks a comparator per database reads the same for every engine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use uuid::Uuid;
+    ///
+    /// let a = Uuid::parse_str("ffffffff-ffff-ffff-ffff-000000000000").unwrap();
+    /// let b = Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    ///
+    /// assert_eq!(a.cmp_oracle(&b), Ordering::Greater);
+    /// ```
+    ///
+    /// [`Ord`]: https://doc.rust-lang.org/std/cmp/trait.Ord.html
+    /// [`Uuid::as_bytes`]: #method.as_bytes
+    /// [`Uuid::to_simple`]: #method.to_simple
+    pub fn cmp_oracle(&self, other: &Uuid) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+
+    /// A buffer that can be used for `encode_...` calls, that is
+    /// guaranteed to be long enough for any of the adapters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::nil();
+    ///
+    /// assert_eq!(
+    ///     uuid.to_simple().encode_lower(&mut Uuid::encode_buffer()),
+    ///     "00000000000000000000000000000000"
+    /// );
+    ///
+    /// assert_eq!(
+    ///     uuid.to_hyphenated()
+    ///         .encode_lower(&mut Uuid::encode_buffer()),
+    ///     "00000000-0000-0000-0000-000000000000"
+    /// );
+    ///
+    /// assert_eq!(
+    ///     uuid.to_urn().encode_lower(&mut Uuid::encode_buffer()),
+    ///     "urn:uuid:00000000-0000-0000-0000-000000000000"
+    /// );
+    /// ```
+    pub const fn encode_buffer() -> EncodeBuffer {
+        [0; adapter::Urn::LENGTH]
+    }
+
+    /// Writes the UUID as a lower case hyphenated string to `buffer`, and
+    /// returns the subslice of the buffer that contains it.
+    ///
+    /// This is a shorthand for [`Hyphenated::encode_lower`], which, like
+    /// the other adapters, can also encode in upper case.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than [`Hyphenated::LENGTH`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// let mut buffer = Uuid::encode_buffer();
+    ///
+    /// assert_eq!(
+    ///     uuid.encode_hyphenated(&mut buffer),
+    ///     "936da01f-9abd-4d9d-80c7-02af85c822a8"
+    /// );
+    /// ```
+    ///
+    /// [`Hyphenated::encode_lower`]: adapter/struct.Hyphenated.html#method.encode_lower
+    /// [`Hyphenated::LENGTH`]: adapter/struct.Hyphenated.html#associatedconstant.LENGTH
+    pub fn encode_hyphenated<'buf>(
+        &self,
+        buffer: &'buf mut [u8],
+    ) -> &'buf mut str {
+        self.to_hyphenated_ref().encode_lower(buffer)
+    }
+
+    /// Writes the UUID as a lower case string of 32 hexadecimal digits to
+    /// `buffer`, and returns the subslice of the buffer that contains it.
+    ///
+    /// This is a shorthand for [`Simple::encode_lower`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than [`Simple::LENGTH`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// let mut buffer = [0; 32];
+    ///
+    /// assert_eq!(
+    ///     uuid.encode_simple(&mut buffer),
+    ///     "936da01f9abd4d9d80c702af85c822a8"
+    /// );
+    /// ```
+    ///
+    /// [`Simple::encode_lower`]: adapter/struct.Simple.html#method.encode_lower
+    /// [`Simple::LENGTH`]: adapter/struct.Simple.html#associatedconstant.LENGTH
+    pub fn encode_simple<'buf>(&self, buffer: &'buf mut [u8]) -> &'buf mut str {
+        self.to_simple_ref().encode_lower(buffer)
+    }
+}
+
+/// Orders UUIDs by their bytes, most significant first.
+///
+/// This is the same order as comparing [`Uuid::as_bytes`] or
+/// [`Uuid::as_u128`], but the bytes are compared as a single integer
+/// instead of one by one.
+///
+/// [`Uuid::as_bytes`]: #method.as_bytes
+/// [`Uuid::as_u128`]: #method.as_u128
+impl Ord for Uuid {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        u128::from_be_bytes(self.0).cmp(&u128::from_be_bytes(other.0))
+    }
+}
+
+impl PartialOrd for Uuid {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Compares the UUID with a string, which is equal if it parses to the
+/// same UUID.
+///
+/// The string may be in any of the formats accepted by
+/// [`Uuid::parse_str`], in either case. Strings that aren't UUIDs are never
+/// equal. Nothing is allocated.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::Uuid;
+///
+/// let uuid = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+///
+/// assert_eq!(uuid, "F3B4958C-52A1-11E7-802A-010203040506");
+/// assert_eq!(uuid, "urn:uuid:f3b4958c-52a1-11e7-802a-010203040506");
+/// assert_eq!("f3b4958c52a111e7802a010203040506", uuid);
+/// assert_ne!(uuid, "not a UUID");
+/// ```
+///
+/// [`Uuid::parse_str`]: #method.parse_str
+impl PartialEq<str> for Uuid {
+    fn eq(&self, other: &str) -> bool {
+        Uuid::parse_str(other) == Ok(*self)
+    }
+}
+
+impl PartialEq<&str> for Uuid {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl PartialEq<Uuid> for str {
+    fn eq(&self, other: &Uuid) -> bool {
+        *other == *self
+    }
+}
+
+impl PartialEq<Uuid> for &str {
+    fn eq(&self, other: &Uuid) -> bool {
+        *other == **self
+    }
+}
+
+/// Formats the UUID for debugging.
+///
+/// The `{:?}` format is guaranteed to be the same as the [`Display`]
+/// format, the lower case hyphenated string, so it is safe to use in
+/// snapshots that need to be stable across releases of this crate.
+///
+/// The alternate `{:#?}` format prints the fields the UUID decomposes into
+/// instead, as decoded by [`Uuid::describe`], and for time-based UUIDs the
+/// time they were generated at in ISO-8601 format. Its layout is meant for
+/// people and may change between releases.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::Uuid;
+///
+/// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+///
+/// assert_eq!(format!("{:?}", uuid), "936da01f-9abd-4d9d-80c7-02af85c822a8");
+/// assert_eq!(
+///     format!("{:#?}", uuid),
+///     "\
+/// UuidInfo {
+///     version: 4,
+///     variant: Some(
+///         RFC4122,
+///     ),
+/// }"
+/// );
+///
+/// let uuid = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+///
+/// assert_eq!(
+///     format!("{:#?}", uuid),
+///     "\
+/// UuidInfo {
+///     version: 1,
+///     variant: Some(
+///         RFC4122,
+///     ),
+///     timestamp: 2017-06-16T14:41:59.000001200Z,
+///     clock_seq: 0x002a,
+///     node_id: 0x010203040506,
+/// }"
+/// );
+/// ```
+///
+/// [`Display`]: #impl-Display
+/// [`Uuid::describe`]: #method.describe
+impl fmt::Debug for Uuid {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return fmt::Debug::fmt(&self.describe(), f);
+        }
+
+        fmt::Display::fmt(&self.to_hyphenated_ref(), f)
+    }
+}
+
+/// Formats the UUID as a lower case hyphenated string.
+///
+/// Width, fill and alignment are respected, and the alternate `{:#}`
+/// format encloses the UUID in braces, like [`Braced`].
+///
+/// # Examples
+///
+/// ```
+/// use uuid::Uuid;
+///
+/// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+///
+/// assert_eq!(
+///     format!("[{:>40}]", uuid),
+///     "[    936da01f-9abd-4d9d-80c7-02af85c822a8]"
+/// );
+/// assert_eq!(
+///     format!("{:#}", uuid),
+///     "{936da01f-9abd-4d9d-80c7-02af85c822a8}"
+/// );
+/// ```
+///
+/// [`Braced`]: adapter/struct.Braced.html
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            fmt::Display::fmt(&self.to_braced_ref(), f)
+        } else {
+            fmt::Display::fmt(&self.to_hyphenated_ref(), f)
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Variant::NCS => write!(f, "NCS"),
+            Variant::RFC4122 => write!(f, "RFC4122"),
+            Variant::Microsoft => write!(f, "Microsoft"),
+            Variant::Future => write!(f, "Future"),
+        }
+    }
+}
+
+/// Converts the number of a variant, as given by `variant as u8`.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use uuid::Variant;
+///
+/// assert_eq!(Variant::try_from(Variant::RFC4122 as u8), Ok(Variant::RFC4122));
+/// assert!(Variant::try_from(4).is_err());
+/// ```
+impl TryFrom<u8> for Variant {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Variant::NCS),
+            1 => Ok(Variant::RFC4122),
+            2 => Ok(Variant::Microsoft),
+            3 => Ok(Variant::Future),
+            _ => Err(Error::invalid_variant(value)),
+        }
+    }
+}
+
+/// Formats the version as its number followed by its name, such as
+/// `4 (Random)`.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::Version;
+///
+/// assert_eq!(Version::SortRand.to_string(), "7 (SortRand)");
+/// assert_eq!(Version::Max.to_string(), "Max");
+/// ```
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match *self {
+            Version::Nil => return write!(f, "Nil"),
+            Version::Max => return write!(f, "Max"),
+            Version::Mac => "Mac",
+            Version::Dce => "Dce",
+            Version::Md5 => "Md5",
+            Version::Random => "Random",
+            Version::Sha1 => "Sha1",
+            Version::SortMac => "SortMac",
+            Version::SortRand => "SortRand",
+            Version::Custom => "Custom",
+        };
+
+        write!(f, "{} ({})", *self as u8, name)
+    }
+}
+
+/// Converts a version number, as given by `version as u8` or
+/// [`Uuid::get_version_num`].
+///
+/// Both `0xf`, the version bits of the max UUID, and `0xff`, the value of
+/// `Version::Max as u8`, are converted to [`Version::Max`].
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use uuid::Version;
+///
+/// assert_eq!(Version::try_from(7), Ok(Version::SortRand));
+/// assert_eq!(Version::try_from(Version::Max as u8), Ok(Version::Max));
+/// assert!(Version::try_from(9).is_err());
+/// ```
+///
+/// [`Uuid::get_version_num`]: struct.Uuid.html#method.get_version_num
+/// [`Version::Max`]: enum.Version.html#variant.Max
+impl TryFrom<u8> for Version {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Version::Nil),
+            1 => Ok(Version::Mac),
+            2 => Ok(Version::Dce),
+            3 => Ok(Version::Md5),
+            4 => Ok(Version::Random),
+            5 => Ok(Version::Sha1),
+            6 => Ok(Version::SortMac),
+            7 => Ok(Version::SortRand),
+            8 => Ok(Version::Custom),
+            0xf | 0xff => Ok(Version::Max),
+            _ => Err(Error::invalid_version(value)),
+        }
+    }
+}
+
+/// Formats the UUID as 32 lower case hexadecimal digits, like the
+/// [`Simple`] adapter.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::Uuid;
+///
+/// let uuid = Uuid::parse_str("936DA01F-9ABD-4D9D-80C7-02AF85C822A8").unwrap();
+///
+/// assert_eq!(format!("{:x}", uuid), "936da01f9abd4d9d80c702af85c822a8");
+/// ```
+///
+/// [`Simple`]: adapter/struct.Simple.html
+impl fmt::LowerHex for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.to_simple_ref(), f)
+    }
+}
+
+/// Formats the UUID as 32 upper case hexadecimal digits, like the
+/// [`Simple`] adapter.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::Uuid;
+///
+/// let uuid = Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822a8").unwrap();
+///
+/// assert_eq!(format!("{:X}", uuid), "936DA01F9ABD4D9D80C702AF85C822A8");
+/// ```
+///
+/// [`Simple`]: adapter/struct.Simple.html
+impl fmt::UpperHex for Uuid {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.to_simple_ref(), f)
+    }
+}
+
+impl str::FromStr for Uuid {
+    type Err = Error;
+
+    fn from_str(uuid_str: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(uuid_str)
+    }
+}
+
+impl Default for Uuid {
+    #[inline]
+    fn default() -> Self {
+        Uuid::nil()
+    }
+}
+
+impl AsRef<[u8]> for Uuid {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A `Uuid` hashes and compares just like its bytes, so collections keyed
+/// by `Uuid` can be queried with a `&[u8; 16]`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use uuid::Uuid;
+///
+/// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+///
+/// let mut set = HashSet::new();
+/// set.insert(uuid);
+///
+/// assert!(set.contains(uuid.as_bytes()));
+/// ```
+impl Borrow<Bytes> for Uuid {
+    #[inline]
+    fn borrow(&self) -> &Bytes {
+        &self.0
+    }
+}
+
+impl From<Bytes> for Uuid {
+    #[inline]
+    fn from(bytes: Bytes) -> Self {
+        Uuid::from_bytes(bytes)
+    }
+}
+
+impl From<Uuid> for Bytes {
+    #[inline]
+    fn from(uuid: Uuid) -> Self {
+        uuid.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        prelude::*,
+        std::string::{String, ToString},
+        test_util,
+    };
+
+    macro_rules! check {
+        ($buf:ident, $format:expr, $target:expr, $len:expr, $cond:expr) => {
+            $buf.clear();
+            write!($buf, $format, $target).unwrap();
+            assert!($buf.len() == $len);
+            assert!($buf.chars().all($cond), "{}", $buf);
+        };
+    }
+
+    #[test]
+    fn test_uuid_compare() {
+        let uuid1 = test_util::new();
+        let uuid2 = test_util::new2();
+
+        assert_eq!(uuid1, uuid1);
+        assert_eq!(uuid2, uuid2);
+
+        assert_ne!(uuid1, uuid2);
+        assert_ne!(uuid2, uuid1);
+    }
+
+    #[test]
+    fn test_uuid_default() {
+        let default_uuid = Uuid::default();
+        let nil_uuid = Uuid::nil();
+
+        assert_eq!(default_uuid, nil_uuid);
+    }
+
+    #[test]
+    fn test_uuid_display() {
+        use super::fmt::Write;
+
+        let uuid = test_util::new();
+        let s = uuid.to_string();
+        let mut buffer = String::new();
+
+        assert_eq!(s, uuid.to_hyphenated().to_string());
+
+        check!(buffer, "{}", uuid, 36, |c| c.is_lowercase()
+            || c.is_ascii_digit()
+            || c == '-');
+    }
+
+    #[test]
+    fn test_uuid_display_flags() {
+        let uuid = test_util::new();
+
+        assert_eq!(
+            format!("{:>40}|", uuid),
+            "    f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4|"
+        );
+        assert_eq!(
+            format!("{:*<38}|", uuid),
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4**|"
+        );
+        assert_eq!(
+            format!("{:^40X}", uuid),
+            "    F9168C5ECEB24FAAB6BF329BF39FA1E4    "
+        );
+        assert_eq!(format!("{:10}", uuid), uuid.to_string());
+        assert_eq!(
+            format!("{:#}", uuid),
+            "{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}"
+        );
+        assert_eq!(
+            format!("{:>#40}", uuid),
+            "  {f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}"
+        );
+        assert_eq!(
+            format!("{:>46}", uuid.to_urn()),
+            " urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4"
+        );
+        assert_eq!(
+            format!("{:>40?}", uuid),
+            "    f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4"
+        );
+        assert_eq!(
+            format!("{:*<38?}", uuid),
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4**"
+        );
+    }
+
+    #[test]
     fn test_uuid_lowerhex() {
         use super::fmt::Write;
 
         let mut buffer = String::new();
         let uuid = test_util::new();
 
-        check!(buffer, "{:x}", uuid, 36, |c| c.is_lowercase()
-            || c.is_digit(10)
-            || c == '-');
+        check!(buffer, "{:x}", uuid, 32, |c| c.is_lowercase()
+            || c.is_ascii_digit());
     }
 
     // noinspection RsAssertEqual
     #[test]
     fn test_uuid_operator_eq() {
         let uuid1 = test_util::new();
-        let uuid1_dup = uuid1.clone();
+        let uuid1_dup = uuid1;
         let uuid2 = test_util::new2();
 
         assert!(uuid1 == uuid1);
@@ -662,7 +1855,7 @@ mod tests {
         assert_eq!(s.len(), 36);
 
         check!(buffer, "{}", s, 36, |c| c.is_lowercase()
-            || c.is_digit(10)
+            || c.is_ascii_digit()
             || c == '-');
     }
 
@@ -673,9 +1866,8 @@ mod tests {
         let mut buffer = String::new();
         let uuid = test_util::new();
 
-        check!(buffer, "{:X}", uuid, 36, |c| c.is_uppercase()
-            || c.is_digit(10)
-            || c == '-');
+        check!(buffer, "{:X}", uuid, 32, |c| c.is_uppercase()
+            || c.is_ascii_digit());
     }
 
     #[test]
@@ -690,6 +1882,10 @@ mod tests {
 
         assert!(nil.is_nil());
         assert!(!not_nil.is_nil());
+        assert!(!Uuid::from_u128(1 << 127).is_nil());
+        assert!(!Uuid::from_u128(1).is_nil());
+
+        const _: [(); 1] = [(); Uuid::nil().is_nil() as usize];
 
         assert_eq!(nil.get_version(), Some(Version::Nil));
         assert_eq!(not_nil.get_version(), Some(Version::Random))
@@ -745,6 +1941,290 @@ mod tests {
         assert_eq!(uuid4.get_variant().unwrap(), Variant::Microsoft);
         assert_eq!(uuid5.get_variant().unwrap(), Variant::Microsoft);
         assert_eq!(uuid6.get_variant().unwrap(), Variant::NCS);
+        assert_eq!(Uuid::max().get_variant(), Some(Variant::Future));
+        assert_eq!(Uuid::nil().get_variant(), Some(Variant::NCS));
+
+        for byte in 0..=255 {
+            let mut bytes = [0; 16];
+            bytes[8] = byte;
+
+            assert!(Uuid::from_bytes(bytes).get_variant().is_some());
+        }
+    }
+
+    #[test]
+    fn test_get_variant_bits() {
+        assert_eq!(test_util::new().get_variant_bits(), 0b101);
+        assert_eq!(Uuid::nil().get_variant_bits(), 0);
+        assert_eq!(Uuid::max().get_variant_bits(), 0b111);
+
+        for byte in 0..=255 {
+            let mut bytes = [0; 16];
+            bytes[8] = byte;
+            let uuid = Uuid::from_bytes(bytes);
+
+            let expected = match uuid.get_variant() {
+                Some(Variant::NCS) => 0b000..=0b011,
+                Some(Variant::RFC4122) => 0b100..=0b101,
+                Some(Variant::Microsoft) => 0b110..=0b110,
+                _ => 0b111..=0b111,
+            };
+
+            assert_eq!(uuid.get_variant_bits(), byte >> 5);
+            assert!(expected.contains(&uuid.get_variant_bits()));
+        }
+
+        // reserved version numbers are still returned
+        let mut bytes = [0; 16];
+        bytes[6] = 0xb0;
+        assert_eq!(Uuid::from_bytes(bytes).get_version_num(), 0xb);
+        assert_eq!(Uuid::from_bytes(bytes).get_version(), None);
+    }
+
+    #[test]
+    fn test_version_try_from() {
+        use std::convert::TryFrom;
+
+        for &version in &[
+            Version::Nil,
+            Version::Mac,
+            Version::Dce,
+            Version::Md5,
+            Version::Random,
+            Version::Sha1,
+            Version::SortMac,
+            Version::SortRand,
+            Version::Custom,
+            Version::Max,
+        ] {
+            assert_eq!(Version::try_from(version as u8), Ok(version));
+        }
+
+        assert_eq!(Version::try_from(0xf), Ok(Version::Max));
+        assert_eq!(
+            Version::try_from(9).unwrap_err().to_string(),
+            "invalid UUID version number: found 9"
+        );
+        assert!(Version::try_from(0xfe).is_err());
+
+        let uuid = test_util::new();
+        assert_eq!(
+            Version::try_from(uuid.get_version_num() as u8).ok(),
+            uuid.get_version()
+        );
+    }
+
+    #[test]
+    fn test_variant_try_from() {
+        use std::convert::TryFrom;
+
+        for &variant in &[
+            Variant::NCS,
+            Variant::RFC4122,
+            Variant::Microsoft,
+            Variant::Future,
+        ] {
+            assert_eq!(Variant::try_from(variant as u8), Ok(variant));
+        }
+
+        assert_eq!(
+            Variant::try_from(4).unwrap_err().to_string(),
+            "invalid UUID variant number: found 4"
+        );
+    }
+
+    #[test]
+    fn test_version_display() {
+        assert_eq!(Version::Nil.to_string(), "Nil");
+        assert_eq!(Version::Mac.to_string(), "1 (Mac)");
+        assert_eq!(Version::Random.to_string(), "4 (Random)");
+        assert_eq!(Version::Custom.to_string(), "8 (Custom)");
+        assert_eq!(Version::Max.to_string(), "Max");
+        assert_eq!(Variant::RFC4122.to_string(), "RFC4122");
+    }
+
+    #[test]
+    fn test_get_version_modern() {
+        let parse = |s| Uuid::parse_str(s).unwrap();
+
+        assert_eq!(
+            parse("1ec9414c-232a-6b00-b3c8-9e6bdeced846").get_version(),
+            Some(Version::SortMac)
+        );
+        assert_eq!(
+            parse("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").get_version(),
+            Some(Version::SortRand)
+        );
+        assert_eq!(
+            parse("320c3d4d-cc00-875b-8ec9-32d5f69181c0").get_version(),
+            Some(Version::Custom)
+        );
+        assert_eq!(Uuid::max().get_version(), Some(Version::Max));
+
+        assert_eq!(
+            parse("320c3d4d-cc00-975b-8ec9-32d5f69181c0").get_version(),
+            None
+        );
+        assert_eq!(
+            parse("ffffffff-ffff-ffff-ffff-fffffffffffe").get_version(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_word_arrays() {
+        for uuid in &[
+            test_util::new(),
+            test_util::new2(),
+            Uuid::nil(),
+            Uuid::max(),
+        ] {
+            assert_eq!(Uuid::from_u32x4(uuid.as_u32x4()), *uuid);
+            assert_eq!(Uuid::from_u16x8(uuid.as_u16x8()), *uuid);
+        }
+
+        let uuid = test_util::new();
+        let (high, low) = uuid.as_u64_pair();
+        let words = uuid.as_u32x4();
+
+        assert_eq!(u64::from(words[0]) << 32 | u64::from(words[1]), high);
+        assert_eq!(u64::from(words[2]) << 32 | u64::from(words[3]), low);
+        assert_eq!(uuid.as_u16x8()[0], (words[0] >> 16) as u16);
+    }
+
+    #[test]
+    fn test_eq_str() {
+        let uuid = test_util::new();
+        let hyphenated = "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4";
+
+        assert!(uuid == *hyphenated);
+        assert!(uuid == hyphenated);
+        assert!(*hyphenated == uuid);
+        assert!(hyphenated == uuid);
+
+        assert_eq!(uuid, "F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4");
+        assert_eq!(uuid, "F9168C5ECEB24FAAB6BF329BF39FA1E4");
+        assert_eq!(uuid, "{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}");
+        assert_eq!(uuid, uuid.to_urn().to_string().as_str());
+
+        assert_ne!(uuid, "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e5");
+        assert_ne!(uuid, "");
+        assert_ne!(Uuid::nil(), "0");
+    }
+
+    #[test]
+    fn test_rfc_conformance() {
+        let parse = |s| Uuid::parse_str(s).unwrap();
+
+        for version in 1..=8 {
+            let uuid = Builder::from_bytes([0; 16])
+                .with_d3(version << 12)
+                .with_variant(Variant::RFC4122)
+                .into_uuid();
+
+            assert!(uuid.is_rfc4122_variant());
+            assert!(uuid.has_known_version());
+            assert!(uuid.is_rfc_conformant());
+        }
+
+        assert!(Uuid::nil().is_rfc_conformant());
+        assert!(!Uuid::nil().has_known_version());
+        assert!(Uuid::max().is_rfc_conformant());
+        assert!(!Uuid::max().is_rfc4122_variant());
+
+        // reserved versions
+        let reserved = parse("320c3d4d-cc00-075b-8ec9-32d5f69181c0");
+        assert!(reserved.is_rfc4122_variant());
+        assert!(!reserved.has_known_version());
+        assert!(!reserved.is_rfc_conformant());
+        assert!(
+            !parse("320c3d4d-cc00-975b-8ec9-32d5f69181c0").is_rfc_conformant()
+        );
+
+        // other variants
+        for uuid in &[
+            "320c3d4d-cc00-475b-0ec9-32d5f69181c0",
+            "320c3d4d-cc00-475b-cec9-32d5f69181c0",
+            "320c3d4d-cc00-475b-eec9-32d5f69181c0",
+        ] {
+            let uuid = parse(uuid);
+
+            assert!(uuid.has_known_version());
+            assert!(!uuid.is_rfc4122_variant());
+            assert!(!uuid.is_rfc_conformant());
+        }
+    }
+
+    #[test]
+    fn test_builder_const() {
+        const UUID: Uuid = Builder::from_bytes([0xff; 16])
+            .with_d1(0x0102_0304)
+            .with_d2(0x0506)
+            .with_d3(0x0708)
+            .with_d4([9, 10, 11, 12, 13, 14, 15, 16])
+            .into_uuid();
+
+        assert_eq!(
+            UUID,
+            Uuid::from_fields(
+                0x0102_0304,
+                0x0506,
+                0x0708,
+                &[9, 10, 11, 12, 13, 14, 15, 16]
+            )
+            .unwrap()
+        );
+
+        const CUSTOM: Uuid = Builder::from_bytes([0xff; 16])
+            .with_variant(Variant::RFC4122)
+            .with_version(Version::Custom)
+            .into_uuid();
+
+        assert_eq!(CUSTOM.get_version(), Some(Version::Custom));
+        assert_eq!(CUSTOM.get_variant(), Some(Variant::RFC4122));
+        assert_eq!(
+            CUSTOM,
+            Builder::from_bytes([0xff; 16])
+                .set_variant(Variant::RFC4122)
+                .set_version(Version::Custom)
+                .build()
+        );
+    }
+
+    #[test]
+    fn test_builder_bytes() {
+        let uuid = test_util::new();
+        let mut builder = Builder::from_slice(uuid.as_bytes()).unwrap();
+
+        assert_eq!(builder.as_bytes(), uuid.as_bytes());
+
+        builder.as_mut_bytes()[15] = 0;
+        assert_eq!(builder.as_bytes()[15], 0);
+        assert_eq!(builder.build().as_bytes()[..15], uuid.as_bytes()[..15]);
+
+        assert_eq!(
+            Builder::from_u128(uuid.as_u128()).into_bytes(),
+            *uuid.as_bytes()
+        );
+        assert_eq!(Builder::from_u128_le(uuid.to_u128_le()).into_uuid(), uuid);
+        assert!(Builder::from_slice(&[0; 15]).is_err());
+    }
+
+    #[test]
+    fn test_set_version_modern() {
+        let mut builder = Builder::from_bytes([0xff; 16]);
+
+        for &version in &[Version::SortMac, Version::SortRand, Version::Custom]
+        {
+            let uuid = builder.set_version(version).build();
+
+            assert_eq!(uuid.get_version(), Some(version));
+        }
+
+        assert_eq!(
+            builder.set_version(Version::Max).build().get_version(),
+            Some(Version::Max)
+        );
     }
 
     #[test]
@@ -753,7 +2233,7 @@ mod tests {
         let s = uuid1.to_simple().to_string();
 
         assert_eq!(s.len(), 32);
-        assert!(s.chars().all(|c| c.is_digit(16)));
+        assert!(s.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
     #[test]
@@ -762,7 +2242,7 @@ mod tests {
         let s = uuid1.to_hyphenated().to_string();
 
         assert!(s.len() == 36);
-        assert!(s.chars().all(|c| c.is_digit(16) || c == '-'));
+        assert!(s.chars().all(|c| c.is_ascii_hexdigit() || c == '-'));
     }
 
     #[test]
@@ -781,20 +2261,242 @@ mod tests {
             };
         }
 
-        check!(buf, "{:X}", u, 36, |c| c.is_uppercase()
-            || c.is_digit(10)
-            || c == '-');
+        check!(buf, "{:X}", u, 32, |c| c.is_uppercase()
+            || c.is_ascii_digit());
         check!(buf, "{:X}", u.to_hyphenated(), 36, |c| c.is_uppercase()
-            || c.is_digit(10)
+            || c.is_ascii_digit()
             || c == '-');
         check!(buf, "{:X}", u.to_simple(), 32, |c| c.is_uppercase()
-            || c.is_digit(10));
+            || c.is_ascii_digit());
 
         check!(buf, "{:x}", u.to_hyphenated(), 36, |c| c.is_lowercase()
-            || c.is_digit(10)
+            || c.is_ascii_digit()
             || c == '-');
         check!(buf, "{:x}", u.to_simple(), 32, |c| c.is_lowercase()
-            || c.is_digit(10));
+            || c.is_ascii_digit());
+    }
+
+    #[test]
+    fn test_sentinels() {
+        use std::cmp::Ordering;
+
+        let u = test_util::new();
+
+        assert!(Uuid::max().is_max());
+        assert!(!Uuid::nil().is_max());
+        assert!(!Uuid::from_u128(u128::MAX >> 1).is_max());
+        assert!(!Uuid::from_u128(u128::MAX - 1).is_max());
+        assert!(Uuid::max() > Uuid::from_u128(u128::MAX - 1));
+
+        const _: [(); 1] = [(); Uuid::max().is_max() as usize];
+        assert!(Uuid::nil().is_sentinel());
+        assert!(Uuid::max().is_sentinel());
+        assert!(!u.is_sentinel());
+
+        assert!(!Uuid::nil().clamp_to_valid_range().is_sentinel());
+        assert!(!Uuid::max().clamp_to_valid_range().is_sentinel());
+        assert_eq!(u.clamp_to_valid_range(), u);
+
+        assert_eq!(
+            Uuid::nil().cmp_with_sentinels(&Uuid::max()),
+            Ordering::Less
+        );
+        assert_eq!(
+            Uuid::max().cmp_with_sentinels(&Uuid::max()),
+            Ordering::Equal
+        );
+        assert_eq!(u.cmp_with_sentinels(&Uuid::nil()), Ordering::Greater);
+        assert_eq!(
+            u.cmp_with_sentinels(&test_util::new2()),
+            u.sort_key().cmp(&test_util::new2().sort_key())
+        );
+    }
+
+    #[test]
+    fn test_encode_into_buffer() {
+        let uuid = test_util::new();
+        let mut buffer = Uuid::encode_buffer();
+
+        assert_eq!(
+            uuid.encode_hyphenated(&mut buffer),
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4"
+        );
+        assert_eq!(
+            uuid.encode_simple(&mut buffer[4..]),
+            "f9168c5eceb24faab6bf329bf39fa1e4"
+        );
+        assert_eq!(&buffer[..4], b"f916");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_encode_into_short_buffer() {
+        test_util::new().encode_hyphenated(&mut [0; 35]);
+    }
+
+    #[test]
+    fn test_conversions() {
+        use crate::std::{borrow::Borrow, collections::BTreeSet};
+
+        let uuid = test_util::new();
+        let bytes = *uuid.as_bytes();
+
+        assert_eq!(Uuid::from(bytes), uuid);
+        assert_eq!(Bytes::from(uuid), bytes);
+        assert_eq!(AsRef::<[u8]>::as_ref(&uuid), &bytes[..]);
+        assert_eq!(Borrow::<Bytes>::borrow(&uuid), &bytes);
+
+        let mut set = BTreeSet::new();
+        set.insert(uuid);
+        set.insert(test_util::new2());
+
+        assert!(set.contains(&bytes));
+        assert!(!set.contains(&[0; 16]));
+    }
+
+    #[test]
+    fn test_timeuuid_cmp() {
+        use std::cmp::Ordering;
+
+        let parse = |s| Uuid::parse_str(s).unwrap();
+
+        let earlier = parse("ffffffff-0000-11e7-8000-000000000000");
+        let later = parse("00000000-0001-11e7-8000-000000000000");
+
+        assert_eq!(earlier.timeuuid_cmp(&later), Ordering::Less);
+        assert_eq!(earlier.cmp(&later), Ordering::Greater);
+
+        let negative = parse("00000000-0001-11e7-8000-000000000000");
+        let positive = parse("00000000-0001-11e7-7fff-ffffffffffff");
+        let node = parse("00000000-0001-11e7-8000-0000000000ff");
+
+        assert_eq!(negative.timeuuid_cmp(&positive), Ordering::Less);
+        assert_eq!(node.timeuuid_cmp(&negative), Ordering::Less);
+        assert_eq!(later.timeuuid_cmp(&later), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_cmp_sqlserver() {
+        use std::cmp::Ordering;
+
+        // sorted the way SQL Server sorts them
+        let sorted = [
+            "01000000-0000-0000-0000-000000000000",
+            "00010000-0000-0000-0000-000000000000",
+            "00000001-0000-0000-0000-000000000000",
+            "00000000-0100-0000-0000-000000000000",
+            "00000000-0001-0000-0000-000000000000",
+            "00000000-0000-0100-0000-000000000000",
+            "00000000-0000-0001-0000-000000000000",
+            "00000000-0000-0000-0001-000000000000",
+            "00000000-0000-0000-0100-000000000000",
+            "00000000-0000-0000-0000-000000000001",
+            "00000000-0000-0000-0000-010000000000",
+        ];
+
+        for pair in sorted.windows(2) {
+            let a = Uuid::parse_str(pair[0]).unwrap();
+            let b = Uuid::parse_str(pair[1]).unwrap();
+
+            assert_eq!(a.cmp_sqlserver(&b), Ordering::Less);
+            assert_eq!(b.cmp_sqlserver(&a), Ordering::Greater);
+        }
+
+        let uuid = test_util::new();
+        assert_eq!(uuid.cmp_sqlserver(&uuid), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_ord() {
+        use std::{cmp::Ordering, vec::Vec};
+
+        let mut uuids = [Uuid::nil(), Uuid::max()]
+            .iter()
+            .cloned()
+            .chain((0..16).flat_map(|i| {
+                [1, 0x7f, 0x80, 0xff].iter().map(move |&byte| {
+                    let mut bytes = [0; 16];
+                    bytes[i] = byte;
+                    Uuid::from_bytes(bytes)
+                })
+            }))
+            .chain(vec![test_util::new(), test_util::new2()])
+            .collect::<Vec<_>>();
+
+        for a in &uuids {
+            for b in &uuids {
+                assert_eq!(a.cmp(b), a.as_bytes().cmp(b.as_bytes()));
+                assert_eq!(a.partial_cmp(b), Some(a.cmp(b)));
+                assert_eq!(a == b, a.cmp(b) == Ordering::Equal);
+            }
+        }
+
+        uuids.sort();
+        assert!(uuids.windows(2).all(|w| w[0].as_bytes() <= w[1].as_bytes()));
+    }
+
+    #[test]
+    fn test_cmp_oracle() {
+        let mut uuids = [
+            test_util::new(),
+            test_util::new2(),
+            Uuid::nil(),
+            Uuid::max(),
+        ];
+        uuids.sort_by(Uuid::cmp_oracle);
+
+        assert_eq!(
+            uuids,
+            [
+                Uuid::nil(),
+                test_util::new(),
+                test_util::new2(),
+                Uuid::max()
+            ]
+        );
+    }
+
+    #[cfg(not(feature = "no-unsafe"))]
+    #[test]
+    fn test_from_bytes_ref() {
+        let mut bytes = *test_util::new().as_bytes();
+
+        assert_eq!(Uuid::from_bytes_ref(&bytes), &test_util::new());
+        assert_eq!(
+            Uuid::from_bytes_ref(&bytes).as_bytes().as_ptr(),
+            bytes.as_ptr()
+        );
+
+        *Uuid::from_bytes_mut(&mut bytes) = test_util::new2();
+        assert_eq!(&bytes, test_util::new2().as_bytes());
+    }
+
+    #[test]
+    fn test_debug() {
+        let u = test_util::new();
+
+        assert_eq!(format!("{:?}", u), u.to_string());
+        assert_eq!(
+            format!("{:?}", Uuid::nil()),
+            "00000000-0000-0000-0000-000000000000"
+        );
+
+        let alternate = format!("{:#?}", u);
+
+        assert_eq!(alternate, format!("{:#?}", u.describe()));
+        assert!(alternate.starts_with("UuidInfo {\n"));
+        assert!(alternate.contains("version: 4,\n"));
+        assert!(!alternate.contains("timestamp"));
+
+        let v1 = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506")
+            .expect("failed to parse a version 1 UUID");
+        let alternate = format!("{:#?}", v1);
+
+        assert!(alternate.contains("version: 1,\n"));
+        assert!(
+            alternate.contains("timestamp: 2017-06-16T14:41:59.000001200Z,\n")
+        );
+        assert!(alternate.contains("node_id: 0x010203040506,\n"));
     }
 
     #[test]
@@ -805,7 +2507,7 @@ mod tests {
 
         assert!(ss.starts_with("urn:uuid:"));
         assert_eq!(s.len(), 36);
-        assert!(s.chars().all(|c| c.is_digit(16) || c == '-'));
+        assert!(s.chars().all(|c| c.is_ascii_hexdigit() || c == '-'));
     }
 
     #[test]
@@ -973,6 +2675,65 @@ mod tests {
         assert_eq!(v_in, v_out.swap_bytes());
     }
 
+    #[test]
+    fn test_from_u64_pair() {
+        let u = Uuid::from_u64_pair(0xa1a2a3a4b1b2c1c2, 0xd1d2d3d4d5d6d7d8);
+
+        let expected = "a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8";
+        let result = u.to_simple().to_string();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_u64_pair_roundtrip() {
+        let high_in: u64 = 0xa1a2a3a4b1b2c1c2;
+        let low_in: u64 = 0xd1d2d3d4d5d6d7d8;
+
+        let u = Uuid::from_u64_pair(high_in, low_in);
+        let (high_out, low_out) = u.as_u64_pair();
+
+        assert_eq!(high_in, high_out);
+        assert_eq!(low_in, low_out);
+        assert_eq!(u.as_u128() >> 64, u128::from(high_out));
+    }
+
+    #[test]
+    fn test_sort_key_orders_v1_by_time() {
+        // (time_low, time_mid, time_hi_and_version) in increasing time order
+        let fields = [
+            (0xffff_ffff, 0x0000, 0x11e7),
+            (0x0000_0000, 0x0001, 0x11e7),
+            (0x0000_0001, 0x0001, 0x11e7),
+            (0x0000_0000, 0xffff, 0x11e7),
+            (0x0000_0000, 0x0000, 0x11e8),
+        ];
+
+        let uuids = fields
+            .iter()
+            .map(|&(d1, d2, d3)| {
+                Uuid::from_fields(d1, d2, d3, &[0x80, 0, 1, 2, 3, 4, 5, 6])
+                    .unwrap()
+            })
+            .collect::<std::vec::Vec<_>>();
+
+        for pair in uuids.windows(2) {
+            assert!(pair[0].sort_key() < pair[1].sort_key());
+        }
+
+        for uuid in &uuids {
+            assert_eq!(uuid.sort_key()[6] >> 4, 1);
+            assert_eq!(Uuid::from_sort_key(uuid.sort_key()), *uuid);
+        }
+    }
+
+    #[test]
+    fn test_sort_key_non_v1() {
+        let uuid = test_util::new();
+
+        assert_eq!(&uuid.sort_key(), uuid.as_bytes());
+        assert_eq!(Uuid::from_sort_key(uuid.sort_key()), uuid);
+    }
+
     #[test]
     fn test_from_slice() {
         let b = [
@@ -1008,6 +2769,24 @@ mod tests {
         assert!(!ub.iter().all(|&b| b == 0));
     }
 
+    #[cfg(not(feature = "no-unsafe"))]
+    #[test]
+    fn test_raw_pointers() {
+        let mut u = test_util::new();
+        let ptr = u.as_ptr();
+
+        assert_eq!(unsafe { *Uuid::from_ptr(ptr) }, test_util::new());
+
+        unsafe { *u.as_mut_ptr().add(7) = 0xab };
+        assert_eq!(u, test_util::new2());
+
+        let buffer = [test_util::new(), test_util::new2()];
+        let uuids =
+            unsafe { Uuid::slice_from_raw_parts(buffer.as_ptr() as _, 2) };
+
+        assert_eq!(uuids, &buffer);
+    }
+
     #[test]
     fn test_bytes_roundtrip() {
         let b_in: crate::Bytes = [
@@ -1027,7 +2806,7 @@ mod tests {
         let mut set = std::collections::HashSet::new();
         let id1 = test_util::new();
         let id2 = test_util::new2();
-        set.insert(id1.clone());
+        set.insert(id1);
 
         assert!(set.contains(&id1));
         assert!(!set.contains(&id2));
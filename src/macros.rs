@@ -0,0 +1,176 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{std::str, Error, ErrorKind};
+
+/// Parses a [`Uuid`] from a string literal at compile time.
+///
+/// Any of the formats accepted by [`Uuid::try_parse`] can be used, and an
+/// invalid literal is a compile time error instead of a runtime one, which
+/// gives the byte offset of the error in the literal. The macro expands to
+/// a constant, so it can be used to define other constants too.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use uuid::{uuid, Uuid};
+///
+/// const NAMESPACE: Uuid = uuid!("936da01f-9abd-4d9d-80c7-02af85c822a8");
+///
+/// assert_eq!(
+///     NAMESPACE,
+///     Uuid::parse_str("936DA01F9ABD4D9D80C702AF85C822A8").unwrap(),
+/// );
+/// ```
+///
+/// Typos don't compile:
+///
+/// ```compile_fail
+/// use uuid::{uuid, Uuid};
+///
+/// const NAMESPACE: Uuid = uuid!("936da01f-9abd-4d9d-80c7-02af85c822ag");
+/// ```
+///
+/// [`Uuid`]: struct.Uuid.html
+/// [`Uuid::try_parse`]: struct.Uuid.html#method.try_parse
+#[macro_export]
+macro_rules! uuid {
+    ($uuid:expr) => {{
+        const OUTPUT: $crate::Uuid = match $crate::Uuid::try_parse($uuid) {
+            Ok(uuid) => uuid,
+            Err(err) => {
+                panic!("{}", $crate::__InvalidLiteral::new(&err).as_str())
+            }
+        };
+
+        OUTPUT
+    }};
+}
+
+/// The compile error message for an invalid [`uuid!`] literal.
+///
+/// Formatting isn't available in `const` evaluation, so the message is
+/// written into a fixed buffer by hand.
+///
+/// [`uuid!`]: macro.uuid.html
+#[derive(Debug)]
+pub struct InvalidLiteral {
+    buf: [u8; 64],
+    len: usize,
+}
+
+impl InvalidLiteral {
+    /// Writes the message for an error from [`Uuid::try_parse`].
+    ///
+    /// [`Uuid::try_parse`]: struct.Uuid.html#method.try_parse
+    pub const fn new(err: &Error) -> Self {
+        let message = InvalidLiteral {
+            buf: [0; 64],
+            len: 0,
+        }
+        .push_str("invalid UUID literal");
+
+        match err.kind() {
+            ErrorKind::InvalidCharacter { index, .. }
+            | ErrorKind::InvalidSeparator { index, .. }
+            | ErrorKind::InvalidGroupCount { index, .. }
+            | ErrorKind::InvalidGroupLength { index, .. } => {
+                message.push_str(" at byte ").push_usize(index)
+            }
+            _ => message,
+        }
+    }
+
+    /// Returns the message.
+    pub const fn as_str(&self) -> &str {
+        match str::from_utf8(self.buf.split_at(self.len).0) {
+            Ok(message) => message,
+            Err(_) => "invalid UUID literal",
+        }
+    }
+
+    const fn push_str(mut self, s: &str) -> Self {
+        let s = s.as_bytes();
+        let mut i = 0;
+
+        while i < s.len() {
+            self.buf[self.len] = s[i];
+            self.len += 1;
+            i += 1;
+        }
+
+        self
+    }
+
+    const fn push_usize(mut self, n: usize) -> Self {
+        let mut digits = [0; 20];
+        let mut len = 0;
+        let mut n = n;
+
+        loop {
+            digits[len] = b'0' + (n % 10) as u8;
+            len += 1;
+            n /= 10;
+
+            if n == 0 {
+                break;
+            }
+        }
+
+        while len > 0 {
+            len -= 1;
+            self.buf[self.len] = digits[len];
+            self.len += 1;
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InvalidLiteral;
+    use crate::prelude::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_uuid_macro() {
+        const UUID: Uuid = uuid!("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4");
+
+        assert_eq!(UUID, test_util::new());
+        assert_eq!(uuid!("F9168C5ECEB24FAAB6BF329BF39FA1E4"), UUID);
+        assert_eq!(
+            uuid!("urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4"),
+            UUID
+        );
+    }
+
+    #[test]
+    fn test_invalid_literal() {
+        fn assert_message(input: &str, expected: &str) {
+            let err = Uuid::try_parse(input).unwrap_err();
+
+            assert_eq!(InvalidLiteral::new(&err).as_str(), expected);
+        }
+
+        assert_message(
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1eX",
+            "invalid UUID literal at byte 35",
+        );
+        assert_message(
+            "urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1eX",
+            "invalid UUID literal at byte 44",
+        );
+        assert_message("f9168c5e", "invalid UUID literal");
+    }
+}
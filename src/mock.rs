@@ -0,0 +1,238 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Deterministic time and randomness for tests.
+//!
+//! Generated UUIDs depend on the current time or on random bytes, which
+//! makes their exact values impossible to assert on. The types in this
+//! module replace both with sources that are fully controlled by the test.
+//!
+//! Note that you need feature `test-util` in order to use this module.
+
+use crate::entropy::EntropySource;
+use crate::std::{cell::Cell, convert::Infallible};
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// A clock that only moves when it's told to.
+///
+/// The time is kept as the seconds and fractional nanoseconds since the
+/// Unix epoch, the same representation taken by
+/// [`Timestamp::from_unix`]. It stops at `u64::MAX` nanoseconds, some
+/// time in the year 2554, instead of overflowing.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::mock::MockClock;
+///
+/// let clock = MockClock::new(1_497_624_119, 0);
+///
+/// clock.advance(1_500_000_000);
+/// assert_eq!(clock.now(), (1_497_624_120, 500_000_000));
+/// ```
+///
+/// [`Timestamp::from_unix`]: ../v1/struct.Timestamp.html#method.from_unix
+#[derive(Clone, Debug)]
+pub struct MockClock {
+    nanos: Cell<u64>,
+    step: u64,
+}
+
+impl MockClock {
+    /// Creates a clock stopped at the given time.
+    pub const fn new(seconds: u64, subsec_nanos: u32) -> Self {
+        MockClock {
+            nanos: Cell::new(to_nanos(seconds, subsec_nanos)),
+            step: 0,
+        }
+    }
+
+    /// Makes the clock advance by `step` nanoseconds every time it's read.
+    ///
+    /// This is useful for generating several distinct time-based UUIDs in
+    /// a row.
+    pub const fn with_step(mut self, step: u64) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Sets the current time.
+    pub fn set(&self, seconds: u64, subsec_nanos: u32) {
+        self.nanos.set(to_nanos(seconds, subsec_nanos));
+    }
+
+    /// Moves the clock forward by `nanos` nanoseconds.
+    pub fn advance(&self, nanos: u64) {
+        self.nanos.set(self.nanos.get().saturating_add(nanos));
+    }
+
+    /// Returns the current time as seconds and fractional nanoseconds
+    /// since the Unix epoch, then advances the clock by its step.
+    pub fn now(&self) -> (u64, u32) {
+        let nanos = self.nanos.get();
+        self.nanos.set(nanos.saturating_add(self.step));
+
+        (nanos / NANOS_PER_SEC, (nanos % NANOS_PER_SEC) as u32)
+    }
+
+    /// Reads the clock as a v1 [`Timestamp`], using `context` for the clock
+    /// sequence.
+    ///
+    /// Note that usage of this method requires the `v1` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::mock::MockClock;
+    /// use uuid::v1::LocalContext;
+    /// use uuid::Uuid;
+    ///
+    /// let clock = MockClock::new(1_497_624_119, 1234);
+    /// let context = LocalContext::new(42);
+    ///
+    /// let uuid = Uuid::new_v1(clock.timestamp(&context), &[1, 2, 3, 4, 5, 6]).unwrap();
+    ///
+    /// assert_eq!(uuid.to_string(), "f3b4958c-52a1-11e7-802a-010203040506");
+    /// ```
+    ///
+    /// [`Timestamp`]: ../v1/struct.Timestamp.html
+    #[cfg(feature = "v1")]
+    pub fn timestamp(
+        &self,
+        context: impl crate::v1::ClockSequence,
+    ) -> crate::v1::Timestamp {
+        let (seconds, subsec_nanos) = self.now();
+
+        crate::v1::Timestamp::from_unix(context, seconds, subsec_nanos)
+    }
+}
+
+const fn to_nanos(seconds: u64, subsec_nanos: u32) -> u64 {
+    seconds
+        .saturating_mul(NANOS_PER_SEC)
+        .saturating_add(subsec_nanos as u64 % NANOS_PER_SEC)
+}
+
+/// A seeded, deterministic [`EntropySource`].
+///
+/// The same seed always produces the same sequence of bytes, so UUIDs
+/// created with [`Uuid::new_v4_from`] can be asserted on exactly. The
+/// bytes are not suitable for anything but tests.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::mock::SeededEntropy;
+/// use uuid::Uuid;
+///
+/// let a = Uuid::new_v4_from(&mut SeededEntropy::new(7)).unwrap();
+/// let b = Uuid::new_v4_from(&mut SeededEntropy::new(7)).unwrap();
+///
+/// assert_eq!(a, b);
+/// ```
+///
+/// [`EntropySource`]: ../entropy/trait.EntropySource.html
+/// [`Uuid::new_v4_from`]: ../struct.Uuid.html#method.new_v4_from
+#[derive(Clone, Debug)]
+pub struct SeededEntropy {
+    state: u64,
+}
+
+impl SeededEntropy {
+    /// Creates a generator from a seed.
+    pub const fn new(seed: u64) -> Self {
+        SeededEntropy { state: seed }
+    }
+
+    /// Returns the next 64 random bits, using SplitMix64.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl EntropySource for SeededEntropy {
+    type Error = Infallible;
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Infallible> {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_mock_clock() {
+        let clock = MockClock::new(10, 999_999_999);
+
+        assert_eq!(clock.now(), (10, 999_999_999));
+        assert_eq!(clock.now(), (10, 999_999_999));
+
+        clock.advance(1);
+        assert_eq!(clock.now(), (11, 0));
+
+        clock.set(5, 5);
+        assert_eq!(clock.now(), (5, 5));
+
+        let clock = MockClock::new(0, 0).with_step(600_000_000);
+        assert_eq!(clock.now(), (0, 0));
+        assert_eq!(clock.now(), (0, 600_000_000));
+        assert_eq!(clock.now(), (1, 200_000_000));
+    }
+
+    #[test]
+    fn test_mock_clock_saturates() {
+        let end = (u64::MAX / NANOS_PER_SEC, (u64::MAX % NANOS_PER_SEC) as u32);
+
+        let clock = MockClock::new(u64::MAX, 0).with_step(1);
+        assert_eq!(clock.now(), end);
+        assert_eq!(clock.now(), end);
+
+        clock.set(end.0, end.1 - 1);
+        clock.advance(u64::MAX);
+        assert_eq!(clock.now(), end);
+    }
+
+    #[test]
+    fn test_seeded_entropy() {
+        let mut a = SeededEntropy::new(0);
+        let mut b = SeededEntropy::new(0);
+        let mut c = SeededEntropy::new(1);
+
+        let (mut x, mut y, mut z) = ([0; 20], [0; 20], [0; 20]);
+        a.fill_bytes(&mut x).unwrap();
+        b.fill_bytes(&mut y).unwrap();
+        c.fill_bytes(&mut z).unwrap();
+
+        assert_eq!(x, y);
+        assert_ne!(x, z);
+
+        // the first SplitMix64 output for a zero seed
+        assert_eq!(x[..8], 0xe220_a839_7b1d_cdaf_u64.to_le_bytes());
+
+        let uuid = Uuid::new_v4_from(&mut SeededEntropy::new(0)).unwrap();
+        assert_eq!(uuid.get_version(), Some(Version::Random));
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions between [`Uuid`]s and the BSON binary values used by
+//! MongoDB.
+//!
+//! [`Uuid`]: ../struct.Uuid.html
+
+use crate::guid::Guid;
+use crate::prelude::*;
+
+/// The BSON binary subtype for UUIDs.
+pub const BINARY_SUBTYPE_UUID: u8 = 4;
+
+/// The BSON binary subtype for UUIDs written by legacy drivers.
+pub const BINARY_SUBTYPE_UUID_OLD: u8 = 3;
+
+/// The byte orders MongoDB drivers have used to store UUIDs.
+///
+/// Only [`Standard`] uses binary subtype 4. Legacy drivers used subtype 3
+/// with a byte order that depends on the language of the driver, so the
+/// representation of stored values has to be known to read them back.
+///
+/// [`Standard`]: #variant.Standard
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum UuidRepresentation {
+    /// The bytes of the UUID in order, stored with subtype 4.
+    Standard,
+    /// The mixed-endian order of the legacy C# driver.
+    CSharpLegacy,
+    /// Both halves of the UUID reversed, as written by the legacy Java
+    /// driver.
+    JavaLegacy,
+    /// The bytes of the UUID in order, stored with subtype 3 by the legacy
+    /// Python driver.
+    PythonLegacy,
+}
+
+impl UuidRepresentation {
+    /// Returns the BSON binary subtype values in this representation are
+    /// stored with.
+    pub fn subtype(self) -> u8 {
+        match self {
+            UuidRepresentation::Standard => BINARY_SUBTYPE_UUID,
+            _ => BINARY_SUBTYPE_UUID_OLD,
+        }
+    }
+}
+
+impl Uuid {
+    /// Returns the bytes of a BSON binary value holding this UUID.
+    ///
+    /// The value should be stored with the subtype returned by
+    /// [`UuidRepresentation::subtype`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{mongodb::UuidRepresentation, Uuid};
+    ///
+    /// let uuid = Uuid::parse_str("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+    ///
+    /// assert_eq!(
+    ///     uuid.to_bson_bytes(UuidRepresentation::JavaLegacy),
+    ///     [
+    ///         0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x00,
+    ///         0xff, 0xee, 0xdd, 0xcc, 0xbb, 0xaa, 0x99, 0x88,
+    ///     ],
+    /// );
+    /// ```
+    ///
+    /// [`UuidRepresentation::subtype`]: mongodb/enum.UuidRepresentation.html#method.subtype
+    pub fn to_bson_bytes(&self, representation: UuidRepresentation) -> Bytes {
+        match representation {
+            UuidRepresentation::Standard | UuidRepresentation::PythonLegacy => {
+                *self.as_bytes()
+            }
+            UuidRepresentation::CSharpLegacy => Guid::from(*self).to_bytes_le(),
+            UuidRepresentation::JavaLegacy => {
+                let mut bytes = *self.as_bytes();
+
+                bytes[..8].reverse();
+                bytes[8..].reverse();
+
+                bytes
+            }
+        }
+    }
+
+    /// Creates a UUID from the bytes of a BSON binary value.
+    ///
+    /// This is the inverse of [`Uuid::to_bson_bytes`]. The subtype of a
+    /// stored value tells [`Standard`] values apart from legacy ones, but
+    /// not which legacy representation was used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{mongodb::UuidRepresentation, Uuid};
+    ///
+    /// let uuid = Uuid::parse_str("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+    /// let bytes = uuid.to_bson_bytes(UuidRepresentation::CSharpLegacy);
+    ///
+    /// assert_eq!(
+    ///     Uuid::from_bson_bytes(bytes, UuidRepresentation::CSharpLegacy),
+    ///     uuid,
+    /// );
+    /// assert_ne!(
+    ///     Uuid::from_bson_bytes(bytes, UuidRepresentation::PythonLegacy),
+    ///     uuid,
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::to_bson_bytes`]: struct.Uuid.html#method.to_bson_bytes
+    /// [`Standard`]: mongodb/enum.UuidRepresentation.html#variant.Standard
+    pub fn from_bson_bytes(
+        bytes: Bytes,
+        representation: UuidRepresentation,
+    ) -> Uuid {
+        // every representation is its own inverse
+        Uuid::from_bytes(Uuid::from_bytes(bytes).to_bson_bytes(representation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    const ALL: [UuidRepresentation; 4] = [
+        UuidRepresentation::Standard,
+        UuidRepresentation::CSharpLegacy,
+        UuidRepresentation::JavaLegacy,
+        UuidRepresentation::PythonLegacy,
+    ];
+
+    #[test]
+    fn test_bson_bytes() {
+        let uuid =
+            Uuid::parse_str("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+
+        assert_eq!(
+            uuid.to_bson_bytes(UuidRepresentation::Standard),
+            *uuid.as_bytes()
+        );
+        assert_eq!(
+            uuid.to_bson_bytes(UuidRepresentation::PythonLegacy),
+            *uuid.as_bytes()
+        );
+        assert_eq!(
+            uuid.to_bson_bytes(UuidRepresentation::CSharpLegacy),
+            [
+                0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99,
+                0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+            ]
+        );
+        assert_eq!(
+            uuid.to_bson_bytes(UuidRepresentation::JavaLegacy),
+            [
+                0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x00, 0xff, 0xee,
+                0xdd, 0xcc, 0xbb, 0xaa, 0x99, 0x88,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bson_roundtrip() {
+        let uuid = test_util::new();
+
+        for &representation in &ALL {
+            let bytes = uuid.to_bson_bytes(representation);
+
+            assert_eq!(Uuid::from_bson_bytes(bytes, representation), uuid);
+        }
+    }
+
+    #[test]
+    fn test_subtype() {
+        assert_eq!(UuidRepresentation::Standard.subtype(), 4);
+
+        for &representation in &ALL[1..] {
+            assert_eq!(representation.subtype(), 3);
+        }
+    }
+}
@@ -0,0 +1,556 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Namespaces for name-based UUIDs.
+
+use crate::prelude::*;
+use crate::std::fmt;
+#[cfg(all(feature = "std", any(feature = "v3", feature = "v5")))]
+use crate::std::io;
+
+/// A namespace that name-based version 3 and 5 UUIDs are generated in.
+///
+/// The same name in the same namespace always gives the same UUID, and
+/// different namespaces keep names from clashing. Besides the namespaces
+/// from RFC4122, such as [`Namespace::DNS`], an organization or
+/// application can declare its own from any UUID it owns, usually a random
+/// one generated once and then fixed in the source:
+///
+/// ```
+/// use uuid::{namespace::Namespace, uuid};
+///
+/// /// The namespace for the IDs of our customers.
+/// const CUSTOMERS: Namespace =
+///     Namespace::new(uuid!("936da01f-9abd-4d9d-80c7-02af85c822a8"));
+///
+/// assert_eq!(CUSTOMERS.as_uuid().to_string(), "936da01f-9abd-4d9d-80c7-02af85c822a8");
+/// ```
+///
+/// Don't use a namespace for anything else, and never change it once
+/// UUIDs were generated in it, as that changes every UUID.
+///
+/// [`Namespace::DNS`]: #associatedconstant.DNS
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct Namespace(Uuid);
+
+impl Namespace {
+    /// The namespace for fully qualified domain names, from RFC4122.
+    pub const DNS: Self = Namespace(Uuid::NAMESPACE_DNS);
+
+    /// The namespace for ISO Object Identifiers, from RFC4122.
+    pub const OID: Self = Namespace(Uuid::NAMESPACE_OID);
+
+    /// The namespace for URLs, from RFC4122.
+    pub const URL: Self = Namespace(Uuid::NAMESPACE_URL);
+
+    /// The namespace for X.500 Distinguished Names in DER or text format,
+    /// from RFC4122.
+    pub const X500: Self = Namespace(Uuid::NAMESPACE_X500);
+
+    /// Creates a namespace identified by `uuid`.
+    pub const fn new(uuid: Uuid) -> Self {
+        Namespace(uuid)
+    }
+
+    /// Returns the UUID that identifies the namespace.
+    pub const fn as_uuid(&self) -> &Uuid {
+        &self.0
+    }
+
+    /// Consumes the namespace, returning the UUID that identifies it.
+    pub const fn into_uuid(self) -> Uuid {
+        self.0
+    }
+
+    /// Starts an MD5 based version 3 UUID for a name from this namespace
+    /// that is hashed incrementally.
+    ///
+    /// Note that usage of this method requires the `v3` feature of this crate
+    /// to be enabled.
+    #[cfg(feature = "v3")]
+    pub fn hasher_v3(&self) -> V3Hasher {
+        V3Hasher::new(self)
+    }
+
+    /// Starts a SHA-1 based version 5 UUID for a name from this namespace
+    /// that is hashed incrementally.
+    ///
+    /// Note that usage of this method requires the `v5` feature of this crate
+    /// to be enabled.
+    #[cfg(feature = "v5")]
+    pub fn hasher_v5(&self) -> V5Hasher {
+        V5Hasher::new(self)
+    }
+}
+
+/// A hash function that name-based UUIDs can be generated with.
+///
+/// [`Uuid::new_v3`] and [`Uuid::new_v5`] hash with the `md5` and `sha1`
+/// crates. To use another implementation of MD5 or SHA-1 instead, such as
+/// a hardware accelerated or certified one, implement this trait for it
+/// and pass it to [`Uuid::new_v3_with`] or [`Uuid::new_v5_with`]. These
+/// don't need the `v3` or `v5` features, so the default implementations
+/// don't have to be built at all.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::{namespace::Digest, Bytes, Uuid};
+///
+/// /// A stand-in for another SHA-1 implementation, which only shows the
+/// /// shape of one, as XOR-ing the input together isn't a hash.
+/// struct OtherSha1 {
+///     state: Bytes,
+///     len: usize,
+/// }
+///
+/// impl Digest for OtherSha1 {
+///     fn update(&mut self, data: &[u8]) {
+///         for byte in data {
+///             self.state[self.len % 16] ^= byte;
+///             self.len += 1;
+///         }
+///     }
+///
+///     fn finish(self) -> Bytes {
+///         self.state
+///     }
+/// }
+///
+/// let uuid = Uuid::new_v5_with(
+///     OtherSha1 { state: [0; 16], len: 0 },
+///     &Uuid::NAMESPACE_DNS,
+///     b"rust-lang.org",
+/// );
+///
+/// assert_eq!(uuid.get_version_num(), 5);
+/// ```
+///
+/// [`Uuid::new_v3`]: ../struct.Uuid.html#method.new_v3
+/// [`Uuid::new_v5`]: ../struct.Uuid.html#method.new_v5
+/// [`Uuid::new_v3_with`]: ../struct.Uuid.html#method.new_v3_with
+/// [`Uuid::new_v5_with`]: ../struct.Uuid.html#method.new_v5_with
+pub trait Digest {
+    /// Hashes the next chunk of data.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the first 16 bytes of the digest of all the data.
+    fn finish(self) -> Bytes;
+}
+
+#[cfg(feature = "v3")]
+impl Digest for md5::Context {
+    fn update(&mut self, data: &[u8]) {
+        self.consume(data);
+    }
+
+    fn finish(self) -> Bytes {
+        self.compute().into()
+    }
+}
+
+#[cfg(feature = "v5")]
+impl Digest for sha1::Sha1 {
+    fn update(&mut self, data: &[u8]) {
+        sha1::Sha1::update(self, data);
+    }
+
+    fn finish(self) -> Bytes {
+        let mut bytes = Bytes::default();
+        bytes.copy_from_slice(&self.digest().bytes()[..16]);
+        bytes
+    }
+}
+
+impl Uuid {
+    /// Creates a UUID using a name from a namespace, based on the MD5 hash
+    /// computed by `digest`.
+    ///
+    /// This is the same as [`Uuid::new_v3`], but with a [`Digest`] of your
+    /// choice. `digest` must compute MD5 for the result to be a valid
+    /// version 3 UUID.
+    ///
+    /// [`Uuid::new_v3`]: #method.new_v3
+    /// [`Digest`]: namespace/trait.Digest.html
+    pub fn new_v3_with(
+        digest: impl Digest,
+        namespace: &Uuid,
+        name: &[u8],
+    ) -> Uuid {
+        name_based(digest, namespace, name, Version::Md5)
+    }
+
+    /// Creates a UUID using a name from a namespace, based on the SHA-1
+    /// hash computed by `digest`.
+    ///
+    /// This is the same as [`Uuid::new_v5`], but with a [`Digest`] of your
+    /// choice. `digest` must compute SHA-1 for the result to be a valid
+    /// version 5 UUID.
+    ///
+    /// [`Uuid::new_v5`]: #method.new_v5
+    /// [`Digest`]: namespace/trait.Digest.html
+    pub fn new_v5_with(
+        digest: impl Digest,
+        namespace: &Uuid,
+        name: &[u8],
+    ) -> Uuid {
+        name_based(digest, namespace, name, Version::Sha1)
+    }
+}
+
+fn name_based(
+    mut digest: impl Digest,
+    namespace: &Uuid,
+    name: &[u8],
+    version: Version,
+) -> Uuid {
+    digest.update(namespace.as_bytes());
+    digest.update(name);
+
+    from_digest(digest, version)
+}
+
+fn from_digest(digest: impl Digest, version: Version) -> Uuid {
+    let mut builder = crate::builder::Builder::from_bytes(digest.finish());
+    builder.set_variant(Variant::RFC4122).set_version(version);
+
+    builder.build()
+}
+
+/// Hashes a name for a version 3 UUID incrementally.
+///
+/// Feeding the name in any number of chunks gives the same UUID as
+/// [`Uuid::new_v3`] with the whole name, without ever holding all of it in
+/// memory. With the `std` feature, the hasher is also an [`io::Write`], so
+/// a file or other reader can be hashed with [`io::copy`].
+///
+/// Note that usage of this type requires the `v3` feature of this crate to
+/// be enabled.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::{namespace::Namespace, Uuid};
+///
+/// let mut hasher = Namespace::DNS.hasher_v3();
+/// hasher.update(b"rust-");
+/// hasher.update(b"lang.org");
+///
+/// assert_eq!(
+///     hasher.finish(),
+///     Uuid::new_v3(&Uuid::NAMESPACE_DNS, b"rust-lang.org"),
+/// );
+/// ```
+///
+/// [`Uuid::new_v3`]: ../struct.Uuid.html#method.new_v3
+/// [`io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+/// [`io::copy`]: https://doc.rust-lang.org/std/io/fn.copy.html
+#[cfg(feature = "v3")]
+#[derive(Clone)]
+pub struct V3Hasher(md5::Context);
+
+#[cfg(feature = "v3")]
+impl V3Hasher {
+    /// Starts hashing a name from `namespace`.
+    pub fn new(namespace: &Namespace) -> Self {
+        let mut context = md5::Context::new();
+        context.consume(namespace.as_uuid().as_bytes());
+
+        V3Hasher(context)
+    }
+
+    /// Hashes the next chunk of the name.
+    pub fn update(&mut self, name: &[u8]) {
+        self.0.consume(name);
+    }
+
+    /// Returns the version 3 UUID for the name hashed so far.
+    pub fn finish(self) -> Uuid {
+        from_digest(self.0, Version::Md5)
+    }
+}
+
+#[cfg(feature = "v3")]
+impl fmt::Debug for V3Hasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("V3Hasher").finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(feature = "std", feature = "v3"))]
+impl io::Write for V3Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Hashes a name for a version 5 UUID incrementally.
+///
+/// Feeding the name in any number of chunks gives the same UUID as
+/// [`Uuid::new_v5`] with the whole name, without ever holding all of it in
+/// memory. With the `std` feature, the hasher is also an [`io::Write`], so
+/// a file or other reader can be hashed with [`io::copy`].
+///
+/// Note that usage of this type requires the `v5` feature of this crate to
+/// be enabled.
+///
+/// # Examples
+///
+/// ```
+/// use std::{fs::File, io};
+/// use uuid::{namespace::Namespace, Uuid};
+///
+/// # fn main() -> io::Result<()> {
+/// let mut hasher = Namespace::URL.hasher_v5();
+/// io::copy(&mut File::open("Cargo.toml")?, &mut hasher)?;
+///
+/// let uuid = hasher.finish();
+/// assert_eq!(
+///     uuid,
+///     Uuid::new_v5(&Uuid::NAMESPACE_URL, &std::fs::read("Cargo.toml")?),
+/// );
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Uuid::new_v5`]: ../struct.Uuid.html#method.new_v5
+/// [`io::Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+/// [`io::copy`]: https://doc.rust-lang.org/std/io/fn.copy.html
+#[cfg(feature = "v5")]
+#[derive(Clone)]
+pub struct V5Hasher(sha1::Sha1);
+
+#[cfg(feature = "v5")]
+impl V5Hasher {
+    /// Starts hashing a name from `namespace`.
+    pub fn new(namespace: &Namespace) -> Self {
+        let mut hash = sha1::Sha1::new();
+        hash.update(namespace.as_uuid().as_bytes());
+
+        V5Hasher(hash)
+    }
+
+    /// Hashes the next chunk of the name.
+    pub fn update(&mut self, name: &[u8]) {
+        self.0.update(name);
+    }
+
+    /// Returns the version 5 UUID for the name hashed so far.
+    pub fn finish(self) -> Uuid {
+        from_digest(self.0, Version::Sha1)
+    }
+}
+
+#[cfg(feature = "v5")]
+impl fmt::Debug for V5Hasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("V5Hasher").finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(feature = "std", feature = "v5"))]
+impl io::Write for V5Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl From<Uuid> for Namespace {
+    fn from(uuid: Uuid) -> Self {
+        Namespace(uuid)
+    }
+}
+
+impl From<Namespace> for Uuid {
+    fn from(namespace: Namespace) -> Self {
+        namespace.0
+    }
+}
+
+impl AsRef<Uuid> for Namespace {
+    fn as_ref(&self) -> &Uuid {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Namespace").field(&self.0).finish()
+    }
+}
+
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_namespace() {
+        assert_eq!(Namespace::DNS.into_uuid(), Uuid::NAMESPACE_DNS);
+        assert_eq!(*Namespace::OID.as_uuid(), Uuid::NAMESPACE_OID);
+        assert_eq!(Uuid::from(Namespace::URL), Uuid::NAMESPACE_URL);
+        assert_eq!(Namespace::X500.as_ref(), &Uuid::NAMESPACE_X500);
+
+        const CUSTOM: Namespace = Namespace::new(test_util::new());
+        assert_eq!(Namespace::from(test_util::new()), CUSTOM);
+    }
+
+    #[cfg(feature = "v3")]
+    #[test]
+    fn test_new_v3_with() {
+        /// Counts the hashed bytes, to check it was used.
+        struct Counting<'a>(md5::Context, &'a mut usize);
+
+        impl Digest for Counting<'_> {
+            fn update(&mut self, data: &[u8]) {
+                *self.1 += data.len();
+                Digest::update(&mut self.0, data);
+            }
+
+            fn finish(self) -> Bytes {
+                self.0.finish()
+            }
+        }
+
+        let mut len = 0;
+        let uuid = Uuid::new_v3_with(
+            Counting(md5::Context::new(), &mut len),
+            &Uuid::NAMESPACE_DNS,
+            b"rust-lang.org",
+        );
+
+        assert_eq!(len, 16 + 13);
+        assert_eq!(uuid, Uuid::new_v3(&Uuid::NAMESPACE_DNS, b"rust-lang.org"));
+    }
+
+    #[test]
+    fn test_new_v5_with() {
+        /// Returns the input as the digest, which is enough to check the
+        /// version and variant are set.
+        struct Identity(Bytes, usize);
+
+        impl Digest for Identity {
+            fn update(&mut self, data: &[u8]) {
+                for &byte in data {
+                    if self.1 < 16 {
+                        self.0[self.1] = byte;
+                    }
+                    self.1 += 1;
+                }
+            }
+
+            fn finish(self) -> Bytes {
+                self.0
+            }
+        }
+
+        let uuid =
+            Uuid::new_v5_with(Identity([0; 16], 0), &Uuid::max(), b"name");
+
+        assert_eq!(uuid.get_version(), Some(Version::Sha1));
+        assert_eq!(uuid.get_variant(), Some(Variant::RFC4122));
+        assert_eq!(
+            uuid,
+            Uuid::parse_str("ffffffff-ffff-5fff-bfff-ffffffffffff").unwrap()
+        );
+    }
+
+    #[cfg(feature = "v3")]
+    #[test]
+    fn test_hasher_v3() {
+        let name = b"lorem ipsum dolor sit amet";
+
+        for split in 0..=name.len() {
+            let mut hasher = Namespace::URL.hasher_v3();
+            hasher.update(&name[..split]);
+            hasher.update(&name[split..]);
+
+            assert_eq!(
+                hasher.finish(),
+                Uuid::new_v3(&Uuid::NAMESPACE_URL, name)
+            );
+        }
+
+        assert_eq!(
+            V3Hasher::new(&Namespace::DNS).finish(),
+            Uuid::new_v3(&Uuid::NAMESPACE_DNS, b"")
+        );
+    }
+
+    #[cfg(feature = "v5")]
+    #[test]
+    fn test_hasher_v5() {
+        // longer than a SHA-1 block
+        let name = [0x5a; 150];
+
+        for split in 0..=name.len() {
+            let mut hasher = Namespace::URL.hasher_v5();
+            hasher.update(&name[..split]);
+            hasher.update(&name[split..]);
+
+            assert_eq!(
+                hasher.finish(),
+                Uuid::new_v5(&Uuid::NAMESPACE_URL, &name)
+            );
+        }
+
+        assert_eq!(
+            V5Hasher::new(&Namespace::DNS).finish(),
+            Uuid::new_v5(&Uuid::NAMESPACE_DNS, b"")
+        );
+    }
+
+    #[cfg(all(feature = "std", feature = "v5"))]
+    #[test]
+    fn test_hasher_write() {
+        use crate::std::io::{self, Write};
+
+        let name = [0xa5; 1000];
+
+        let mut hasher = Namespace::OID.hasher_v5();
+        io::copy(&mut &name[..], &mut hasher).unwrap();
+        hasher.flush().unwrap();
+
+        assert_eq!(hasher.finish(), Uuid::new_v5(&Uuid::NAMESPACE_OID, &name));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_namespace_fmt() {
+        use crate::std::string::ToString;
+
+        assert_eq!(
+            Namespace::DNS.to_string(),
+            "6ba7b810-9dad-11d1-80b4-00c04fd430c8"
+        );
+        assert_eq!(
+            format!("{:?}", Namespace::DNS),
+            "Namespace(6ba7b810-9dad-11d1-80b4-00c04fd430c8)"
+        );
+    }
+}
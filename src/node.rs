@@ -0,0 +1,466 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Sources of node IDs for time-based UUIDs.
+//!
+//! A version 1 UUID ends in a 6 byte node ID that should be unique to the
+//! host generating it. [`NodeSource`] abstracts over how it's picked, so
+//! the policy can be chosen by configuration instead of at every call to
+//! [`Uuid::new_v1`]:
+//!
+//! * a fixed `[u8; 6]`,
+//! * [`MacAddress`], the address of a network interface,
+//! * [`PersistedRandom`], a random ID stored in a file the first time it's
+//!   needed, and
+//! * [`HostnameHash`], a hash of the host name.
+//!
+//! Note that you need features `v1` and `std` in order to use this module.
+//!
+//! # Examples
+//!
+//! ```
+//! use uuid::{
+//!     node::{HostnameHash, NodeSource},
+//!     v1::{Context, Timestamp},
+//!     Uuid,
+//! };
+//!
+//! let source: Box<dyn NodeSource> = match std::env::var("NODE_ID_SOURCE") {
+//!     Ok(ref fixed) if fixed == "fixed" => Box::new([1, 2, 3, 4, 5, 6]),
+//!     _ => Box::new(HostnameHash::with_hostname("build-42")),
+//! };
+//!
+//! let context = Context::new(0);
+//! let ts = Timestamp::from_unix(&context, 1497624119, 1234);
+//! let uuid = Uuid::new_v1(ts, &source.node_id()?).expect("failed to generate UUID");
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! [`NodeSource`]: trait.NodeSource.html
+//! [`MacAddress`]: struct.MacAddress.html
+//! [`PersistedRandom`]: struct.PersistedRandom.html
+//! [`HostnameHash`]: struct.HostnameHash.html
+//! [`Uuid::new_v1`]: ../struct.Uuid.html#method.new_v1
+
+use crate::std::{borrow::ToOwned, boxed::Box, io, string::String};
+#[cfg(feature = "v4")]
+use crate::std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    string::ToString,
+};
+
+pub use crate::node_probe::NodeId;
+
+/// The bit of the first byte that marks a node ID as a multicast address,
+/// which no network card uses, so RFC4122 sets it on node IDs that aren't
+/// MAC addresses.
+const MULTICAST: u8 = 0x01;
+
+/// The bit of the first byte that marks a MAC address as locally
+/// administered, instead of assigned by the manufacturer.
+#[cfg(target_os = "linux")]
+const LOCAL: u8 = 0x02;
+
+/// A way to pick the node ID of time-based UUIDs.
+pub trait NodeSource {
+    /// Returns the node ID.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the node ID can't be
+    /// determined, such as when there's no network interface or a file
+    /// can't be read.
+    fn node_id(&self) -> io::Result<NodeId>;
+}
+
+impl NodeSource for NodeId {
+    fn node_id(&self) -> io::Result<NodeId> {
+        Ok(*self)
+    }
+}
+
+impl<T: NodeSource + ?Sized> NodeSource for &T {
+    fn node_id(&self) -> io::Result<NodeId> {
+        (**self).node_id()
+    }
+}
+
+impl<T: NodeSource + ?Sized> NodeSource for Box<T> {
+    fn node_id(&self) -> io::Result<NodeId> {
+        (**self).node_id()
+    }
+}
+
+/// Uses the MAC address of a network interface as the node ID.
+///
+/// The interfaces are tried in order of their names, and addresses
+/// assigned by a manufacturer are preferred over locally administered
+/// ones, which virtual interfaces usually have. The loopback interface and
+/// all-zero addresses are skipped.
+///
+/// This is the node ID RFC4122 intends, but it reveals the address of the
+/// host in every UUID. It's only supported on Linux, through `/sys`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MacAddress;
+
+impl NodeSource for MacAddress {
+    #[cfg(target_os = "linux")]
+    fn node_id(&self) -> io::Result<NodeId> {
+        use crate::std::{fs, vec::Vec};
+
+        let mut interfaces = fs::read_dir("/sys/class/net")?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect::<Vec<_>>();
+        interfaces.sort();
+
+        let addresses = interfaces.iter().filter_map(|interface| {
+            let address = fs::read_to_string(interface.join("address")).ok()?;
+
+            parse_node_id(address.trim())
+                .filter(|node| *node != [0; 6] && node[0] & MULTICAST == 0)
+        });
+
+        let mut local = None;
+        for address in addresses {
+            if address[0] & LOCAL == 0 {
+                return Ok(address);
+            }
+
+            local = local.or(Some(address));
+        }
+
+        local.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "no MAC address found")
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    #[allow(clippy::io_other_error)] // `io::Error::other` needs Rust 1.74
+    fn node_id(&self) -> io::Result<NodeId> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "reading MAC addresses isn't supported on this platform",
+        ))
+    }
+}
+
+/// Uses a random node ID that's stored in a file the first time it's
+/// needed, and read from there afterwards.
+///
+/// This keeps the node ID stable across restarts of a process, without
+/// revealing anything about the host. The file holds the ID as text, like
+/// `03:1a:2b:3c:4d:5e`, so it can be inspected or set up in advance. The
+/// multicast bit of generated IDs is set, so they can't clash with MAC
+/// addresses.
+///
+/// Note that usage of this type requires the `v4` feature of this crate to
+/// be enabled.
+#[cfg(feature = "v4")]
+#[derive(Clone, Debug)]
+pub struct PersistedRandom {
+    path: PathBuf,
+}
+
+#[cfg(feature = "v4")]
+impl PersistedRandom {
+    /// Creates a node source that stores its ID in the file at `path`.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        PersistedRandom {
+            path: path.as_ref().to_owned(),
+        }
+    }
+
+    fn read(&self) -> io::Result<NodeId> {
+        let text = fs::read_to_string(&self.path)?;
+
+        parse_node_id(text.trim()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the node ID file doesn't hold a node ID",
+            )
+        })
+    }
+}
+
+#[cfg(feature = "v4")]
+impl NodeSource for PersistedRandom {
+    #[allow(clippy::io_other_error)] // `io::Error::other` needs Rust 1.74
+    fn node_id(&self) -> io::Result<NodeId> {
+        match self.read() {
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => (),
+            result => return result,
+        }
+
+        let mut node = [0; 6];
+        let mut suffix = [0; 8];
+        getrandom::getrandom(&mut node)
+            .and_then(|()| getrandom::getrandom(&mut suffix))
+            .map_err(|err| {
+                io::Error::new(io::ErrorKind::Other, err.to_string())
+            })?;
+        node[0] |= MULTICAST;
+
+        // the ID is written to a temporary file and then linked into place,
+        // so readers never see a partly written file. Another process may
+        // get there first, in which case its ID wins.
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(format!(".{:016x}.tmp", u64::from_le_bytes(suffix)));
+
+        let result = write_new(temp.as_ref(), &node)
+            .and_then(|()| fs::hard_link(&temp, &self.path));
+        let _ = fs::remove_file(&temp);
+
+        match result {
+            Ok(()) => Ok(node),
+            Err(ref err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                self.read()
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Writes a node ID to a new file at `path`.
+#[cfg(feature = "v4")]
+fn write_new(path: &Path, node: &NodeId) -> io::Result<()> {
+    let mut file =
+        OpenOptions::new().write(true).create_new(true).open(path)?;
+
+    writeln!(file, "{}", format_node_id(node))?;
+    file.sync_all()
+}
+
+/// Uses a hash of the host name as the node ID.
+///
+/// This is stable for as long as the host name doesn't change, and doesn't
+/// need any state, but hosts must have unique names for their node IDs to
+/// be unique. The multicast bit is set, so the IDs can't clash with MAC
+/// addresses.
+#[derive(Clone, Debug, Default)]
+pub struct HostnameHash {
+    hostname: Option<String>,
+}
+
+impl HostnameHash {
+    /// Creates a node source that hashes the name of this host.
+    ///
+    /// The name is read from `/proc/sys/kernel/hostname` or `/etc/hostname`
+    /// on Unix-like systems, and from the `COMPUTERNAME` environment
+    /// variable on Windows.
+    pub fn new() -> Self {
+        HostnameHash::default()
+    }
+
+    /// Creates a node source that hashes `hostname`, instead of the name
+    /// of this host.
+    pub fn with_hostname(hostname: impl Into<String>) -> Self {
+        HostnameHash {
+            hostname: Some(hostname.into()),
+        }
+    }
+}
+
+impl NodeSource for HostnameHash {
+    fn node_id(&self) -> io::Result<NodeId> {
+        let hostname = match self.hostname {
+            Some(ref hostname) => hostname.clone(),
+            None => hostname()?,
+        };
+
+        // 64-bit FNV-1a, which is stable across platforms and releases
+        let hash = hostname.trim().bytes().fold(
+            0xcbf2_9ce4_8422_2325,
+            |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            },
+        );
+
+        let mut node = [0; 6];
+        node.copy_from_slice(&hash.to_be_bytes()[2..]);
+        node[0] |= MULTICAST;
+
+        Ok(node)
+    }
+}
+
+fn hostname() -> io::Result<String> {
+    use crate::std::{env, fs};
+
+    let from_file = ["/proc/sys/kernel/hostname", "/etc/hostname"]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .chain(env::var("COMPUTERNAME").ok())
+        .map(|name| name.trim().to_owned())
+        .find(|name| !name.is_empty());
+
+    from_file.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "no host name found")
+    })
+}
+
+/// Parses a node ID written as 12 hex digits, optionally in pairs
+/// separated by `:` or `-`.
+fn parse_node_id(input: &str) -> Option<NodeId> {
+    let mut digits = input.bytes().filter(|&b| b != b':' && b != b'-');
+    let mut node = [0; 6];
+
+    for byte in &mut node {
+        let high = (digits.next()? as char).to_digit(16)?;
+        let low = (digits.next()? as char).to_digit(16)?;
+        *byte = (high << 4 | low) as u8;
+    }
+
+    if digits.next().is_some() {
+        return None;
+    }
+
+    Some(node)
+}
+
+#[cfg(feature = "v4")]
+fn format_node_id(node: &NodeId) -> String {
+    use crate::std::fmt::Write as _;
+
+    let mut text = String::with_capacity(17);
+    for (i, byte) in node.iter().enumerate() {
+        if i > 0 {
+            text.push(':');
+        }
+        let _ = write!(text, "{:02x}", byte);
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed() {
+        let node = [1, 2, 3, 4, 5, 6];
+        let borrowed: &dyn NodeSource = &node;
+        let boxed: Box<dyn NodeSource> = Box::new(node);
+
+        assert_eq!(node.node_id().unwrap(), node);
+        assert_eq!(borrowed.node_id().unwrap(), node);
+        assert_eq!(boxed.node_id().unwrap(), node);
+    }
+
+    #[test]
+    fn test_parse_node_id() {
+        let node = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab];
+
+        assert_eq!(parse_node_id("01:23:45:67:89:ab"), Some(node));
+        assert_eq!(parse_node_id("01-23-45-67-89-AB"), Some(node));
+        assert_eq!(parse_node_id("0123456789ab"), Some(node));
+
+        assert_eq!(parse_node_id(""), None);
+        assert_eq!(parse_node_id("01:23:45:67:89"), None);
+        assert_eq!(parse_node_id("01:23:45:67:89:ab:cd"), None);
+        assert_eq!(parse_node_id("01:23:45:67:89:ag"), None);
+        assert_eq!(parse_node_id("01:23:45:67:89:\u{e9}"), None);
+    }
+
+    #[test]
+    fn test_hostname_hash() {
+        let a = HostnameHash::with_hostname("a.example.com")
+            .node_id()
+            .unwrap();
+        let b = HostnameHash::with_hostname("b.example.com")
+            .node_id()
+            .unwrap();
+
+        assert_ne!(a, b);
+        assert_eq!(a[0] & MULTICAST, MULTICAST);
+        assert_eq!(
+            HostnameHash::with_hostname("a.example.com\n")
+                .node_id()
+                .unwrap(),
+            a
+        );
+
+        // pinned, as changing the hash changes every node ID
+        assert_eq!(
+            HostnameHash::with_hostname("").node_id().unwrap(),
+            [0x9d, 0xe4, 0x84, 0x22, 0x23, 0x25]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_mac_address() {
+        // sandboxes may have no interfaces besides loopback
+        if let Ok(node) = MacAddress.node_id() {
+            assert_ne!(node, [0; 6]);
+            assert_eq!(node[0] & MULTICAST, 0);
+        }
+    }
+
+    #[cfg(feature = "v4")]
+    #[test]
+    fn test_persisted_random() {
+        let path = crate::std::env::temp_dir().join(format!(
+            "uuid-node-{}-{:?}",
+            crate::std::process::id(),
+            crate::std::thread::current().id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let source = PersistedRandom::new(&path);
+        let node = source.node_id().unwrap();
+
+        assert_eq!(node[0] & MULTICAST, MULTICAST);
+        assert_eq!(source.node_id().unwrap(), node);
+        assert_eq!(PersistedRandom::new(&path).node_id().unwrap(), node);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("{}\n", format_node_id(&node))
+        );
+
+        fs::write(&path, "not a node ID").unwrap();
+        assert_eq!(
+            source.node_id().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "v4")]
+    #[test]
+    fn test_persisted_random_concurrent() {
+        use crate::std::{thread, vec::Vec};
+
+        let path = crate::std::env::temp_dir().join(format!(
+            "uuid-node-concurrent-{}",
+            crate::std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let threads = (0..8)
+            .map(|_| {
+                let source = PersistedRandom::new(&path);
+                thread::spawn(move || source.node_id().unwrap())
+            })
+            .collect::<Vec<_>>();
+        let nodes = threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(nodes.iter().all(|node| *node == nodes[0]));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
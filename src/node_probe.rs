@@ -0,0 +1,282 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Detection of hosts sharing a node ID.
+//!
+//! Time-based UUIDs are only unique if every host generating them uses a
+//! different node ID. [`NodeIdProbe`] looks for evidence that this isn't
+//! the case in a sample of recently generated UUIDs.
+//!
+//! Note that you need features `v1` and `std` in order to use this module.
+//!
+//! [`NodeIdProbe`]: struct.NodeIdProbe.html
+
+use crate::prelude::*;
+use crate::std::{collections::HashMap, vec::Vec};
+
+/// A node ID, the last 6 bytes of a time-based UUID.
+pub type NodeId = [u8; 6];
+
+/// Collects time-based UUIDs and reports node IDs that appear to be used
+/// by more than one host.
+///
+/// A single host hands out clock sequences from one counter, so ordered by
+/// timestamp, the clock sequences of its UUIDs never decrease. When the
+/// UUIDs for a node ID can only be explained by several interleaved
+/// counters, several hosts are probably using that node ID.
+///
+/// The probe only sees what is in the sample: a collision is only detected
+/// if the hosts sharing a node ID were generating UUIDs at the same time.
+/// The clock sequence also wraps around after 16384 UUIDs, which looks like
+/// a second counter, so the sample should cover fewer UUIDs per host than
+/// that.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::node_probe::NodeIdProbe;
+/// use uuid::v1::{Context, Timestamp};
+/// use uuid::Uuid;
+///
+/// let node = [1, 2, 3, 4, 5, 6];
+/// let host_a = Context::new(0);
+/// let host_b = Context::new(1000);
+///
+/// let mut probe = NodeIdProbe::new();
+///
+/// for seconds in 1_500_000_000..1_500_000_010 {
+///     for context in &[&host_a, &host_b] {
+///         let ts = Timestamp::from_unix(context, seconds, 0);
+///         probe.observe(&Uuid::new_v1(ts, &node).unwrap());
+///     }
+/// }
+///
+/// let collisions = probe.collisions();
+///
+/// assert_eq!(collisions.len(), 1);
+/// assert_eq!(collisions[0].node_id(), node);
+/// assert_eq!(collisions[0].generators(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NodeIdProbe {
+    observations: HashMap<NodeId, Vec<(u64, u16)>>,
+}
+
+/// A node ID that appears to be used by more than one host.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Collision {
+    node_id: NodeId,
+    generators: usize,
+    observations: usize,
+}
+
+impl NodeIdProbe {
+    /// Creates an empty probe.
+    pub fn new() -> Self {
+        NodeIdProbe::default()
+    }
+
+    /// Adds a UUID to the sample.
+    ///
+    /// Returns `false`, ignoring the UUID, if it isn't a version 1 or
+    /// version 6 UUID.
+    pub fn observe(&mut self, uuid: &Uuid) -> bool {
+        let b = uuid.as_bytes();
+
+        let ticks = match uuid.get_version_num() {
+            1 => match uuid.to_timestamp() {
+                Some(ts) => ts.to_rfc4122().0,
+                None => return false,
+            },
+            6 => {
+                b[..6]
+                    .iter()
+                    .fold(0u64, |ticks, &byte| ticks << 8 | u64::from(byte))
+                    << 12
+                    | u64::from(b[6] & 0x0F) << 8
+                    | u64::from(b[7])
+            }
+            _ => return false,
+        };
+
+        let clock_seq = u16::from(b[8] & 0x3F) << 8 | u16::from(b[9]);
+
+        let mut node_id = [0; 6];
+        node_id.copy_from_slice(&b[10..]);
+
+        self.observations
+            .entry(node_id)
+            .or_default()
+            .push((ticks, clock_seq));
+
+        true
+    }
+
+    /// Returns the node IDs that appear to be used by more than one host.
+    ///
+    /// Collisions are ordered by node ID.
+    pub fn collisions(&self) -> Vec<Collision> {
+        let mut collisions = self
+            .observations
+            .iter()
+            .filter_map(|(node_id, observations)| {
+                let mut observations = observations.clone();
+                observations.sort_unstable();
+                observations.dedup();
+
+                let generators = count_generators(&observations);
+
+                if generators > 1 {
+                    Some(Collision {
+                        node_id: *node_id,
+                        generators,
+                        observations: observations.len(),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        collisions.sort_unstable_by_key(|collision| collision.node_id);
+        collisions
+    }
+}
+
+impl Extend<Uuid> for NodeIdProbe {
+    fn extend<I: IntoIterator<Item = Uuid>>(&mut self, iter: I) {
+        for uuid in iter {
+            self.observe(&uuid);
+        }
+    }
+}
+
+impl Collision {
+    /// The node ID shared by several hosts.
+    pub const fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    /// The smallest number of clock sequence counters that explains the
+    /// UUIDs seen for the node ID.
+    ///
+    /// This is a lower bound on the number of hosts using the node ID.
+    pub const fn generators(&self) -> usize {
+        self.generators
+    }
+
+    /// The number of distinct UUIDs seen for the node ID.
+    pub const fn observations(&self) -> usize {
+        self.observations
+    }
+}
+
+/// Counts the fewest chains of non-decreasing clock sequences that cover
+/// observations sorted by timestamp.
+fn count_generators(observations: &[(u64, u16)]) -> usize {
+    // the last clock sequence of each chain, in ascending order
+    let mut tails: Vec<u16> = Vec::new();
+
+    for &(_, clock_seq) in observations {
+        // extend the chain with the largest tail that fits, so chains with
+        // smaller tails stay available for later observations
+        match tails.iter().rposition(|&tail| tail <= clock_seq) {
+            Some(i) => tails[i] = clock_seq,
+            None => tails.insert(0, clock_seq),
+        }
+
+        debug_assert!(tails.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    tails.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v1::{Context, Timestamp};
+
+    const NODE: NodeId = [1, 2, 3, 4, 5, 6];
+
+    fn new_v1(context: &Context, seconds: u64, node: &NodeId) -> Uuid {
+        Uuid::new_v1(Timestamp::from_unix(context, seconds, 0), node).unwrap()
+    }
+
+    #[test]
+    fn test_single_host() {
+        let context = Context::new(0);
+        let mut probe = NodeIdProbe::new();
+
+        for seconds in 0..100 {
+            let uuid = new_v1(&context, seconds, &NODE);
+
+            assert!(probe.observe(&uuid));
+            // seeing the same UUID again isn't a collision
+            assert!(probe.observe(&uuid));
+        }
+
+        assert!(probe.collisions().is_empty());
+    }
+
+    #[test]
+    fn test_distinct_nodes() {
+        let a = Context::new(0);
+        let b = Context::new(500);
+        let mut probe = NodeIdProbe::new();
+
+        for seconds in 0..100 {
+            probe.observe(&new_v1(&a, seconds, &NODE));
+            probe.observe(&new_v1(&b, seconds, &[6, 5, 4, 3, 2, 1]));
+        }
+
+        assert!(probe.collisions().is_empty());
+    }
+
+    #[test]
+    fn test_shared_node() {
+        let contexts = [Context::new(0), Context::new(300), Context::new(600)];
+        let mut probe = NodeIdProbe::new();
+
+        for seconds in 0..100 {
+            for context in &contexts {
+                probe.observe(&new_v1(context, seconds, &NODE));
+            }
+        }
+
+        assert_eq!(
+            probe.collisions(),
+            [Collision {
+                node_id: NODE,
+                generators: 3,
+                observations: 300,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_ignores_other_versions() {
+        let mut probe = NodeIdProbe::new();
+
+        assert!(!probe.observe(&crate::test_util::new()));
+        assert!(!probe.observe(&Uuid::nil()));
+
+        probe.extend(vec![Uuid::max(), crate::test_util::new2()]);
+        assert!(probe.collisions().is_empty());
+    }
+
+    #[test]
+    fn test_count_generators() {
+        assert_eq!(count_generators(&[]), 0);
+        assert_eq!(count_generators(&[(0, 5), (1, 5), (2, 7)]), 1);
+        assert_eq!(count_generators(&[(0, 5), (1, 2), (2, 6), (3, 3)]), 2);
+        assert_eq!(count_generators(&[(0, 3), (1, 2), (2, 1)]), 3);
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A [`Uuid`] that is known not to be nil.
+//!
+//! [`Uuid`]: ../struct.Uuid.html
+
+use crate::prelude::*;
+use crate::std::{fmt, num::NonZeroU128};
+
+/// A [`Uuid`] that is known not to be [`Uuid::nil`].
+///
+/// Like [`NonZeroU64`], the compiler uses the nil value to represent
+/// `None`, so `Option<NonNilUuid>` is the same size as a `Uuid`. It's
+/// ordered, formatted and compared in the same way as the `Uuid` it holds.
+///
+/// # Examples
+///
+/// ```
+/// use std::mem;
+/// use uuid::{non_nil::NonNilUuid, Uuid};
+///
+/// assert_eq!(mem::size_of::<Option<NonNilUuid>>(), 16);
+///
+/// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+/// let non_nil = NonNilUuid::new(uuid).unwrap();
+///
+/// assert_eq!(non_nil.get(), uuid);
+/// assert_eq!(NonNilUuid::new(Uuid::nil()), None);
+/// ```
+///
+/// [`Uuid`]: ../struct.Uuid.html
+/// [`Uuid::nil`]: ../struct.Uuid.html#method.nil
+/// [`NonZeroU64`]: https://doc.rust-lang.org/std/num/struct.NonZeroU64.html
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct NonNilUuid(NonZeroU128);
+
+impl NonNilUuid {
+    /// Creates a `NonNilUuid` if `uuid` isn't nil.
+    pub fn new(uuid: Uuid) -> Option<Self> {
+        NonZeroU128::new(uuid.as_u128()).map(NonNilUuid)
+    }
+
+    /// Creates a `NonNilUuid` without checking that `uuid` isn't nil.
+    ///
+    /// # Safety
+    ///
+    /// `uuid` must not be nil.
+    #[cfg(not(feature = "no-unsafe"))]
+    pub unsafe fn new_unchecked(uuid: Uuid) -> Self {
+        NonNilUuid(NonZeroU128::new_unchecked(uuid.as_u128()))
+    }
+
+    /// Returns the [`Uuid`].
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    pub fn get(self) -> Uuid {
+        Uuid::from_u128(self.0.get())
+    }
+}
+
+impl From<NonNilUuid> for Uuid {
+    fn from(uuid: NonNilUuid) -> Self {
+        uuid.get()
+    }
+}
+
+impl PartialEq<Uuid> for NonNilUuid {
+    fn eq(&self, other: &Uuid) -> bool {
+        self.get() == *other
+    }
+}
+
+impl PartialEq<NonNilUuid> for Uuid {
+    fn eq(&self, other: &NonNilUuid) -> bool {
+        *self == other.get()
+    }
+}
+
+impl fmt::Debug for NonNilUuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+impl fmt::Display for NonNilUuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.get(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std::mem;
+    use crate::test_util;
+
+    #[test]
+    fn test_non_nil() {
+        let uuid = test_util::new();
+        let non_nil = NonNilUuid::new(uuid).unwrap();
+
+        assert_eq!(non_nil.get(), uuid);
+        assert_eq!(Uuid::from(non_nil), uuid);
+        assert_eq!(non_nil, uuid);
+        assert_eq!(uuid, non_nil);
+
+        assert_eq!(NonNilUuid::new(Uuid::nil()), None);
+        assert!(NonNilUuid::new(Uuid::from_u128(1)).is_some());
+    }
+
+    #[test]
+    fn test_niche() {
+        assert_eq!(mem::size_of::<Option<NonNilUuid>>(), 16);
+        assert_eq!(mem::size_of::<NonNilUuid>(), mem::size_of::<Uuid>());
+    }
+
+    #[test]
+    fn test_order() {
+        let a = NonNilUuid::new(test_util::new()).unwrap();
+        let b = NonNilUuid::new(test_util::new2()).unwrap();
+        let max = NonNilUuid::new(Uuid::max()).unwrap();
+
+        assert!(a < b && b < max);
+        assert_eq!(a.cmp(&b), a.get().cmp(&b.get()));
+    }
+
+    #[cfg(not(feature = "no-unsafe"))]
+    #[test]
+    fn test_new_unchecked() {
+        let uuid = test_util::new();
+
+        assert_eq!(unsafe { NonNilUuid::new_unchecked(uuid) }.get(), uuid);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fmt() {
+        use crate::std::string::ToString;
+
+        let uuid = test_util::new();
+        let non_nil = NonNilUuid::new(uuid).unwrap();
+
+        assert_eq!(non_nil.to_string(), uuid.to_string());
+        assert_eq!(format!("{:?}", non_nil), format!("{:?}", uuid));
+    }
+}
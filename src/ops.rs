@@ -0,0 +1,267 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Arithmetic and bitwise operators on `Uuid`s, treating them as 128-bit
+//! numbers.
+//!
+//! The results usually don't have a meaningful version or variant, they
+//! are meant for algorithms like consistent hashing and XOR distance, and
+//! for deriving keys in tests and range tooling.
+
+use crate::prelude::*;
+use crate::std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not,
+};
+
+impl Uuid {
+    /// Returns the UUID that follows this one as a big-endian 128-bit
+    /// number, or `None` for the max UUID.
+    ///
+    /// This turns an inclusive upper bound into an exclusive one, and an
+    /// exclusive lower bound into an inclusive one, such as when the last
+    /// key of a page becomes the start of the next range query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let last = Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822a8").unwrap();
+    ///
+    /// assert_eq!(
+    ///     last.successor().unwrap().to_string(),
+    ///     "936da01f-9abd-4d9d-80c7-02af85c822a9"
+    /// );
+    /// assert_eq!(Uuid::max().successor(), None);
+    /// ```
+    pub fn successor(&self) -> Option<Uuid> {
+        self.checked_add(1)
+    }
+
+    /// Returns the UUID that precedes this one as a big-endian 128-bit
+    /// number, or `None` for the nil UUID.
+    ///
+    /// This is the inverse of [`Uuid::successor`], turning an exclusive
+    /// upper bound into an inclusive one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// assert_eq!(Uuid::max().predecessor().unwrap().as_u128(), u128::MAX - 1);
+    /// assert_eq!(Uuid::nil().predecessor(), None);
+    /// ```
+    ///
+    /// [`Uuid::successor`]: #method.successor
+    pub fn predecessor(&self) -> Option<Uuid> {
+        self.checked_sub(1)
+    }
+
+    /// Adds `rhs` to the UUID as a big-endian 128-bit number, returning
+    /// `None` on overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822ff").unwrap();
+    ///
+    /// assert_eq!(
+    ///     uuid.checked_add(1).unwrap().to_string(),
+    ///     "936da01f-9abd-4d9d-80c7-02af85c82300"
+    /// );
+    /// assert_eq!(Uuid::max().checked_add(1), None);
+    /// ```
+    pub fn checked_add(&self, rhs: u128) -> Option<Uuid> {
+        self.as_u128().checked_add(rhs).map(Uuid::from_u128)
+    }
+
+    /// Adds `rhs` to the UUID as a big-endian 128-bit number, wrapping
+    /// around at the max UUID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// assert_eq!(Uuid::max().wrapping_add(1), Uuid::nil());
+    /// ```
+    pub fn wrapping_add(&self, rhs: u128) -> Uuid {
+        Uuid::from_u128(self.as_u128().wrapping_add(rhs))
+    }
+
+    /// Subtracts `rhs` from the UUID as a big-endian 128-bit number,
+    /// returning `None` on overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// assert_eq!(Uuid::max().checked_sub(u128::MAX), Some(Uuid::nil()));
+    /// assert_eq!(Uuid::nil().checked_sub(1), None);
+    /// ```
+    pub fn checked_sub(&self, rhs: u128) -> Option<Uuid> {
+        self.as_u128().checked_sub(rhs).map(Uuid::from_u128)
+    }
+
+    /// Subtracts `rhs` from the UUID as a big-endian 128-bit number,
+    /// wrapping around at the nil UUID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// assert_eq!(Uuid::nil().wrapping_sub(1), Uuid::max());
+    /// ```
+    pub fn wrapping_sub(&self, rhs: u128) -> Uuid {
+        Uuid::from_u128(self.as_u128().wrapping_sub(rhs))
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident;)+) => {$(
+        impl $Op for Uuid {
+            type Output = Uuid;
+
+            #[inline]
+            fn $op(self, rhs: Uuid) -> Uuid {
+                Uuid::from_u128(self.as_u128().$op(rhs.as_u128()))
+            }
+        }
+
+        impl $OpAssign for Uuid {
+            #[inline]
+            fn $op_assign(&mut self, rhs: Uuid) {
+                *self = (*self).$op(rhs);
+            }
+        }
+    )+};
+}
+
+impl_bit_op! {
+    BitAnd, bitand, BitAndAssign, bitand_assign;
+    BitOr, bitor, BitOrAssign, bitor_assign;
+    BitXor, bitxor, BitXorAssign, bitxor_assign;
+}
+
+/// Flips every bit, so the nil UUID becomes the max UUID and vice versa.
+impl Not for Uuid {
+    type Output = Uuid;
+
+    #[inline]
+    fn not(self) -> Uuid {
+        Uuid::from_u128(!self.as_u128())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_add() {
+        let uuid = test_util::new();
+
+        assert_eq!(uuid.checked_add(0), Some(uuid));
+        assert_eq!(
+            uuid.checked_add(1).map(|uuid| uuid.as_u128()),
+            Some(uuid.as_u128() + 1)
+        );
+        assert!(uuid.checked_add(1).unwrap() > uuid);
+        assert_eq!(Uuid::max().checked_add(1), None);
+        assert_eq!(Uuid::nil().checked_add(u128::MAX), Some(Uuid::max()));
+
+        assert_eq!(Uuid::max().wrapping_add(2), Uuid::from_u128(1));
+        assert_eq!(uuid.wrapping_add(5).wrapping_sub(5), uuid);
+    }
+
+    #[test]
+    fn test_sub() {
+        let uuid = test_util::new();
+
+        assert_eq!(uuid.checked_sub(0), Some(uuid));
+        assert_eq!(uuid.checked_add(7).unwrap().checked_sub(7), Some(uuid));
+        assert_eq!(Uuid::nil().checked_sub(1), None);
+        assert_eq!(Uuid::nil().wrapping_sub(2), Uuid::from_u128(u128::MAX - 1));
+    }
+
+    #[test]
+    fn test_successor_predecessor() {
+        let uuid = test_util::new();
+
+        assert!(uuid.successor().unwrap() > uuid);
+        assert!(uuid.predecessor().unwrap() < uuid);
+        assert_eq!(uuid.successor().unwrap().predecessor(), Some(uuid));
+        assert_eq!(Uuid::nil().successor(), Some(Uuid::from_u128(1)));
+        assert_eq!(Uuid::max().successor(), None);
+        assert_eq!(Uuid::nil().predecessor(), None);
+
+        // carries across bytes
+        let uuid = Uuid::from_u128(0x00ff_ffff);
+        assert_eq!(uuid.successor(), Some(Uuid::from_u128(0x0100_0000)));
+        assert_eq!(uuid.successor().unwrap().predecessor(), Some(uuid));
+    }
+
+    #[test]
+    fn test_consecutive() {
+        let start = Uuid::from_u128(0xff);
+        let mut uuids = (0..3).map(|i| start.checked_add(i).unwrap());
+
+        assert_eq!(uuids.next(), Some(start));
+        assert_eq!(uuids.next().unwrap().as_bytes()[14..], [0x01, 0x00]);
+        assert_eq!(uuids.next().unwrap().as_bytes()[14..], [0x01, 0x01]);
+    }
+
+    #[test]
+    fn test_bit_ops() {
+        let a = test_util::new();
+        let b = test_util::new2();
+
+        assert_eq!(a ^ a, Uuid::nil());
+        assert_eq!(a ^ b, Uuid::from_u128(a.as_u128() ^ b.as_u128()));
+        assert_eq!(a ^ b, b ^ a);
+        assert_eq!(a & Uuid::max(), a);
+        assert_eq!(a & Uuid::nil(), Uuid::nil());
+        assert_eq!(a | Uuid::nil(), a);
+        assert_eq!(a | b, Uuid::from_u128(a.as_u128() | b.as_u128()));
+        assert_eq!(!Uuid::nil(), Uuid::max());
+        assert_eq!(!!a, a);
+    }
+
+    #[test]
+    fn test_bit_op_assign() {
+        let a = test_util::new();
+        let mut uuid = a;
+
+        uuid ^= test_util::new2();
+        assert_eq!(uuid, a ^ test_util::new2());
+
+        uuid &= Uuid::nil();
+        assert!(uuid.is_nil());
+
+        uuid |= a;
+        assert_eq!(uuid, a);
+    }
+
+    #[test]
+    fn test_xor_distance() {
+        let target = test_util::new();
+        let near = Uuid::from_u128(target.as_u128() ^ 1);
+        let far = Uuid::from_u128(target.as_u128() ^ (1 << 127));
+
+        assert!((target ^ near) < (target ^ far));
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The 122 bits of a UUID around its version and variant, as one number.
+//!
+//! The version sits in the middle of a UUID and the variant after it, so
+//! the remaining bits are packed together into the lower 122 bits of a
+//! `u128`, most significant first. Incrementing the packed value steps to
+//! the next UUID with the same version and variant.
+
+/// Packs the bits of `value` that aren't its version and variant.
+pub(crate) const fn pack(value: u128) -> u128 {
+    (value >> 80) << 74 | (value >> 64 & 0xfff) << 62 | value & ((1 << 62) - 1)
+}
+
+/// The inverse of [`pack`], leaving the version and variant bits unset.
+///
+/// Bits above the lower 122 of `packed` are ignored.
+pub(crate) const fn unpack(packed: u128) -> u128 {
+    (packed >> 74 & 0xffff_ffff_ffff) << 80
+        | (packed >> 62 & 0xfff) << 64
+        | packed & ((1 << 62) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_unpack() {
+        // a version 7 UUID without its version and variant
+        let value = 0x0174_876e_8000_06eb_10ee_0e6d_43c9_b90d;
+
+        assert_eq!(unpack(pack(value)), value);
+        assert_eq!(pack(u128::MAX), (1 << 122) - 1);
+        assert_eq!(unpack(1 << 122), 0);
+
+        // carries skip over the version and variant
+        assert_eq!(unpack(pack(value) + 1), value + 1);
+        assert_eq!(unpack(1 << 62), 1 << 64);
+        assert_eq!(unpack(1 << 74), 1 << 80);
+    }
+}
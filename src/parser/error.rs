@@ -23,6 +23,16 @@ pub(crate) enum Error {
         /// [`Uuid`]: ../Uuid.html
         urn: UrnPrefix,
     },
+    /// A character other than a hyphen between two groups of a hyphenated
+    /// [`Uuid`] string.
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    InvalidSeparator {
+        /// The character found instead of a hyphen.
+        found: char,
+        /// The position of the character.
+        index: usize,
+    },
     /// Invalid number of segments in the [`Uuid`] string.
     ///
     /// [`Uuid`]: ../struct.Uuid.html
@@ -34,6 +44,9 @@ pub(crate) enum Error {
         expected: ExpectedLength,
         /// The number of segments found.
         found: usize,
+        /// The position of the first character past the expected number of
+        /// segments.
+        index: usize,
     },
     /// Invalid length of a segment in a [`Uuid`] string.
     ///
@@ -45,6 +58,9 @@ pub(crate) enum Error {
         found: usize,
         /// The segment with invalid length.
         group: usize,
+        /// The position of the hyphen or the end of input terminating the
+        /// segment.
+        index: usize,
     },
     /// Invalid length of the [`Uuid`] string.
     ///
@@ -77,9 +93,24 @@ pub(crate) enum UrnPrefix {
 }
 
 impl Error {
+    /// Moves the position of the error `by` bytes further into the input,
+    /// for errors in a part of a larger string.
+    pub(crate) fn offset(mut self, by: usize) -> Self {
+        match self {
+            Error::InvalidCharacter { ref mut index, .. }
+            | Error::InvalidSeparator { ref mut index, .. }
+            | Error::InvalidGroupCount { ref mut index, .. }
+            | Error::InvalidGroupLength { ref mut index, .. } => *index += by,
+            Error::InvalidLength { .. } => (),
+        }
+
+        self
+    }
+
     fn _description(&self) -> &str {
         match *self {
             Error::InvalidCharacter { .. } => "invalid character",
+            Error::InvalidSeparator { .. } => "invalid group separator",
             Error::InvalidGroupCount { .. } => "invalid number of groups",
             Error::InvalidGroupLength { .. } => "invalid group length",
             Error::InvalidLength { .. } => "invalid length",
@@ -119,14 +150,19 @@ impl fmt::Display for Error {
                     urn_str, expected, found, index
                 )
             }
+            Error::InvalidSeparator { found, index } => {
+                write!(f, "expected -, found {} at {}", found, index)
+            }
             Error::InvalidGroupCount {
                 ref expected,
                 found,
+                ..
             } => write!(f, "expected {}, found {}", expected, found),
             Error::InvalidGroupLength {
                 ref expected,
                 found,
                 group,
+                ..
             } => write!(
                 f,
                 "expected {}, found {} in group {}",
@@ -140,10 +176,4 @@ impl fmt::Display for Error {
     }
 }
 
-#[cfg(feature = "std")]
-mod std_support {
-    use super::*;
-    use crate::std::error;
-
-    impl error::Error for Error {}
-}
+impl crate::std::error::Error for Error {}
@@ -16,18 +16,10 @@
 pub(crate) mod error;
 pub(crate) use self::error::Error;
 
-use crate::{adapter, Uuid};
+#[cfg(all(feature = "simd", not(feature = "no-unsafe")))]
+mod simd;
 
-/// Check if the length matches any of the given criteria lengths.
-fn len_matches_any(len: usize, crits: &[usize]) -> bool {
-    for crit in crits {
-        if len == *crit {
-            return true;
-        }
-    }
-
-    false
-}
+use crate::{adapter, Uuid, Version};
 
 /// Check if the length matches any criteria lengths in the given range
 /// (inclusive).
@@ -52,151 +44,611 @@ impl Uuid {
     /// Parses a `Uuid` from a string of hexadecimal digits with optional
     /// hyphens.
     ///
-    /// Any of the formats generated by this module (simple, hyphenated, urn)
-    /// are supported by this parsing function.
-    pub fn parse_str(mut input: &str) -> Result<Uuid, crate::Error> {
+    /// Any of the formats generated by this module (simple, hyphenated, urn,
+    /// braced) are supported by this parsing function.
+    pub fn parse_str(input: &str) -> Result<Uuid, crate::Error> {
+        #[cfg(all(feature = "simd", not(feature = "no-unsafe")))]
+        {
+            if let Some(uuid) = simd::parse(input.as_bytes()) {
+                return Ok(uuid);
+            }
+        }
+
+        Uuid::try_parse(input)
+    }
+
+    /// Parses a `Uuid` from a string of hexadecimal digits with optional
+    /// hyphens, in a `const` context.
+    ///
+    /// This accepts the same formats and returns the same errors as
+    /// [`Uuid::parse_str`], so it can be used to define UUID constants
+    /// from their string form. The [`uuid!`] macro does that with a
+    /// compile time error for invalid input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// const ID: Uuid =
+    ///     match Uuid::try_parse("936da01f-9abd-4d9d-80c7-02af85c822a8") {
+    ///         Ok(uuid) => uuid,
+    ///         Err(_) => panic!("invalid UUID"),
+    ///     };
+    ///
+    /// assert_eq!(ID.to_string(), "936da01f-9abd-4d9d-80c7-02af85c822a8");
+    /// ```
+    ///
+    /// [`Uuid::parse_str`]: #method.parse_str
+    /// [`uuid!`]: macro.uuid.html
+    pub const fn try_parse(input: &str) -> Result<Uuid, crate::Error> {
+        Uuid::try_parse_ascii(input.as_bytes())
+    }
+
+    /// Parses a `Uuid` from ASCII bytes, such as a slice of a network
+    /// buffer, without checking that they're valid UTF-8 first.
+    ///
+    /// This accepts the same formats and returns the same errors as
+    /// [`Uuid::try_parse`]. Non-ASCII bytes are reported as invalid
+    /// characters, decoded as UTF-8 where possible and as
+    /// `char::REPLACEMENT_CHARACTER` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let request = b"GET /items/936da01f-9abd-4d9d-80c7-02af85c822a8 HTTP/1.1";
+    ///
+    /// assert_eq!(
+    ///     Uuid::try_parse_ascii(&request[11..47]),
+    ///     Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822a8"),
+    /// );
+    /// assert!(Uuid::try_parse_ascii(&[0xff; 32]).is_err());
+    /// ```
+    ///
+    /// [`Uuid::try_parse`]: #method.try_parse
+    pub const fn try_parse_ascii(input: &[u8]) -> Result<Uuid, crate::Error> {
+        const EXPECTED_LENGTHS: error::ExpectedLength =
+            error::ExpectedLength::Any(&[
+                adapter::Hyphenated::LENGTH,
+                adapter::Simple::LENGTH,
+            ]);
+
         // Ensure length is valid for any of the supported formats
         let len = input.len();
 
-        if len == adapter::Urn::LENGTH && input.starts_with("urn:uuid:") {
-            input = &input[9..];
-        } else if !len_matches_any(
-            len,
-            &[adapter::Hyphenated::LENGTH, adapter::Simple::LENGTH],
-        ) {
-            Err(Error::InvalidLength {
-                expected: error::ExpectedLength::Any(&[
-                    adapter::Hyphenated::LENGTH,
-                    adapter::Simple::LENGTH,
-                ]),
+        // Positions in errors are relative to the whole input, so
+        // iteration starts after the prefix instead of slicing it off.
+        let mut i_char = 0;
+        let mut end = len;
+
+        if len == adapter::Urn::LENGTH && has_urn_prefix(input) {
+            i_char = 9;
+        } else if len == adapter::Braced::LENGTH
+            && input[0] == b'{'
+            && input[len - 1] == b'}'
+        {
+            i_char = 1;
+            end = len - 1;
+        } else if len != adapter::Hyphenated::LENGTH
+            && len != adapter::Simple::LENGTH
+        {
+            return Err(crate::Error::parser(Error::InvalidLength {
+                expected: EXPECTED_LENGTHS,
                 found: len,
-            })?;
+            }));
         }
 
+        // A hyphen is expected after each of the first four groups.
+        let hyphenated = end - i_char == adapter::Hyphenated::LENGTH;
+
         // `digit` counts only hexadecimal digits, `i_char` counts all chars.
         let mut digit = 0;
         let mut group = 0;
-        let mut acc = 0;
         let mut buffer = [0u8; 16];
 
-        for (i_char, chr) in input.bytes().enumerate() {
-            if digit as usize >= adapter::Simple::LENGTH && group != 4 {
+        while i_char < end {
+            let chr = input[i_char];
+
+            if digit >= adapter::Simple::LENGTH && group != 4 {
                 if group == 0 {
-                    Err(Error::InvalidLength {
-                        expected: error::ExpectedLength::Any(&[
-                            adapter::Hyphenated::LENGTH,
-                            adapter::Simple::LENGTH,
-                        ]),
+                    return Err(crate::Error::parser(Error::InvalidLength {
+                        expected: EXPECTED_LENGTHS,
                         found: len,
-                    })?;
+                    }));
                 }
 
-                Err(Error::InvalidGroupCount {
+                return Err(crate::Error::parser(Error::InvalidGroupCount {
                     expected: error::ExpectedLength::Any(&[1, 5]),
                     found: group + 1,
-                })?;
+                    index: i_char,
+                }));
             }
 
-            if digit % 2 == 0 {
-                // First digit of the byte.
-                match chr {
-                    // Calulate upper half.
-                    b'0'..=b'9' => acc = chr - b'0',
-                    b'a'..=b'f' => acc = chr - b'a' + 10,
-                    b'A'..=b'F' => acc = chr - b'A' + 10,
-                    // Found a group delimiter
-                    b'-' => {
-                        // TODO: remove the u8 cast
-                        // BODY: this only needed until we switch to
-                        //       ParseError
-                        if ACC_GROUP_LENS[group] as u8 != digit {
-                            // Calculate how many digits this group consists of
-                            // in the input.
-                            let found = if group > 0 {
-                                // TODO: remove the u8 cast
-                                // BODY: this only needed until we switch to
-                                //       ParseError
-                                digit - ACC_GROUP_LENS[group - 1] as u8
-                            } else {
-                                digit
-                            };
-
-                            Err(Error::InvalidGroupLength {
-                                expected: error::ExpectedLength::Exact(
-                                    GROUP_LENS[group],
-                                ),
-                                found: found as usize,
-                                group,
-                            })?;
-                        }
-                        // Next group, decrement digit, it is incremented again
-                        // at the bottom.
-                        group += 1;
-                        digit -= 1;
-                    }
-                    _ => {
-                        Err(Error::InvalidCharacter {
-                            expected: "0123456789abcdefABCDEF-",
-                            found: input[i_char..].chars().next().unwrap(),
-                            index: i_char,
-                            urn: error::UrnPrefix::Optional,
-                        })?;
-                    }
-                }
-            } else {
-                // Second digit of the byte, shift the upper half.
-                acc *= 16;
-                match chr {
-                    b'0'..=b'9' => acc += chr - b'0',
-                    b'a'..=b'f' => acc += chr - b'a' + 10,
-                    b'A'..=b'F' => acc += chr - b'A' + 10,
-                    b'-' => {
-                        // The byte isn't complete yet.
-                        let found = if group > 0 {
-                            // TODO: remove the u8 cast
-                            // BODY: this only needed until we switch to
-                            //       ParseError
-                            digit - ACC_GROUP_LENS[group - 1] as u8
-                        } else {
-                            digit
-                        };
-
-                        Err(Error::InvalidGroupLength {
+            if chr == b'-' {
+                // Found a group delimiter, which must follow a complete
+                // group.
+                if ACC_GROUP_LENS[group] != digit {
+                    // Calculate how many digits this group consists of in
+                    // the input.
+                    let found = if group > 0 {
+                        digit - ACC_GROUP_LENS[group - 1]
+                    } else {
+                        digit
+                    };
+
+                    return Err(crate::Error::parser(
+                        Error::InvalidGroupLength {
                             expected: error::ExpectedLength::Exact(
                                 GROUP_LENS[group],
                             ),
-                            found: found as usize,
+                            found,
                             group,
-                        })?;
+                            index: i_char,
+                        },
+                    ));
+                }
+
+                group += 1;
+            } else {
+                let value = match chr {
+                    b'0'..=b'9' => chr - b'0',
+                    b'a'..=b'f' => chr - b'a' + 10,
+                    b'A'..=b'F' => chr - b'A' + 10,
+                    _ if hyphenated
+                        && group < 4
+                        && digit == ACC_GROUP_LENS[group] =>
+                    {
+                        return Err(crate::Error::parser(
+                            Error::InvalidSeparator {
+                                found: char_at(input, i_char),
+                                index: i_char,
+                            },
+                        ))
                     }
                     _ => {
-                        Err(Error::InvalidCharacter {
-                            expected: "0123456789abcdefABCDEF-",
-                            found: input[i_char..].chars().next().unwrap(),
-                            index: i_char,
-                            urn: error::UrnPrefix::Optional,
-                        })?;
+                        return Err(crate::Error::parser(
+                            Error::InvalidCharacter {
+                                expected: "0123456789abcdefABCDEF-",
+                                found: char_at(input, i_char),
+                                index: i_char,
+                                urn: error::UrnPrefix::Optional,
+                            },
+                        ))
                     }
+                };
+
+                if digit % 2 == 0 {
+                    // First digit of the byte, the upper half.
+                    buffer[digit / 2] = value << 4;
+                } else {
+                    buffer[digit / 2] |= value;
                 }
-                buffer[(digit / 2) as usize] = acc;
+
+                digit += 1;
             }
-            digit += 1;
+
+            i_char += 1;
         }
 
         // Now check the last group.
-        // TODO: remove the u8 cast
-        // BODY: this only needed until we switch to
-        //       ParseError
-        if ACC_GROUP_LENS[4] as u8 != digit {
-            Err(Error::InvalidGroupLength {
+        if ACC_GROUP_LENS[4] != digit {
+            return Err(crate::Error::parser(Error::InvalidGroupLength {
                 expected: error::ExpectedLength::Exact(GROUP_LENS[4]),
-                found: (digit as usize - ACC_GROUP_LENS[3]),
+                found: digit - ACC_GROUP_LENS[3],
                 group,
-            })?;
+                index: end,
+            }));
         }
 
         Ok(Uuid::from_bytes(buffer))
     }
+
+    /// Parses a `Uuid` from a UTF-16 string, as returned by Windows APIs.
+    ///
+    /// This accepts the same formats as [`Uuid::parse_str`], including the
+    /// braced strings produced by `StringFromGUID2`. A single trailing NUL
+    /// is ignored. Positions in errors count UTF-16 code units from the
+    /// start of `input`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let wide = "{936DA01F-9ABD-4D9D-80C7-02AF85C822A8}\0"
+    ///     .encode_utf16()
+    ///     .collect::<Vec<u16>>();
+    ///
+    /// assert_eq!(
+    ///     Uuid::parse_utf16(&wide),
+    ///     Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822a8"),
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::parse_str`]: #method.parse_str
+    pub fn parse_utf16(mut input: &[u16]) -> Result<Uuid, crate::Error> {
+        if let Some((&0, rest)) = input.split_last() {
+            input = rest;
+        }
+
+        if input.len() > adapter::Urn::LENGTH {
+            return Err(crate::Error::parser(Error::InvalidLength {
+                expected: error::ExpectedLength::Any(&[
+                    adapter::Hyphenated::LENGTH,
+                    adapter::Simple::LENGTH,
+                ]),
+                found: input.len(),
+            }));
+        }
+
+        // Units outside ASCII are replaced with a byte that's never valid,
+        // and decoded again if they're reported in an error.
+        let mut buffer = [0; adapter::Urn::LENGTH];
+        for (byte, &unit) in buffer.iter_mut().zip(input) {
+            *byte = if unit < 0x80 { unit as u8 } else { 0xff };
+        }
+
+        let found_at = |index: usize| {
+            crate::std::char::decode_utf16(input[index..].iter().cloned())
+                .next()
+                .and_then(Result::ok)
+                .unwrap_or(crate::std::char::REPLACEMENT_CHARACTER)
+        };
+
+        Uuid::try_parse_ascii(&buffer[..input.len()]).map_err(|err| {
+            err.map_parser(|err| match err {
+                Error::InvalidCharacter {
+                    expected,
+                    index,
+                    urn,
+                    ..
+                } if input[index] >= 0x80 => Error::InvalidCharacter {
+                    expected,
+                    found: found_at(index),
+                    index,
+                    urn,
+                },
+                Error::InvalidSeparator { index, .. }
+                    if input[index] >= 0x80 =>
+                {
+                    Error::InvalidSeparator {
+                        found: found_at(index),
+                        index,
+                    }
+                }
+                err => err,
+            })
+        })
+    }
+
+    /// Parses every `Uuid` in a list separated by `delim`, such as a comma
+    /// or a newline.
+    ///
+    /// Whitespace around each item is ignored, and so are empty items, so
+    /// a trailing newline or `\r\n` line endings don't cause errors. The
+    /// [`Error::parse_error_index`] of an error is relative to the whole
+    /// `input`, pointing at the offending character in the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let input = "936da01f-9abd-4d9d-80c7-02af85c822a8, 00000000000000000000000000000000";
+    /// let uuids = Uuid::parse_all(input, ',').collect::<Result<Vec<_>, _>>();
+    ///
+    /// assert_eq!(
+    ///     uuids,
+    ///     Ok(vec![
+    ///         Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822a8").unwrap(),
+    ///         Uuid::nil(),
+    ///     ])
+    /// );
+    ///
+    /// let input = "936da01f-9abd-4d9d-80c7-02af85c822a8\n936da01f-9abd-4d9d-80c7-02af85c822ag\n";
+    /// let errors = Uuid::parse_all(input, '\n')
+    ///     .filter_map(Result::err)
+    ///     .map(|err| err.parse_error_index())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(errors, [Some(72)]);
+    /// ```
+    ///
+    /// [`Error::parse_error_index`]: struct.Error.html#method.parse_error_index
+    pub fn parse_all(
+        input: &str,
+        delim: char,
+    ) -> impl Iterator<Item = Result<Uuid, crate::Error>> + '_ {
+        input.split(delim).filter_map(move |item| {
+            let item = item.trim();
+
+            if item.is_empty() {
+                return None;
+            }
+
+            let offset = item.as_ptr() as usize - input.as_ptr() as usize;
+
+            Some(Uuid::parse_str(item).map_err(|err| err.offset(offset)))
+        })
+    }
+
+    /// Parses every `Uuid` in a list separated by `delim`, stopping at the
+    /// first error.
+    ///
+    /// This collects the results of [`Uuid::parse_all`].
+    ///
+    /// Note that usage of this method requires the `std` feature of this
+    /// crate to be enabled.
+    ///
+    /// [`Uuid::parse_all`]: #method.parse_all
+    #[cfg(feature = "std")]
+    pub fn parse_all_to_vec(
+        input: &str,
+        delim: char,
+    ) -> Result<crate::std::vec::Vec<Uuid>, crate::Error> {
+        Uuid::parse_all(input, delim).collect()
+    }
+
+    /// Checks if a string is a valid UUID, without building the `Uuid` or
+    /// an error.
+    ///
+    /// This returns `true` exactly when [`Uuid::parse_str`] would succeed,
+    /// and is cheaper for input that's often invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// assert!(Uuid::is_valid("936da01f-9abd-4d9d-80c7-02af85c822a8"));
+    /// assert!(Uuid::is_valid("urn:uuid:936da01f-9abd-4d9d-80c7-02af85c822a8"));
+    /// assert!(!Uuid::is_valid("936da01f-9abd-4d9d-80c7-02af85c822ag"));
+    /// ```
+    ///
+    /// [`Uuid::parse_str`]: #method.parse_str
+    pub const fn is_valid(input: &str) -> bool {
+        validate(input.as_bytes()).is_some()
+    }
+
+    /// Checks if a string is a valid UUID of the given [`Version`], with
+    /// the RFC4122 variant.
+    ///
+    /// Like [`Uuid::is_valid`], this doesn't build the `Uuid`. The nil
+    /// UUID doesn't have the RFC4122 variant, so it's never valid for
+    /// [`Version::Nil`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{Uuid, Version};
+    ///
+    /// let input = "936da01f-9abd-4d9d-80c7-02af85c822a8";
+    ///
+    /// assert!(Uuid::is_valid_version(input, Version::Random));
+    /// assert!(!Uuid::is_valid_version(input, Version::Sha1));
+    /// ```
+    ///
+    /// [`Version`]: enum.Version.html
+    /// [`Version::Nil`]: enum.Version.html#variant.Nil
+    /// [`Uuid::is_valid`]: #method.is_valid
+    pub const fn is_valid_version(input: &str, version: Version) -> bool {
+        let input = input.as_bytes();
+
+        match validate(input) {
+            // the version is the first digit of the third group, and the
+            // variant the first digit of the fourth
+            Some(groups) => {
+                input[groups[2]] == version_digit(version)
+                    && matches!(
+                        input[groups[3]],
+                        b'8' | b'9' | b'a' | b'b' | b'A' | b'B'
+                    )
+            }
+            None => false,
+        }
+    }
+
+    /// Parses a `Uuid` from a string, tolerating common variations in
+    /// formatting.
+    ///
+    /// On top of the formats accepted by [`Uuid::parse_str`], this allows
+    /// any combination of:
+    ///
+    /// * leading and trailing whitespace,
+    /// * braces around the UUID,
+    /// * a `urn:uuid:` prefix in any case,
+    /// * missing hyphens between any of the groups.
+    ///
+    /// # Errors
+    ///
+    /// If the input is invalid, the error is usually the one
+    /// [`Uuid::parse_str`] returns for the input with the whitespace,
+    /// braces and prefix removed. Its [`Error::parse_error_index`] is
+    /// relative to the whole `input`, like the one returned by
+    /// [`Uuid::parse_all`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::parse_str("936da01f-9abd-4d9d-80c7-02af85c822a8").unwrap();
+    ///
+    /// for input in &[
+    ///     " {936DA01F-9ABD-4D9D-80C7-02AF85C822A8}\n",
+    ///     "URN:UUID:936da01f9abd4d9d-80c7-02af85c822a8",
+    ///     "{urn:uuid:936da01f-9abd4d9d80c702af85c822a8}",
+    /// ] {
+    ///     assert_eq!(Uuid::parse_lenient(input), Ok(uuid));
+    /// }
+    ///
+    /// assert!(Uuid::parse_lenient("{936da01f-9abd-4d9d-80c7-02af85c822a8").is_err());
+    ///
+    /// let err = Uuid::parse_lenient(" {936da01f-9abd-4d9d-80c7-02af85c822ag}").unwrap_err();
+    /// assert_eq!(err.parse_error_index(), Some(37));
+    /// ```
+    ///
+    /// [`Uuid::parse_str`]: #method.parse_str
+    /// [`Uuid::parse_all`]: #method.parse_all
+    /// [`Error::parse_error_index`]: struct.Error.html#method.parse_error_index
+    pub fn parse_lenient(input: &str) -> Result<Uuid, crate::Error> {
+        let original = input;
+        let mut input = input.trim();
+
+        if input.len() >= 2 && input.starts_with('{') && input.ends_with('}') {
+            input = &input[1..input.len() - 1];
+        }
+
+        if input.len() >= 9
+            && input.is_char_boundary(9)
+            && input[..9].eq_ignore_ascii_case("urn:uuid:")
+        {
+            input = &input[9..];
+        }
+
+        let offset = input.as_ptr() as usize - original.as_ptr() as usize;
+
+        Uuid::parse_lenient_stripped(input).map_err(|err| err.offset(offset))
+    }
+
+    /// Parses the part of the input to [`Uuid::parse_lenient`] that's left
+    /// after removing whitespace, braces and the `urn:uuid:` prefix.
+    ///
+    /// [`Uuid::parse_lenient`]: #method.parse_lenient
+    fn parse_lenient_stripped(input: &str) -> Result<Uuid, crate::Error> {
+        let mut digits = [0u8; adapter::Simple::LENGTH];
+        let mut digit = 0;
+        let mut hyphen_allowed = false;
+
+        for (i_char, chr) in input.bytes().enumerate() {
+            if chr == b'-' && hyphen_allowed {
+                hyphen_allowed = false;
+                continue;
+            }
+
+            if digit == digits.len() || !chr.is_ascii_hexdigit() {
+                // `parse_str` still accepts a second `urn:uuid:` prefix
+                return Uuid::parse_str(input).and_then(|_| {
+                    Err(crate::Error::parser(Error::InvalidCharacter {
+                        expected: "0123456789abcdefABCDEF-",
+                        found: char_at(input.as_bytes(), i_char),
+                        index: i_char,
+                        urn: error::UrnPrefix::Optional,
+                    }))
+                });
+            }
+
+            digits[digit] = chr;
+            digit += 1;
+            hyphen_allowed = ACC_GROUP_LENS[..4].contains(&digit);
+        }
+
+        match crate::std::str::from_utf8(&digits[..digit]) {
+            Ok(simple) if digit == digits.len() => Uuid::parse_str(simple),
+            _ => Uuid::parse_str(input),
+        }
+    }
+}
+
+/// Returns the position of each group of digits in a valid UUID string, or
+/// `None` if the string isn't valid.
+const fn validate(input: &[u8]) -> Option<[usize; 5]> {
+    let (start, hyphens) = match input.len() {
+        32 => (0, 0),
+        36 => (0, 1),
+        45 if has_urn_prefix(input) => (9, 1),
+        38 if input[0] == b'{' && input[37] == b'}' => (1, 1),
+        _ => return None,
+    };
+
+    let mut groups = [0; 5];
+    let mut group = 0;
+    while group < 5 {
+        groups[group] =
+            start + ACC_GROUP_LENS[group] - GROUP_LENS[group] + group * hyphens;
+
+        if group > 0 && hyphens == 1 && input[groups[group] - 1] != b'-' {
+            return None;
+        }
+
+        let mut i = groups[group];
+        while i < groups[group] + GROUP_LENS[group] {
+            if !input[i].is_ascii_hexdigit() {
+                return None;
+            }
+
+            i += 1;
+        }
+
+        group += 1;
+    }
+
+    Some(groups)
+}
+
+/// Checks if `input` starts with `urn:uuid:`.
+const fn has_urn_prefix(input: &[u8]) -> bool {
+    const PREFIX: &[u8] = b"urn:uuid:";
+
+    let mut i = 0;
+    while i < PREFIX.len() {
+        if input[i] != PREFIX[i] {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
+/// Returns the lower case hex digit of a version number.
+const fn version_digit(version: Version) -> u8 {
+    match version {
+        Version::Max => b'f',
+        version => b'0' + version as u8,
+    }
+}
+
+/// Decodes the UTF-8 encoded character starting at `input[index]`, or
+/// returns the replacement character if the bytes aren't valid UTF-8.
+const fn char_at(input: &[u8], index: usize) -> char {
+    let first = input[index] as u32;
+
+    let (mut code, len) = if first < 0x80 {
+        (first, 1)
+    } else if first < 0xc0 {
+        return char::REPLACEMENT_CHARACTER;
+    } else if first < 0xe0 {
+        (first & 0x1f, 2)
+    } else if first < 0xf0 {
+        (first & 0x0f, 3)
+    } else {
+        (first & 0x07, 4)
+    };
+
+    if index + len > input.len() {
+        return char::REPLACEMENT_CHARACTER;
+    }
+
+    let mut i = 1;
+    while i < len {
+        let next = input[index + i] as u32;
+
+        if next & 0xc0 != 0x80 {
+            return char::REPLACEMENT_CHARACTER;
+        }
+
+        code = code << 6 | (next & 0x3f);
+        i += 1;
+    }
+
+    match char::from_u32(code) {
+        Some(chr) => chr,
+        None => char::REPLACEMENT_CHARACTER,
+    }
 }
 
 #[cfg(test)]
@@ -215,7 +667,7 @@ mod tests {
         const EXPECTED_GROUP_COUNTS: error::ExpectedLength =
             error::ExpectedLength::Any(&[1, 5]);
 
-        const EXPECTED_CHARS: &'static str = "0123456789abcdefABCDEF-";
+        const EXPECTED_CHARS: &str = "0123456789abcdefABCDEF-";
 
         // Invalid
         assert_eq!(
@@ -268,7 +720,8 @@ mod tests {
                 .map_err(crate::Error::expect_parser),
             Err(Error::InvalidGroupCount {
                 expected: EXPECTED_GROUP_COUNTS,
-                found: 2
+                found: 2,
+                index: 33,
             })
         );
 
@@ -278,6 +731,7 @@ mod tests {
             Err(Error::InvalidGroupCount {
                 expected: EXPECTED_GROUP_COUNTS,
                 found: 3,
+                index: 34,
             })
         );
 
@@ -287,6 +741,7 @@ mod tests {
             Err(Error::InvalidGroupCount {
                 expected: EXPECTED_GROUP_COUNTS,
                 found: 4,
+                index: 35,
             })
         );
 
@@ -302,11 +757,18 @@ mod tests {
         assert_eq!(
             Uuid::parse_str("F9168C5E-CEB2-4faaXB6BFF329BF39FA1E4")
                 .map_err(crate::Error::expect_parser),
-            Err(Error::InvalidCharacter {
-                expected: EXPECTED_CHARS,
+            Err(Error::InvalidSeparator {
                 found: 'X',
                 index: 18,
-                urn: error::UrnPrefix::Optional,
+            })
+        );
+
+        assert_eq!(
+            Uuid::parse_str("{F9168C5E_CEB2-4faa-B6BF-329BF39FA1E4}")
+                .map_err(crate::Error::expect_parser),
+            Err(Error::InvalidSeparator {
+                found: '_',
+                index: 9,
             })
         );
 
@@ -317,6 +779,7 @@ mod tests {
                 expected: error::ExpectedLength::Exact(4),
                 found: 3,
                 group: 1,
+                index: 12,
             })
         );
         // (group, found, expecting)
@@ -328,6 +791,7 @@ mod tests {
                 expected: error::ExpectedLength::Exact(12),
                 found: 8,
                 group: 4,
+                index: 32,
             })
         );
 
@@ -388,6 +852,29 @@ mod tests {
             "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"
         )
         .is_ok());
+        assert!(
+            Uuid::parse_str("{67e55044-10b1-426f-9247-bb680e5fe0c8}").is_ok()
+        );
+
+        assert_eq!(
+            Uuid::parse_str("{67e55044-10b1-426f-9247-bb680e5fe0cX}")
+                .map_err(crate::Error::expect_parser),
+            Err(Error::InvalidCharacter {
+                expected: EXPECTED_CHARS,
+                found: 'X',
+                index: 36,
+                urn: error::UrnPrefix::Optional,
+            })
+        );
+
+        assert_eq!(
+            Uuid::parse_str("{67e55044-10b1-426f-9247-bb680e5fe0c8)")
+                .map_err(crate::Error::expect_parser),
+            Err(Error::InvalidLength {
+                expected: EXPECTED_UUID_LENGTHS,
+                found: 38,
+            })
+        );
 
         // Nil
         let nil = Uuid::nil();
@@ -432,6 +919,7 @@ mod tests {
                 expected: error::ExpectedLength::Exact(8),
                 found: 6,
                 group: 0,
+                index: 6,
             })
         );
         assert_eq!(
@@ -441,7 +929,369 @@ mod tests {
                 expected: error::ExpectedLength::Exact(4),
                 found: 5,
                 group: 3,
+                index: 24,
             })
         );
     }
+
+    #[test]
+    fn test_try_parse() {
+        const UUID: Uuid =
+            match Uuid::try_parse("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4") {
+                Ok(uuid) => uuid,
+                Err(_) => panic!("invalid UUID"),
+            };
+
+        assert_eq!(UUID, test_util::new());
+
+        for input in &[
+            "urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+            "F9168C5ECEB24FAAB6BF329BF39FA1E4",
+            "F9168C5E-CEB2-4faa-B6BF-329BF39FA1E45",
+            "F9168C5E-CEB2F4faaFB6BFF329BF39FA1E4",
+            "F9168C5E-CEB2-4faa-B6BF1-02BF39FA1E4",
+            "F9168C5E-CEB2-4faa-B\u{e9}F-329BF39FA1E4",
+            "F9168C5E-CEB2-4faa-B\u{1f600}-329BF39FA1E4",
+        ] {
+            assert_eq!(
+                Uuid::try_parse(input),
+                Uuid::parse_str(input),
+                "{}",
+                input
+            );
+        }
+
+        assert_eq!(
+            Uuid::try_parse("F9168C5E-CEB2-4faa-B\u{e9}F-329BF39FA1E4")
+                .map_err(crate::Error::expect_parser),
+            Err(Error::InvalidCharacter {
+                expected: "0123456789abcdefABCDEF-",
+                found: '\u{e9}',
+                index: 20,
+                urn: error::UrnPrefix::Optional,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let uuid = test_util::new();
+
+        for input in &[
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+            "F9168C5ECEB24FAAB6BF329BF39FA1E4",
+            "  f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4\t",
+            "{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}",
+            "{F9168C5ECEB24FAAB6BF329BF39FA1E4}",
+            "Urn:Uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+            "{URN:UUID:f9168c5eceb24faab6bf329bf39fa1e4}",
+            "f9168c5e-ceb24faa-b6bf329bf39fa1e4",
+            "f9168c5eceb2-4faab6bf-329bf39fa1e4",
+        ] {
+            assert_eq!(Uuid::parse_lenient(input), Ok(uuid), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_lenient_invalid() {
+        for input in &[
+            "",
+            "{}",
+            "{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+            "f9168c5e--ceb2-4faa-b6bf-329bf39fa1e4",
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4-",
+            "f9168c5-eceb2-4faa-b6bf-329bf39fa1e4",
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e",
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4f",
+            "urn:uuid:urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+            "f9168c5e ceb2 4faa b6bf 329bf39fa1e4",
+            "urn:uui\u{e9}f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+        ] {
+            assert!(Uuid::parse_lenient(input).is_err(), "{}", input);
+        }
+
+        assert_eq!(
+            Uuid::parse_lenient(" {F9168C5E-CEB2-4faa-BGBF-329BF39FA1E4} ")
+                .map_err(crate::Error::expect_parser),
+            Err(Error::InvalidCharacter {
+                expected: "0123456789abcdefABCDEF-",
+                found: 'G',
+                index: 22,
+                urn: error::UrnPrefix::Optional,
+            })
+        );
+
+        // the prefix is skipped, and so is the position of the error
+        assert_eq!(
+            Uuid::parse_lenient(
+                "URN:UUID:f9168c5e ceb2-4faa-b6bf-329bf39fa1e4"
+            )
+            .unwrap_err()
+            .parse_error_index(),
+            Some(17)
+        );
+    }
+
+    #[test]
+    fn test_is_valid() {
+        for input in &[
+            "",
+            "!",
+            "F9168C5E-CEB2-4faa-B6BF-329BF39FA1E4",
+            "F9168C5E-CEB2-4faa-B6BF-329BF39FA1E45",
+            "F9168C5E-CEB2-4faa-BBF-329BF39FA1E4",
+            "F9168C5E-CEB2-4faa-BGBF-329BF39FA1E4",
+            "F9168C5E-CEB2F4faaFB6BFF329BF39FA1E4",
+            "F9168C5E-CEB-24fa-eB6BFF32-BF39FA1E4",
+            "01020304-1112-2122-3132-41424344",
+            "F9168C5ECEB24faaB6BF329BF39FA1E4",
+            "F9168C5ECEB24faaB6BF329BF39FA1E4-",
+            "F9168C5E-CEB2-4faa-B6BF-329BF39FA1E4-",
+            "urn:uuid:F9168C5E-CEB2-4faa-B6BF-329BF39FA1E4",
+            "urn:uuid:F9168C5E-CEB2-4faa-B6BF-329BF39FA1G4",
+            "urn:uuid:F9168C5ECEB24faaB6BF329BF39FA1E4",
+            "urn:uuid-F9168C5E-CEB2-4faa-B6BF-329BF39FA1E4",
+            "{F9168C5E-CEB2-4faa-B6BF-329BF39FA1E4}",
+            "{F9168C5E-CEB2-4faa-B6BF-329BF39FA1E4)",
+            "{F9168C5ECEB24faaB6BF329BF39FA1E4}",
+            "F9168C5E-CEB2-4faa-B\u{e9}F-329BF39FA1E4",
+        ] {
+            assert_eq!(
+                Uuid::is_valid(input),
+                Uuid::parse_str(input).is_ok(),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_valid_version() {
+        let input = "F9168C5E-CEB2-4faa-B6BF-329BF39FA1E4";
+
+        assert!(Uuid::is_valid_version(input, Version::Random));
+        assert!(Uuid::is_valid_version(
+            "f9168c5eceb24faab6bf329bf39fa1e4",
+            Version::Random
+        ));
+        assert!(!Uuid::is_valid_version(input, Version::Md5));
+        assert!(!Uuid::is_valid_version(input, Version::Max));
+        assert!(Uuid::is_valid_version(
+            "017f22e2-79b0-7cc3-98c4-dc0c0c07398f",
+            Version::SortRand
+        ));
+        assert!(!Uuid::is_valid_version(
+            "F9168C5E-CEB2-4faa-76BF-329BF39FA1E4",
+            Version::Random
+        ));
+        assert!(!Uuid::is_valid_version(
+            "F9168C5E-CEB2-4faa-B6BF-329BF39FA1EG",
+            Version::Random
+        ));
+        assert!(!Uuid::is_valid_version(
+            "00000000-0000-0000-0000-000000000000",
+            Version::Nil
+        ));
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let input = "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4\r\n\
+                     \r\n  \
+                     F9168C5ECEB24FAAB6BF329BF39FA1E4\r\n\
+                     F9168C5E-CEB2-4faa-BGBF-329BF39FA1E4\r\n\
+                     F9168C5E\r\n";
+
+        let results = Uuid::parse_all(input, '\n')
+            .map(|result| result.map_err(crate::Error::expect_parser))
+            .collect::<std::vec::Vec<_>>();
+
+        assert_eq!(
+            results,
+            [
+                Ok(test_util::new()),
+                Ok(test_util::new()),
+                Err(Error::InvalidCharacter {
+                    expected: "0123456789abcdefABCDEF-",
+                    found: 'G',
+                    index: 76 + 20,
+                    urn: error::UrnPrefix::Optional,
+                }),
+                Err(Error::InvalidLength {
+                    expected: error::ExpectedLength::Any(&[36, 32]),
+                    found: 8,
+                }),
+            ]
+        );
+        assert_eq!(&input[96..97], "G");
+
+        assert_eq!(Uuid::parse_all("", ',').count(), 0);
+        assert_eq!(Uuid::parse_all(" , ,", ',').count(), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_all_to_vec() {
+        let uuids = Uuid::parse_all_to_vec(
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4,\
+             f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+            ',',
+        );
+
+        assert_eq!(uuids, Ok(vec![test_util::new(), test_util::new()]));
+        assert!(Uuid::parse_all_to_vec("f9168c5e,", ',').is_err());
+    }
+
+    #[test]
+    fn test_parse_never_panics() {
+        use crate::std::{string::String, vec::Vec};
+
+        let check = |input: &str| {
+            let wide: Vec<u16> = input.encode_utf16().collect();
+
+            let parsed = Uuid::parse_str(input);
+            assert_eq!(Uuid::try_parse(input), parsed);
+            assert_eq!(Uuid::try_parse_ascii(input.as_bytes()), parsed);
+            assert_eq!(Uuid::is_valid(input), parsed.is_ok());
+
+            let _ = Uuid::parse_lenient(input);
+            let _ = Uuid::parse_utf16(&wide);
+            let _ = Uuid::from_base32(input);
+            let _ = Uuid::from_base58(input);
+            let _ = Uuid::from_base64url(input);
+        };
+
+        for base in &[
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+            "f9168c5eceb24faab6bf329bf39fa1e4",
+            "urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+            "{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}",
+            "4KDPG1Z6NX9PER1HR2NY2WG8N8",
+        ] {
+            for len in 0..=base.len() {
+                check(&base[..len]);
+
+                for &chr in &['\u{e9}', '\u{1f600}', '-', '{', '}', ':', 'g'] {
+                    let mut input = String::from(&base[..len]);
+                    input.push(chr);
+                    input.push_str(base.get(len + 1..).unwrap_or(""));
+
+                    check(&input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_parse_ascii() {
+        let input = b"f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4";
+
+        assert_eq!(Uuid::try_parse_ascii(input), Ok(test_util::new()));
+        assert_eq!(
+            Uuid::try_parse_ascii(&input[..]),
+            Uuid::parse_str("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4")
+        );
+
+        for (bytes, found) in &[
+            (&[0xc3, 0xa9][..], '\u{e9}'),
+            (&[0xff, b'0'][..], char::REPLACEMENT_CHARACTER),
+            (&[0xa9, b'0'][..], char::REPLACEMENT_CHARACTER),
+            (&[0xc3, b'0'][..], char::REPLACEMENT_CHARACTER),
+        ] {
+            let mut input = *input;
+            input[20..22].copy_from_slice(bytes);
+
+            assert_eq!(
+                Uuid::try_parse_ascii(&input)
+                    .map_err(crate::Error::expect_parser),
+                Err(Error::InvalidCharacter {
+                    expected: "0123456789abcdefABCDEF-",
+                    found: *found,
+                    index: 20,
+                    urn: error::UrnPrefix::Optional,
+                })
+            );
+        }
+
+        let mut input = *b"f9168c5eceb24faab6bf329bf39fa1e4";
+        input[31] = 0xf0;
+
+        assert!(Uuid::try_parse_ascii(&input).is_err());
+    }
+
+    #[test]
+    fn test_parse_utf16() {
+        let wide = |s: &str| s.encode_utf16().collect::<std::vec::Vec<_>>();
+
+        for input in &[
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+            "F9168C5ECEB24FAAB6BF329BF39FA1E4",
+            "urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+            "{F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4}",
+            "{F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4}\0",
+        ] {
+            assert_eq!(
+                Uuid::parse_utf16(&wide(input)),
+                Ok(test_util::new()),
+                "{}",
+                input
+            );
+        }
+
+        for (input, found, index) in &[
+            ("F9168C5E-CEB2-4faa-B\u{e9}BF-329BF39FA1E4", '\u{e9}', 20),
+            (
+                "{F9168C5E-CEB2-4faa-B\u{1f600}F-329BF39FA1E4}",
+                '\u{1f600}',
+                21,
+            ),
+            ("{F9168C5E-CEB2-4faa-BGBF-329BF39FA1E4}", 'G', 21),
+        ] {
+            assert_eq!(
+                Uuid::parse_utf16(&wide(input))
+                    .map_err(crate::Error::expect_parser),
+                Err(Error::InvalidCharacter {
+                    expected: "0123456789abcdefABCDEF-",
+                    found: *found,
+                    index: *index,
+                    urn: error::UrnPrefix::Optional,
+                }),
+                "{}",
+                input
+            );
+        }
+
+        // a unit outside ASCII where a hyphen belongs
+        assert_eq!(
+            Uuid::parse_utf16(&wide(
+                "F9168C5E\u{e9}CEB2-4faa-B6BF-329BF39FA1E4"
+            ))
+            .map_err(crate::Error::expect_parser),
+            Err(Error::InvalidSeparator {
+                found: '\u{e9}',
+                index: 8,
+            })
+        );
+
+        let mut lone_surrogate = wide("F9168C5E-CEB2-4faa-BBBF-329BF39FA1E4");
+        lone_surrogate[20] = 0xd800;
+
+        assert_eq!(
+            Uuid::parse_utf16(&lone_surrogate)
+                .map_err(crate::Error::expect_parser),
+            Err(Error::InvalidCharacter {
+                expected: "0123456789abcdefABCDEF-",
+                found: std::char::REPLACEMENT_CHARACTER,
+                index: 20,
+                urn: error::UrnPrefix::Optional,
+            })
+        );
+
+        assert!(Uuid::parse_utf16(&[]).is_err());
+        assert!(Uuid::parse_utf16(&[b'0' as u16; 64]).is_err());
+        assert!(
+            Uuid::parse_utf16(&wide("{F9168C5ECEB24FAAB6BF329BF39FA1E4}"))
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,353 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Vectorized fast path for parsing.
+//!
+//! Only valid simple and hyphenated strings are handled here. Anything
+//! else falls through to the scalar parser, which reports the error.
+
+use crate::prelude::*;
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use crate::std::arch::aarch64::*;
+#[cfg(target_arch = "x86")]
+use crate::std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use crate::std::arch::x86_64::*;
+
+/// Parses a simple or hyphenated string, returning `None` if it isn't
+/// valid.
+pub(super) fn parse(input: &[u8]) -> Option<Uuid> {
+    let mut digits = [0; 32];
+
+    match input.len() {
+        32 => digits.copy_from_slice(input),
+        36 => {
+            if input[8] != b'-'
+                || input[13] != b'-'
+                || input[18] != b'-'
+                || input[23] != b'-'
+            {
+                return None;
+            }
+
+            digits[..8].copy_from_slice(&input[..8]);
+            digits[8..12].copy_from_slice(&input[9..13]);
+            digits[12..16].copy_from_slice(&input[14..18]);
+            digits[16..20].copy_from_slice(&input[19..23]);
+            digits[20..].copy_from_slice(&input[24..]);
+        }
+        _ => return None,
+    }
+
+    decode(&digits).map(Uuid::from_bytes)
+}
+
+/// A backend for [`decode`], which is only safe to call if the CPU
+/// supports the features it's compiled with.
+///
+/// [`decode`]: fn.decode.html
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+type Decode = unsafe fn(&[u8; 32]) -> Option<Bytes>;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn decode(digits: &[u8; 32]) -> Option<Bytes> {
+    // `backend` only returns backends the CPU supports
+    unsafe { backend()(digits) }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+fn decode(digits: &[u8; 32]) -> Option<Bytes> {
+    // NEON is part of the aarch64 baseline, so it's enabled at compile time
+    unsafe { decode_neon(digits) }
+}
+
+#[cfg(not(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_feature = "neon")
+)))]
+fn decode(_: &[u8; 32]) -> Option<Bytes> {
+    None
+}
+
+/// Picks the fastest backend the CPU supports, detecting the features on
+/// the first call.
+#[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))]
+fn backend() -> Decode {
+    use crate::std::{mem, ptr, sync::atomic};
+
+    static BACKEND: atomic::AtomicPtr<()> =
+        atomic::AtomicPtr::new(ptr::null_mut());
+
+    let backend = BACKEND.load(atomic::Ordering::Relaxed);
+    if !backend.is_null() {
+        // only `Decode` function pointers are stored
+        return unsafe { mem::transmute::<*mut (), Decode>(backend) };
+    }
+
+    let backend: Decode = if is_x86_feature_detected!("avx2") {
+        decode_avx2
+    } else if is_x86_feature_detected!("sse2") {
+        decode_sse2
+    } else {
+        decode_none
+    };
+
+    BACKEND.store(backend as *mut (), atomic::Ordering::Relaxed);
+
+    backend
+}
+
+/// Picks the fastest backend enabled at compile time, since detecting CPU
+/// features at runtime needs `std`.
+#[cfg(all(
+    not(feature = "std"),
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+fn backend() -> Decode {
+    if cfg!(target_feature = "avx2") {
+        decode_avx2
+    } else if cfg!(target_feature = "sse2") {
+        decode_sse2
+    } else {
+        decode_none
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+unsafe fn decode_none(_: &[u8; 32]) -> Option<Bytes> {
+    None
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn decode_sse2(digits: &[u8; 32]) -> Option<Bytes> {
+    let mut bytes = [0; 16];
+
+    let digits = digits.as_ptr() as *const __m128i;
+
+    let high = nibbles_to_bytes_sse2(_mm_loadu_si128(digits))?;
+    let low = nibbles_to_bytes_sse2(_mm_loadu_si128(digits.add(1)))?;
+
+    _mm_storeu_si128(
+        bytes.as_mut_ptr() as *mut __m128i,
+        _mm_packus_epi16(high, low),
+    );
+
+    Some(bytes)
+}
+
+/// Decodes 16 hexadecimal digits into 8 bytes, each in the low half of a
+/// 16-bit lane.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn nibbles_to_bytes_sse2(chunk: __m128i) -> Option<__m128i> {
+    // `digit` is in `0..=9` for `0-9`, `alpha` in `0..=5` for `a-f` and
+    // `A-F`, and both wrap around to large values otherwise.
+    let digit = _mm_sub_epi8(chunk, _mm_set1_epi8(b'0' as i8));
+    let alpha = _mm_sub_epi8(
+        _mm_or_si128(chunk, _mm_set1_epi8(0x20)),
+        _mm_set1_epi8(b'a' as i8),
+    );
+
+    let is_digit = _mm_cmpeq_epi8(_mm_min_epu8(digit, _mm_set1_epi8(9)), digit);
+    let is_alpha = _mm_cmpeq_epi8(_mm_min_epu8(alpha, _mm_set1_epi8(5)), alpha);
+
+    if _mm_movemask_epi8(_mm_or_si128(is_digit, is_alpha)) != 0xffff {
+        return None;
+    }
+
+    let nibbles = _mm_or_si128(
+        _mm_and_si128(is_digit, digit),
+        _mm_andnot_si128(is_digit, _mm_add_epi8(alpha, _mm_set1_epi8(10))),
+    );
+
+    // Each 16-bit lane holds a high nibble in its low byte, and the
+    // matching low nibble in its high byte.
+    let high = _mm_slli_epi16(_mm_and_si128(nibbles, _mm_set1_epi16(0xff)), 4);
+    let low = _mm_srli_epi16(nibbles, 8);
+
+    Some(_mm_or_si128(high, low))
+}
+
+/// Decodes all 32 hexadecimal digits at once, the same way as
+/// [`decode_sse2`].
+///
+/// [`decode_sse2`]: fn.decode_sse2.html
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn decode_avx2(digits: &[u8; 32]) -> Option<Bytes> {
+    let mut bytes = [0; 16];
+
+    let chunk = _mm256_loadu_si256(digits.as_ptr() as *const __m256i);
+
+    let digit = _mm256_sub_epi8(chunk, _mm256_set1_epi8(b'0' as i8));
+    let alpha = _mm256_sub_epi8(
+        _mm256_or_si256(chunk, _mm256_set1_epi8(0x20)),
+        _mm256_set1_epi8(b'a' as i8),
+    );
+
+    let is_digit =
+        _mm256_cmpeq_epi8(_mm256_min_epu8(digit, _mm256_set1_epi8(9)), digit);
+    let is_alpha =
+        _mm256_cmpeq_epi8(_mm256_min_epu8(alpha, _mm256_set1_epi8(5)), alpha);
+
+    if _mm256_movemask_epi8(_mm256_or_si256(is_digit, is_alpha)) != -1 {
+        return None;
+    }
+
+    let nibbles = _mm256_or_si256(
+        _mm256_and_si256(is_digit, digit),
+        _mm256_andnot_si256(
+            is_digit,
+            _mm256_add_epi8(alpha, _mm256_set1_epi8(10)),
+        ),
+    );
+
+    let high = _mm256_slli_epi16(
+        _mm256_and_si256(nibbles, _mm256_set1_epi16(0xff)),
+        4,
+    );
+    let low = _mm256_srli_epi16(nibbles, 8);
+
+    // Packing works within each 128-bit half, so the 8 bytes of each half
+    // end up in the first and third 64-bit lanes.
+    let packed =
+        _mm256_packus_epi16(_mm256_or_si256(high, low), _mm256_setzero_si256());
+    let packed = _mm256_permute4x64_epi64(packed, 0b00_00_10_00);
+
+    _mm_storeu_si128(
+        bytes.as_mut_ptr() as *mut __m128i,
+        _mm256_castsi256_si128(packed),
+    );
+
+    Some(bytes)
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+#[target_feature(enable = "neon")]
+unsafe fn decode_neon(digits: &[u8; 32]) -> Option<Bytes> {
+    let mut bytes = [0; 16];
+
+    let first = nibbles_neon(vld1q_u8(digits.as_ptr()))?;
+    let second = nibbles_neon(vld1q_u8(digits.as_ptr().add(16)))?;
+
+    // the even digits are the high nibbles of the bytes, and the odd ones
+    // the low nibbles
+    let packed = vorrq_u8(
+        vshlq_n_u8(vuzp1q_u8(first, second), 4),
+        vuzp2q_u8(first, second),
+    );
+
+    vst1q_u8(bytes.as_mut_ptr(), packed);
+
+    Some(bytes)
+}
+
+/// Decodes 16 hexadecimal digits into their values, one per byte.
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+#[target_feature(enable = "neon")]
+unsafe fn nibbles_neon(chunk: uint8x16_t) -> Option<uint8x16_t> {
+    // the same ranges as in `nibbles_to_bytes_sse2`
+    let digit = vsubq_u8(chunk, vdupq_n_u8(b'0'));
+    let alpha = vsubq_u8(vorrq_u8(chunk, vdupq_n_u8(0x20)), vdupq_n_u8(b'a'));
+
+    let is_digit = vcltq_u8(digit, vdupq_n_u8(10));
+    let is_alpha = vcltq_u8(alpha, vdupq_n_u8(6));
+
+    if vminvq_u8(vorrq_u8(is_digit, is_alpha)) == 0 {
+        return None;
+    }
+
+    Some(vbslq_u8(is_digit, digit, vaddq_u8(alpha, vdupq_n_u8(10))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std::{string::ToString, vec::Vec};
+    use crate::test_util;
+
+    #[test]
+    fn test_parse_valid() {
+        if cfg!(not(any(
+            target_arch = "x86",
+            target_arch = "x86_64",
+            all(target_arch = "aarch64", target_feature = "neon")
+        ))) {
+            return;
+        }
+
+        for &uuid in &[
+            Uuid::nil(),
+            Uuid::max(),
+            test_util::new(),
+            test_util::new2(),
+        ] {
+            let simple = uuid.to_simple().to_string();
+            let hyphenated = uuid.to_hyphenated().to_string();
+
+            for s in &[
+                simple.to_uppercase(),
+                simple,
+                hyphenated.to_uppercase(),
+                hyphenated,
+            ] {
+                assert_eq!(parse(s.as_bytes()), Some(uuid));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let valid = *b"f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4";
+
+        for i in 0..valid.len() {
+            for &c in b"/:@Gg`\xff-" {
+                let mut input = valid;
+                input[i] = c;
+
+                if input != valid {
+                    assert_eq!(parse(&input), None, "{:?}", input);
+                }
+            }
+        }
+
+        assert_eq!(parse(b"f9168c5e-ceb2-4faa-b6bf-329bf39fa1e"), None);
+        assert_eq!(parse(b"f9168c5eceb24faab6bf329bf39fa1e"), None);
+        assert_eq!(parse(b"f9168c5e-ceb24faa-b6bf-329bf39fa1e4"), None);
+    }
+
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[test]
+    fn test_backends() {
+        let valid = *b"f9168c5eceb24faab6bf329bf39fa1e4";
+        let mut invalid = valid;
+        invalid[31] = b'g';
+
+        let mut backends: Vec<Decode> = vec![];
+
+        if is_x86_feature_detected!("sse2") {
+            backends.push(decode_sse2);
+        }
+        if is_x86_feature_detected!("avx2") {
+            backends.push(decode_avx2);
+        }
+
+        for backend in backends {
+            unsafe {
+                assert_eq!(backend(&valid), Some(*test_util::new().as_bytes()));
+                assert_eq!(backend(&invalid), None);
+            }
+        }
+    }
+}
@@ -0,0 +1,396 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A pool of pre-generated random UUIDs.
+//!
+//! [`Uuid::new_v4`] asks the operating system for 16 random bytes every
+//! time, which is usually fast, but is a system call whose latency isn't
+//! bounded. [`UuidPool`] asks for random bytes in bulk ahead of time, so
+//! taking a UUID out of it is only a few atomic operations.
+//!
+//! Only version 4 UUIDs are pooled: a pooled time-based UUID would carry
+//! the time it was generated at, instead of the time it was used at.
+//!
+//! Note that you need features `v4` and `std` in order to use this module,
+//! and that it's only available on targets with 64-bit atomics.
+//!
+//! [`Uuid::new_v4`]: ../struct.Uuid.html#method.new_v4
+//! [`UuidPool`]: struct.UuidPool.html
+
+use crate::prelude::*;
+use crate::std::{
+    boxed::Box,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+    thread,
+    time::Duration,
+    vec::Vec,
+};
+
+/// The number of UUIDs generated with a single request for random bytes.
+const BATCH: usize = 64;
+
+/// A pool of pre-generated version 4 UUIDs.
+///
+/// The pool is a fixed size ring buffer that any number of threads can
+/// take UUIDs out of without locking. It's refilled either by the thread
+/// that finds it empty, or ahead of time by a background thread started
+/// with [`UuidPool::spawn_refill`]. Clones of a pool share its buffer.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use uuid::{pool::UuidPool, Version};
+///
+/// let pool = UuidPool::new(1024);
+/// pool.spawn_refill(Duration::from_millis(10));
+///
+/// let uuid = pool.get().expect("failed to generate UUID");
+///
+/// assert_eq!(uuid.get_version(), Some(Version::Random));
+/// ```
+///
+/// [`UuidPool::spawn_refill`]: #method.spawn_refill
+#[derive(Clone, Debug)]
+pub struct UuidPool {
+    inner: Arc<Ring>,
+}
+
+impl UuidPool {
+    /// Creates an empty pool that holds up to `capacity` UUIDs.
+    ///
+    /// The capacity is rounded up to the next power of two, and is at
+    /// least 2.
+    pub fn new(capacity: usize) -> Self {
+        UuidPool {
+            inner: Arc::new(Ring::new(capacity.max(2).next_power_of_two())),
+        }
+    }
+
+    /// Returns the number of UUIDs the pool can hold.
+    pub fn capacity(&self) -> usize {
+        self.inner.slots.len()
+    }
+
+    /// Returns the number of UUIDs in the pool.
+    ///
+    /// Other threads may take UUIDs out of the pool or put them in at the
+    /// same time, so this is only an estimate.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the pool is empty.
+    ///
+    /// This is only an estimate, just like [`UuidPool::len`].
+    ///
+    /// [`UuidPool::len`]: #method.len
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Takes a UUID out of the pool, refilling it first if it's empty.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the pool is empty and random
+    /// bytes can't be read from the operating system.
+    pub fn get(&self) -> Result<Uuid, getrandom::Error> {
+        loop {
+            if let Some(uuid) = self.try_get() {
+                return Ok(uuid);
+            }
+
+            self.refill()?;
+        }
+    }
+
+    /// Takes a UUID out of the pool, or returns `None` if it's empty.
+    ///
+    /// This never asks the operating system for random bytes.
+    pub fn try_get(&self) -> Option<Uuid> {
+        self.inner.pop()
+    }
+
+    /// Fills the free space in the pool with new UUIDs, and returns how
+    /// many were added.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if random bytes can't be read
+    /// from the operating system.
+    pub fn refill(&self) -> Result<usize, getrandom::Error> {
+        self.inner.refill()
+    }
+
+    /// Starts a thread that refills the pool whenever it's less than half
+    /// full, checking every `interval`.
+    ///
+    /// The thread stops once every clone of the pool has been dropped, or
+    /// random bytes can't be read from the operating system. Threads taking
+    /// UUIDs out of a pool that's run empty still refill it themselves.
+    pub fn spawn_refill(&self, interval: Duration) -> thread::JoinHandle<()> {
+        let ring = Arc::downgrade(&self.inner);
+
+        thread::spawn(move || refill_loop(ring, interval))
+    }
+}
+
+fn refill_loop(ring: Weak<Ring>, interval: Duration) {
+    while let Some(ring) = ring.upgrade() {
+        if ring.len() < ring.slots.len() / 2 && ring.refill().is_err() {
+            return;
+        }
+
+        // don't keep the pool alive while sleeping
+        drop(ring);
+        thread::sleep(interval);
+    }
+}
+
+/// A slot of the ring buffer.
+///
+/// `seq` tells producers and consumers whose turn it is, the UUID is kept
+/// in two atomic halves so the slot can be shared without locking or
+/// `unsafe`.
+#[derive(Debug)]
+struct Slot {
+    seq: AtomicUsize,
+    high: AtomicU64,
+    low: AtomicU64,
+}
+
+/// A bounded multi-producer multi-consumer queue of UUIDs, after Dmitry
+/// Vyukov's design.
+#[derive(Debug)]
+struct Ring {
+    slots: Box<[Slot]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+impl Ring {
+    fn new(capacity: usize) -> Self {
+        debug_assert!(capacity.is_power_of_two());
+
+        Ring {
+            slots: (0..capacity)
+                .map(|i| Slot {
+                    seq: AtomicUsize::new(i),
+                    high: AtomicU64::new(0),
+                    low: AtomicU64::new(0),
+                })
+                .collect(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Relaxed);
+
+        tail.wrapping_sub(head).min(self.slots.len())
+    }
+
+    fn refill(&self) -> Result<usize, getrandom::Error> {
+        let mut added = 0;
+        let mut bytes = Vec::new();
+
+        loop {
+            let free = self.slots.len().saturating_sub(self.len()).min(BATCH);
+            if free == 0 {
+                return Ok(added);
+            }
+
+            bytes.resize(free * 16, 0);
+            getrandom::getrandom(&mut bytes)?;
+
+            for chunk in bytes.chunks_exact(16) {
+                let mut random = [0; 16];
+                random.copy_from_slice(chunk);
+
+                if !self.push(Builder::from_random_bytes(random).build()) {
+                    return Ok(added);
+                }
+                added += 1;
+            }
+        }
+    }
+
+    fn push(&self, uuid: Uuid) -> bool {
+        let mask = self.slots.len() - 1;
+        let mut pos = self.tail.load(Ordering::Relaxed);
+
+        let slot = loop {
+            let slot = &self.slots[pos & mask];
+            let seq = slot.seq.load(Ordering::Acquire);
+
+            if seq == pos {
+                match self.tail.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => break slot,
+                    Err(current) => pos = current,
+                }
+            } else if (seq.wrapping_sub(pos) as isize) < 0 {
+                // the slot still holds a UUID from the previous lap
+                return false;
+            } else {
+                pos = self.tail.load(Ordering::Relaxed);
+            }
+        };
+
+        let value = uuid.as_u128();
+        slot.high.store((value >> 64) as u64, Ordering::Relaxed);
+        slot.low.store(value as u64, Ordering::Relaxed);
+        slot.seq.store(pos.wrapping_add(1), Ordering::Release);
+
+        true
+    }
+
+    fn pop(&self) -> Option<Uuid> {
+        let mask = self.slots.len() - 1;
+        let mut pos = self.head.load(Ordering::Relaxed);
+
+        let slot = loop {
+            let slot = &self.slots[pos & mask];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let filled = pos.wrapping_add(1);
+
+            if seq == filled {
+                match self.head.compare_exchange_weak(
+                    pos,
+                    filled,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => break slot,
+                    Err(current) => pos = current,
+                }
+            } else if (seq.wrapping_sub(filled) as isize) < 0 {
+                // the slot hasn't been filled yet
+                return None;
+            } else {
+                pos = self.head.load(Ordering::Relaxed);
+            }
+        };
+
+        let high = slot.high.load(Ordering::Relaxed);
+        let low = slot.low.load(Ordering::Relaxed);
+        slot.seq
+            .store(pos.wrapping_add(self.slots.len()), Ordering::Release);
+
+        Some(Uuid::from_u128(u128::from(high) << 64 | u128::from(low)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std::collections::HashSet;
+    use crate::Version;
+
+    #[test]
+    fn test_capacity() {
+        assert_eq!(UuidPool::new(0).capacity(), 2);
+        assert_eq!(UuidPool::new(100).capacity(), 128);
+        assert_eq!(UuidPool::new(128).capacity(), 128);
+    }
+
+    #[test]
+    fn test_ring() {
+        let ring = Ring::new(4);
+
+        assert_eq!(ring.pop(), None);
+
+        for i in 0..4 {
+            assert!(ring.push(Uuid::from_u128(i)));
+        }
+        assert!(!ring.push(Uuid::from_u128(4)));
+        assert_eq!(ring.len(), 4);
+
+        // wrap around a few times
+        for i in 0..20 {
+            assert_eq!(ring.pop(), Some(Uuid::from_u128(i)));
+            assert!(ring.push(Uuid::from_u128(i + 4)));
+        }
+        assert_eq!(ring.pop(), Some(Uuid::from_u128(20)));
+        assert_eq!(ring.len(), 3);
+    }
+
+    #[test]
+    fn test_refill() {
+        let pool = UuidPool::new(100);
+
+        assert!(pool.is_empty());
+        assert_eq!(pool.try_get(), None);
+
+        assert_eq!(pool.refill().unwrap(), 128);
+        assert_eq!(pool.len(), 128);
+        assert_eq!(pool.refill().unwrap(), 0);
+
+        let uuid = pool.try_get().unwrap();
+        assert_eq!(uuid.get_version(), Some(Version::Random));
+        assert_eq!(pool.refill().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_get_refills() {
+        let pool = UuidPool::new(4);
+        let uuids =
+            (0..10).map(|_| pool.get().unwrap()).collect::<HashSet<_>>();
+
+        assert_eq!(uuids.len(), 10);
+    }
+
+    #[test]
+    fn test_concurrent() {
+        let pool = UuidPool::new(16);
+
+        let threads = (0..4)
+            .map(|_| {
+                let pool = pool.clone();
+                thread::spawn(move || {
+                    (0..1000).map(|_| pool.get().unwrap()).collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut uuids = HashSet::new();
+        for thread in threads {
+            for uuid in thread.join().unwrap() {
+                assert!(uuids.insert(uuid), "{} was handed out twice", uuid);
+            }
+        }
+
+        assert_eq!(uuids.len(), 4000);
+    }
+
+    #[test]
+    fn test_spawn_refill() {
+        let pool = UuidPool::new(8);
+        let handle = pool.spawn_refill(Duration::from_millis(1));
+
+        while pool.len() < 8 {
+            thread::yield_now();
+        }
+
+        drop(pool);
+        handle.join().unwrap();
+    }
+}
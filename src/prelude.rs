@@ -33,15 +33,24 @@
 //! [`Version`]: ../enum.Version.html
 //! [`Builder`]: ../builder/struct.Builder.html
 //!
-#![cfg_attr(feature = "v1",
-doc = "
-[`uuid::v1`]`::{`[`ClockSequence`],[`Context`]`}`: The types useful for
-handling uuid version 1. Requires feature `v1`.
+#![cfg_attr(
+    feature = "v1",
+    doc = "
+[`uuid::v1`]`::{`[`ClockSequence`],[`Context`],[`LocalContext`]`}`: The
+types useful for handling uuid version 1. Requires feature `v1`. `Context`
+is only available on targets with pointer-sized atomics.
 
 [`uuid::v1`]: ../v1/index.html
 [`Context`]: ../v1/struct.Context.html
-[`ClockSequence`]: ../v1/trait.ClockSequence.html")]
+[`LocalContext`]: ../v1/struct.LocalContext.html
+[`ClockSequence`]: ../v1/trait.ClockSequence.html"
+)]
 
+#[allow(unused_imports)]
 pub use super::{Builder, Bytes, Error, Uuid, Variant, Version};
+#[cfg(all(feature = "v1", target_has_atomic = "ptr"))]
+#[allow(unused_imports)]
+pub use crate::v1::Context;
 #[cfg(feature = "v1")]
-pub use crate::v1::{ClockSequence, Context};
+#[allow(unused_imports)]
+pub use crate::v1::{ClockSequence, LocalContext};
@@ -0,0 +1,315 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Encryption of UUIDs that are shown to third parties.
+//!
+//! Version 1 and 6 UUIDs reveal when they were created, and usually the
+//! MAC address of the host that created them. [`Cipher`] encrypts a UUID
+//! with a secret key, so it can be handed out without leaking either, and
+//! decrypts it again when it comes back.
+//!
+//! The encryption is format preserving: the result is a UUID again, with
+//! the same version and variant, so it fits into the same columns and
+//! passes the same validation. Its other bits are a keyed permutation of
+//! the original ones, so the timestamp and node ID of an encrypted UUID
+//! are meaningless, and encrypted UUIDs no longer sort by creation time.
+//!
+//! Note that you need feature `privacy` in order to use this module.
+//!
+//! [`Cipher`]: struct.Cipher.html
+
+use crate::prelude::*;
+use crate::{packed, std::fmt};
+
+/// The number of Feistel rounds.
+const ROUNDS: u8 = 8;
+
+/// The bits of a UUID, as a `u128`, that hold the version and variant.
+const FIXED_BITS: u128 = 0xf << 76 | 0b11 << 62;
+
+/// The mask of one half of the 122 encrypted bits.
+const HALF: u64 = (1 << 61) - 1;
+
+/// A key for encrypting UUIDs that are shown to third parties.
+///
+/// The same UUID always encrypts to the same UUID with the same key, so
+/// encrypted UUIDs can still be compared and used as keys. Different keys,
+/// such as one per customer, give unrelated UUIDs.
+///
+/// The permutation is an 8 round Feistel network with SipHash-2-4 as the
+/// round function. The key must be kept secret, and changing it changes
+/// every encrypted UUID.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::{privacy::Cipher, Uuid};
+///
+/// let cipher = Cipher::new(*b"an example key!!");
+/// let uuid = Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+///
+/// let public = cipher.encrypt(&uuid);
+///
+/// assert_ne!(public, uuid);
+/// assert_eq!(public.get_version_num(), 1);
+/// assert_eq!(cipher.decrypt(&public), uuid);
+/// ```
+#[derive(Clone)]
+pub struct Cipher {
+    key: (u64, u64),
+}
+
+impl Cipher {
+    /// Creates a cipher from a 128-bit secret key.
+    pub fn new(key: [u8; 16]) -> Self {
+        let mut k0 = [0; 8];
+        let mut k1 = [0; 8];
+        k0.copy_from_slice(&key[..8]);
+        k1.copy_from_slice(&key[8..]);
+
+        Cipher {
+            key: (u64::from_le_bytes(k0), u64::from_le_bytes(k1)),
+        }
+    }
+
+    /// Encrypts a UUID, keeping its version and variant.
+    pub fn encrypt(&self, uuid: &Uuid) -> Uuid {
+        let value = uuid.as_u128();
+        let mut encrypted = self.forward(value);
+
+        // the Microsoft and future variants have a third variant bit, which
+        // is kept by cycle walking until the permutation leaves it alone
+        while third_variant_bit(encrypted) != third_variant_bit(value) {
+            encrypted = self.forward(encrypted);
+        }
+
+        Uuid::from_u128(encrypted)
+    }
+
+    /// Decrypts a UUID encrypted with [`Cipher::encrypt`].
+    ///
+    /// [`Cipher::encrypt`]: #method.encrypt
+    pub fn decrypt(&self, uuid: &Uuid) -> Uuid {
+        let value = uuid.as_u128();
+        let mut decrypted = self.backward(value);
+
+        while third_variant_bit(decrypted) != third_variant_bit(value) {
+            decrypted = self.backward(decrypted);
+        }
+
+        Uuid::from_u128(decrypted)
+    }
+
+    fn forward(&self, value: u128) -> u128 {
+        let (fixed, mut left, mut right) = split(value);
+
+        for round in 0..ROUNDS {
+            let next = left ^ self.round(round, right);
+            left = right;
+            right = next;
+        }
+
+        join(fixed, left, right)
+    }
+
+    fn backward(&self, value: u128) -> u128 {
+        let (fixed, mut left, mut right) = split(value);
+
+        for round in (0..ROUNDS).rev() {
+            let previous = right ^ self.round(round, left);
+            right = left;
+            left = previous;
+        }
+
+        join(fixed, left, right)
+    }
+
+    fn round(&self, round: u8, half: u64) -> u64 {
+        siphash(self.key, u64::from(round) << 61 | half) & HALF
+    }
+}
+
+impl fmt::Debug for Cipher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Cipher").finish_non_exhaustive()
+    }
+}
+
+/// The third bit of the variant, if the first two make it part of it.
+const fn third_variant_bit(value: u128) -> u128 {
+    if value >> 62 & 0b11 == 0b11 {
+        value & 1 << 61
+    } else {
+        0
+    }
+}
+
+/// Splits a UUID into its version and variant bits, and two halves of 61
+/// bits of everything else.
+fn split(value: u128) -> (u128, u64, u64) {
+    let packed = packed::pack(value);
+
+    (
+        value & FIXED_BITS,
+        (packed >> 61) as u64,
+        packed as u64 & HALF,
+    )
+}
+
+/// The inverse of `split`.
+fn join(fixed: u128, left: u64, right: u64) -> u128 {
+    fixed | packed::unpack(u128::from(left) << 61 | u128::from(right))
+}
+
+/// SipHash-2-4 of a single 8 byte message.
+fn siphash((k0, k1): (u64, u64), message: u64) -> u64 {
+    let mut v = [
+        k0 ^ 0x736f_6d65_7073_6575,
+        k1 ^ 0x646f_7261_6e64_6f6d,
+        k0 ^ 0x6c79_6765_6e65_7261,
+        k1 ^ 0x7465_6462_7974_6573,
+    ];
+
+    // the message block, then the final block holding the length
+    for &block in &[message, 8 << 56] {
+        v[3] ^= block;
+        sip_round(&mut v);
+        sip_round(&mut v);
+        v[0] ^= block;
+    }
+
+    v[2] ^= 0xff;
+    for _ in 0..4 {
+        sip_round(&mut v);
+    }
+
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+fn sip_round(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13) ^ v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16) ^ v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21) ^ v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17) ^ v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    const KEY: [u8; 16] = *b"an example key!!";
+
+    #[test]
+    fn test_siphash() {
+        // the 8 byte vector of the reference implementation
+        assert_eq!(
+            siphash(
+                (0x0706_0504_0302_0100, 0x0f0e_0d0c_0b0a_0908),
+                0x0706_0504_0302_0100
+            ),
+            0x93f5_f579_9a93_2462
+        );
+    }
+
+    #[test]
+    fn test_split() {
+        for uuid in &[
+            test_util::new(),
+            test_util::new2(),
+            Uuid::nil(),
+            Uuid::from_u128(u128::MAX),
+        ] {
+            let (fixed, left, right) = split(uuid.as_u128());
+
+            assert!(left <= HALF && right <= HALF);
+            assert_eq!(join(fixed, left, right), uuid.as_u128());
+        }
+
+        assert_eq!(split(u128::MAX), (FIXED_BITS, HALF, HALF));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let cipher = Cipher::new(KEY);
+
+        for uuid in &[
+            test_util::new(),
+            test_util::new2(),
+            Uuid::nil(),
+            Uuid::from_u128(u128::MAX),
+            Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap(),
+            Uuid::parse_str("1e752a1f-3b49-658c-802a-010203040506").unwrap(),
+        ] {
+            let encrypted = cipher.encrypt(uuid);
+
+            assert_ne!(encrypted, *uuid);
+            assert_eq!(encrypted.get_version_num(), uuid.get_version_num());
+            assert_eq!(encrypted.as_bytes()[8] >> 6, uuid.as_bytes()[8] >> 6);
+            assert_eq!(cipher.decrypt(&encrypted), *uuid);
+        }
+    }
+
+    #[test]
+    fn test_keeps_variant() {
+        let cipher = Cipher::new(KEY);
+
+        for byte in 0..=255 {
+            let mut bytes = *test_util::new().as_bytes();
+            bytes[8] = byte;
+            let uuid = Uuid::from_bytes(bytes);
+
+            let encrypted = cipher.encrypt(&uuid);
+
+            assert_eq!(encrypted.get_variant(), uuid.get_variant());
+            assert_eq!(cipher.decrypt(&encrypted), uuid);
+        }
+    }
+
+    #[test]
+    fn test_hides_time_and_node() {
+        let cipher = Cipher::new(KEY);
+        let a =
+            Uuid::parse_str("f3b4958c-52a1-11e7-802a-010203040506").unwrap();
+        let b =
+            Uuid::parse_str("f3b4958d-52a1-11e7-802a-010203040506").unwrap();
+
+        let (a, b) = (cipher.encrypt(&a), cipher.encrypt(&b));
+
+        // neighbouring UUIDs share neither their timestamp nor their node
+        assert_ne!(a.as_bytes()[..8], b.as_bytes()[..8]);
+        assert_ne!(a.as_bytes()[10..], b.as_bytes()[10..]);
+    }
+
+    #[test]
+    fn test_keys_differ() {
+        let uuid = test_util::new();
+        let mut other = KEY;
+        other[15] ^= 1;
+
+        assert_ne!(
+            Cipher::new(KEY).encrypt(&uuid),
+            Cipher::new(other).encrypt(&uuid)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_debug_hides_key() {
+        assert_eq!(format!("{:?}", Cipher::new(KEY)), "Cipher { .. }");
+    }
+}
@@ -0,0 +1,350 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Ranges of [`Uuid`]s, for partitioning the keyspace.
+//!
+//! [`Uuid`]: ../struct.Uuid.html
+
+use crate::prelude::*;
+use crate::std::ops::RangeInclusive;
+
+/// An inclusive range of [`Uuid`]s, ordered as big-endian 128-bit numbers.
+///
+/// Both ends are included, so the whole keyspace, from [`Uuid::nil`] to
+/// [`Uuid::max`], can be represented, see [`UuidRange::full`]. A range
+/// whose `start` is greater than its `end` is empty.
+///
+/// # Examples
+///
+/// Splitting the keyspace into shards for a parallel table scan:
+///
+/// ```
+/// use uuid::{range::UuidRange, Uuid};
+///
+/// let shards: Vec<_> = UuidRange::full().split(4).collect();
+///
+/// assert_eq!(shards.len(), 4);
+/// assert_eq!(
+///     shards[1].start.to_string(),
+///     "40000000-0000-0000-0000-000000000000"
+/// );
+/// assert_eq!(
+///     shards[1].end.to_string(),
+///     "7fffffff-ffff-ffff-ffff-ffffffffffff"
+/// );
+///
+/// let uuid = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+/// assert!(shards[2].contains(&uuid));
+/// ```
+///
+/// [`Uuid`]: ../struct.Uuid.html
+/// [`Uuid::nil`]: ../struct.Uuid.html#method.nil
+/// [`Uuid::max`]: ../struct.Uuid.html#method.max
+/// [`UuidRange::full`]: #method.full
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct UuidRange {
+    /// The first UUID in the range.
+    pub start: Uuid,
+    /// The last UUID in the range.
+    pub end: Uuid,
+}
+
+impl UuidRange {
+    /// Creates the range from `start` to `end`, both included.
+    pub const fn new(start: Uuid, end: Uuid) -> Self {
+        UuidRange { start, end }
+    }
+
+    /// Creates the range of every UUID, from nil to max.
+    pub const fn full() -> Self {
+        UuidRange::new(Uuid::nil(), Uuid::max())
+    }
+
+    /// Tests if `uuid` is in the range.
+    pub fn contains(&self, uuid: &Uuid) -> bool {
+        self.start <= *uuid && *uuid <= self.end
+    }
+
+    /// Tests if the range contains no UUIDs, which is the case when
+    /// `start` is greater than `end`.
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    /// Splits the range into `n` consecutive ranges of as equal size as
+    /// possible.
+    ///
+    /// The sizes differ by at most one, with the larger ranges first.
+    /// Together the ranges cover exactly the UUIDs in this range, without
+    /// overlapping. If the range holds fewer than `n` UUIDs, there is one
+    /// range per UUID, and an empty range isn't split at all.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{range::UuidRange, Uuid};
+    ///
+    /// let range = UuidRange::new(Uuid::from_u128(0), Uuid::from_u128(9));
+    /// let sizes: Vec<_> = range
+    ///     .split(3)
+    ///     .map(|range| range.end.as_u128() - range.start.as_u128() + 1)
+    ///     .collect();
+    ///
+    /// assert_eq!(sizes, [4, 3, 3]);
+    /// ```
+    pub fn split(&self, n: usize) -> Split {
+        assert!(n > 0, "can't split a range into zero parts");
+
+        if self.is_empty() {
+            return Split {
+                start: 0,
+                step: 0,
+                larger: 0,
+                remaining: 0,
+            };
+        }
+
+        // the number of UUIDs, `end - start + 1`, overflows for the full
+        // range, so divide the distance between the ends instead
+        let n = n as u128;
+        let distance = self.end.as_u128() - self.start.as_u128();
+
+        Split {
+            start: self.start.as_u128(),
+            step: distance / n,
+            larger: distance % n + 1,
+            remaining: n,
+        }
+    }
+
+    /// Returns an iterator over the UUIDs in the range, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{range::UuidRange, Uuid};
+    ///
+    /// let range = UuidRange::new(Uuid::from_u128(1), Uuid::from_u128(3));
+    ///
+    /// assert_eq!(range.iter().count(), 3);
+    /// assert_eq!(range.iter().last(), Some(Uuid::from_u128(3)));
+    /// ```
+    pub fn iter(&self) -> Iter {
+        Iter {
+            next: self.start.as_u128(),
+            end: self.end.as_u128(),
+            done: self.is_empty(),
+        }
+    }
+}
+
+impl From<RangeInclusive<Uuid>> for UuidRange {
+    fn from(range: RangeInclusive<Uuid>) -> Self {
+        let (start, end) = range.into_inner();
+
+        UuidRange::new(start, end)
+    }
+}
+
+impl IntoIterator for UuidRange {
+    type Item = Uuid;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+/// An iterator over the ranges returned by [`UuidRange::split`].
+///
+/// [`UuidRange::split`]: struct.UuidRange.html#method.split
+#[derive(Clone, Debug)]
+pub struct Split {
+    start: u128,
+    // the larger ranges hold `step + 1` UUIDs, the others `step`
+    step: u128,
+    larger: u128,
+    remaining: u128,
+}
+
+impl Iterator for Split {
+    type Item = UuidRange;
+
+    fn next(&mut self) -> Option<UuidRange> {
+        if self.remaining == 0 || (self.larger == 0 && self.step == 0) {
+            self.remaining = 0;
+            return None;
+        }
+
+        let end = if self.larger > 0 {
+            self.larger -= 1;
+            self.start + self.step
+        } else {
+            self.start + (self.step - 1)
+        };
+        let range =
+            UuidRange::new(Uuid::from_u128(self.start), Uuid::from_u128(end));
+
+        self.start = end.wrapping_add(1);
+        self.remaining -= 1;
+
+        Some(range)
+    }
+}
+
+/// An iterator over the UUIDs in a [`UuidRange`].
+///
+/// [`UuidRange`]: struct.UuidRange.html
+#[derive(Clone, Debug)]
+pub struct Iter {
+    next: u128,
+    end: u128,
+    done: bool,
+}
+
+impl Iterator for Iter {
+    type Item = Uuid;
+
+    fn next(&mut self) -> Option<Uuid> {
+        if self.done {
+            return None;
+        }
+
+        let uuid = Uuid::from_u128(self.next);
+
+        if self.next == self.end {
+            self.done = true;
+        } else {
+            self.next += 1;
+        }
+
+        Some(uuid)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+
+        match (self.end - self.next).checked_add(1) {
+            Some(len) if len <= usize::MAX as u128 => {
+                (len as usize, Some(len as usize))
+            }
+            _ => (usize::MAX, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    fn range(start: u128, end: u128) -> UuidRange {
+        UuidRange::new(Uuid::from_u128(start), Uuid::from_u128(end))
+    }
+
+    fn check_split(range: UuidRange, n: usize) {
+        let mut parts = range.split(n);
+        let first = parts.next().unwrap();
+        assert_eq!(first.start, range.start);
+
+        let mut last = first;
+        let mut count = 1;
+        for part in parts {
+            assert!(!part.is_empty());
+            assert_eq!(part.start, last.end.wrapping_add(1));
+
+            let size = part.end.as_u128() - part.start.as_u128();
+            let last_size = last.end.as_u128() - last.start.as_u128();
+            assert!(size == last_size || size + 1 == last_size);
+
+            last = part;
+            count += 1;
+        }
+
+        assert_eq!(last.end, range.end);
+        assert!(count <= n);
+    }
+
+    #[test]
+    fn test_contains() {
+        let range = range(10, 20);
+
+        assert!(range.contains(&Uuid::from_u128(10)));
+        assert!(range.contains(&Uuid::from_u128(20)));
+        assert!(!range.contains(&Uuid::from_u128(21)));
+        assert!(!range.contains(&Uuid::from_u128(9)));
+
+        assert!(UuidRange::full().contains(&Uuid::nil()));
+        assert!(UuidRange::full().contains(&Uuid::max()));
+        assert!(UuidRange::full().contains(&test_util::new()));
+    }
+
+    #[test]
+    fn test_empty() {
+        let empty = range(2, 1);
+
+        assert!(empty.is_empty());
+        assert!(!range(1, 1).is_empty());
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty.split(3).next(), None);
+    }
+
+    #[test]
+    fn test_split() {
+        for &n in &[1, 2, 3, 7, 16, 1000] {
+            check_split(UuidRange::full(), n);
+            check_split(range(0, 9), n);
+            check_split(range(5, 5), n);
+            check_split(UuidRange::new(test_util::new(), test_util::new2()), n);
+            check_split(range(1, u128::MAX), n);
+        }
+
+        assert_eq!(range(0, 2).split(5).count(), 3);
+        assert_eq!(UuidRange::full().split(3).count(), 3);
+
+        let halves: [UuidRange; 2] = {
+            let mut split = UuidRange::full().split(2);
+            [split.next().unwrap(), split.next().unwrap()]
+        };
+        assert_eq!(halves[0].end, Uuid::from_u128(u128::MAX >> 1));
+        assert_eq!(halves[1].start, Uuid::from_u128(1 << 127));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_zero() {
+        UuidRange::full().split(0);
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut iter = range(u128::MAX - 1, u128::MAX).into_iter();
+
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.next(), Some(Uuid::from_u128(u128::MAX - 1)));
+        assert_eq!(iter.next(), Some(Uuid::max()));
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(UuidRange::full().iter().size_hint(), (usize::MAX, None));
+        assert_eq!(
+            UuidRange::from(Uuid::nil()..=Uuid::from_u128(4))
+                .iter()
+                .count(),
+            5
+        );
+    }
+}
@@ -9,10 +9,17 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use crate::adapter::{
+    Braced, BracedRef, Hyphenated, HyphenatedRef, Simple, SimpleRef, Urn,
+    UrnRef,
+};
 use crate::prelude::*;
-use core::fmt;
+use crate::typed::TypedUuid;
+use core::{fmt, marker::PhantomData, str};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
+/// Serializes the UUID as a hyphenated string for human-readable formats,
+/// and as 16 bytes otherwise.
 #[cfg(feature = "serde")]
 impl Serialize for Uuid {
     fn serialize<S: Serializer>(
@@ -21,76 +28,382 @@ impl Serialize for Uuid {
     ) -> Result<S::Ok, S::Error> {
         if serializer.is_human_readable() {
             serializer
-                .serialize_str(&self.to_hyphenated().encode_lower(&mut [0; 36]))
+                .serialize_str(self.to_hyphenated().encode_lower(&mut [0; 36]))
         } else {
             serializer.serialize_bytes(self.as_bytes())
         }
     }
 }
 
+/// Deserializes the UUID from a string or from 16 bytes.
+///
+/// Both are accepted regardless of the format, so data written by
+/// producers that disagree on the representation can be read back. Strings
+/// may be in any of the formats accepted by [`Uuid::parse_str`]. Bytes
+/// holding one of these strings are accepted as well, and so is a sequence
+/// of exactly 16 `u8` elements, as written by formats without native byte
+/// strings.
+///
+/// [`Uuid::parse_str`]: struct.Uuid.html#method.parse_str
 #[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Uuid {
     fn deserialize<D: Deserializer<'de>>(
         deserializer: D,
     ) -> Result<Self, D::Error> {
         if deserializer.is_human_readable() {
-            struct UuidStringVisitor;
-
-            impl<'vi> de::Visitor<'vi> for UuidStringVisitor {
-                type Value = Uuid;
-
-                fn expecting(
-                    &self,
-                    formatter: &mut fmt::Formatter<'_>,
-                ) -> fmt::Result {
-                    write!(formatter, "a UUID string")
-                }
-
-                fn visit_str<E: de::Error>(
-                    self,
-                    value: &str,
-                ) -> Result<Uuid, E> {
-                    value.parse::<Uuid>().map_err(E::custom)
-                }
-
-                fn visit_bytes<E: de::Error>(
-                    self,
-                    value: &[u8],
-                ) -> Result<Uuid, E> {
-                    Uuid::from_slice(value).map_err(E::custom)
-                }
+            deserializer.deserialize_any(UuidVisitor)
+        } else {
+            deserializer.deserialize_bytes(UuidVisitor)
+        }
+    }
+}
+
+struct UuidVisitor;
+
+impl<'vi> de::Visitor<'vi> for UuidVisitor {
+    type Value = Uuid;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "a UUID string or 16 bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Uuid, E> {
+        Uuid::parse_str(value).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Uuid, E> {
+        if value.len() == 16 {
+            return Uuid::from_slice(value).map_err(E::custom);
+        }
+
+        match str::from_utf8(value) {
+            Ok(value) => Uuid::parse_str(value).map_err(E::custom),
+            Err(_) => Err(E::invalid_length(value.len(), &self)),
+        }
+    }
+
+    fn visit_seq<A: de::SeqAccess<'vi>>(
+        self,
+        mut seq: A,
+    ) -> Result<Uuid, A::Error> {
+        let mut bytes = [0; 16];
+
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(17, &self));
+        }
+
+        Ok(Uuid::from_bytes(bytes))
+    }
+}
+
+fn deserialize_str<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Uuid, D::Error> {
+    deserializer.deserialize_str(UuidVisitor)
+}
+
+/// Deserializes one of the adapters from a string in exactly its format.
+struct AdapterVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<'vi, T> de::Visitor<'vi> for AdapterVisitor<T>
+where
+    T: str::FromStr<Err = crate::Error>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        value.parse().map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<T, E> {
+        match str::from_utf8(value) {
+            Ok(value) => self.visit_str(value),
+            Err(_) => {
+                Err(E::invalid_value(de::Unexpected::Bytes(value), &self))
+            }
+        }
+    }
+}
+
+macro_rules! impl_adapter_serde {
+    ($($T:ident, $Ref:ident => $expecting:expr;)+) => {$(
+        /// Serializes the UUID as a lower case string in this format, for
+        /// both human-readable and compact formats.
+        impl Serialize for $T {
+            fn serialize<S: Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(
+                    self.encode_lower(&mut Uuid::encode_buffer()),
+                )
             }
+        }
 
-            deserializer.deserialize_str(UuidStringVisitor)
-        } else {
-            struct UuidBytesVisitor;
-
-            impl<'vi> de::Visitor<'vi> for UuidBytesVisitor {
-                type Value = Uuid;
-
-                fn expecting(
-                    &self,
-                    formatter: &mut fmt::Formatter<'_>,
-                ) -> fmt::Result {
-                    write!(formatter, "bytes")
-                }
-
-                fn visit_bytes<E: de::Error>(
-                    self,
-                    value: &[u8],
-                ) -> Result<Uuid, E> {
-                    Uuid::from_slice(value).map_err(E::custom)
-                }
+        /// Serializes the UUID as a lower case string in this format, for
+        /// both human-readable and compact formats.
+        impl<'a> Serialize for $Ref<'a> {
+            fn serialize<S: Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(
+                    self.encode_lower(&mut Uuid::encode_buffer()),
+                )
             }
+        }
 
-            deserializer.deserialize_bytes(UuidBytesVisitor)
+        /// Deserializes the UUID from a string in exactly this format, in
+        /// either case.
+        impl<'de> Deserialize<'de> for $T {
+            fn deserialize<D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(AdapterVisitor {
+                    expecting: $expecting,
+                    marker: PhantomData,
+                })
+            }
         }
+    )+};
+}
+
+impl_adapter_serde! {
+    Hyphenated, HyphenatedRef => "a hyphenated UUID string";
+    Simple, SimpleRef => "a UUID string of 32 hexadecimal digits";
+    Urn, UrnRef => "a UUID URN string";
+    Braced, BracedRef => "a hyphenated UUID string enclosed in braces";
+}
+
+/// Serializes the ID exactly like the [`Uuid`] it holds.
+///
+/// [`Uuid`]: ../struct.Uuid.html
+impl<T: ?Sized> Serialize for TypedUuid<T> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.as_uuid().serialize(serializer)
+    }
+}
+
+/// Deserializes the ID from anything a [`Uuid`] can be deserialized from.
+///
+/// [`Uuid`]: ../struct.Uuid.html
+impl<'de, T: ?Sized> Deserialize<'de> for TypedUuid<T> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Uuid::deserialize(deserializer).map(TypedUuid::from_uuid)
+    }
+}
+
+/// Serializes the timestamp as a tuple of its RFC4122 ticks and counter.
+#[cfg(feature = "v1")]
+impl Serialize for crate::v1::Timestamp {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.to_rfc4122().serialize(serializer)
+    }
+}
+
+/// Deserializes the timestamp from a tuple of its RFC4122 ticks and
+/// counter.
+#[cfg(feature = "v1")]
+impl<'de> Deserialize<'de> for crate::v1::Timestamp {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let (ticks, counter) = Deserialize::deserialize(deserializer)?;
+
+        Ok(crate::v1::Timestamp::from_rfc4122(ticks, counter))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Hyphenated,
+    Simple,
+    Braced,
+    Urn,
+}
+
+fn serialize_as<S: Serializer>(
+    u: &Uuid,
+    serializer: S,
+    format: Format,
+    upper: bool,
+) -> Result<S::Ok, S::Error> {
+    macro_rules! encode {
+        ($adapter:expr, $buffer:expr) => {
+            if upper {
+                $adapter.encode_upper($buffer)
+            } else {
+                $adapter.encode_lower($buffer)
+            }
+        };
+    }
+
+    let mut buffer = Uuid::encode_buffer();
+
+    serializer.serialize_str(match format {
+        Format::Hyphenated => encode!(u.to_hyphenated_ref(), &mut buffer),
+        Format::Simple => encode!(u.to_simple_ref(), &mut buffer),
+        Format::Urn => encode!(u.to_urn_ref(), &mut buffer),
+        Format::Braced => encode!(u.to_braced_ref(), &mut buffer),
+    })
+}
+
+/// Declares the `serialize` and `deserialize` functions of a
+/// `#[serde(with = "...")]` module serializing in the given format.
+macro_rules! impl_serde_with {
+    ($format:ident, $upper:expr, $case:expr, $uuid_link:expr) => {
+        #[doc = concat!("Serializer for a [`Uuid`] into ", $case, " string.")]
+        ///
+        #[doc = concat!("[`Uuid`]: ", $uuid_link)]
+        pub fn serialize<S: Serializer>(
+            u: &Uuid,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serialize_as(u, serializer, Format::$format, $upper)
+        }
+
+        /// Deserializer from a UUID string into a [`Uuid`].
+        ///
+        #[doc = concat!("[`Uuid`]: ", $uuid_link)]
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Uuid, D::Error> {
+            deserialize_str(deserializer)
+        }
+    };
+}
+
+/// Serialize a [`Uuid`] as an upper case hyphenated string, such as
+/// `936DA01F-9ABD-4D9D-80C7-02AF85C822A8`.
+///
+/// Use with `#[serde(with = "uuid::serde::uppercase")]`. Deserialization
+/// accepts the same strings as the [`Uuid`] `Deserialize` impl.
+///
+/// [`Uuid`]: ../../struct.Uuid.html
+pub mod uppercase {
+    use super::*;
+
+    impl_serde_with!(
+        Hyphenated,
+        true,
+        "an upper case",
+        "../../struct.Uuid.html"
+    );
+}
+
+/// Serialize a [`Uuid`] as a 32 character lower case hexadecimal string, such as
+/// `936da01f9abd4d9d80c702af85c822a8`.
+///
+/// Use with `#[serde(with = "uuid::serde::simple")]`, or with
+/// `#[serde(with = "uuid::serde::simple::uppercase")]` for upper case
+/// digits. Deserialization accepts the same strings as the [`Uuid`]
+/// `Deserialize` impl.
+///
+/// [`Uuid`]: ../../struct.Uuid.html
+pub mod simple {
+    use super::*;
+
+    impl_serde_with!(Simple, false, "a lower case", "../../struct.Uuid.html");
+
+    /// Serialize a [`Uuid`] as a 32 character upper case hexadecimal string,
+    /// such as `936DA01F9ABD4D9D80C702AF85C822A8`.
+    ///
+    /// [`Uuid`]: ../../../struct.Uuid.html
+    pub mod uppercase {
+        use super::super::*;
+
+        impl_serde_with!(
+            Simple,
+            true,
+            "an upper case",
+            "../../../struct.Uuid.html"
+        );
+    }
+}
+
+/// Serialize a [`Uuid`] as a lower case hyphenated string enclosed in braces, such as
+/// `{936da01f-9abd-4d9d-80c7-02af85c822a8}`.
+///
+/// Use with `#[serde(with = "uuid::serde::braced")]`, or with
+/// `#[serde(with = "uuid::serde::braced::uppercase")]` for upper case
+/// digits. Deserialization accepts the same strings as the [`Uuid`]
+/// `Deserialize` impl.
+///
+/// [`Uuid`]: ../../struct.Uuid.html
+pub mod braced {
+    use super::*;
+
+    impl_serde_with!(Braced, false, "a lower case", "../../struct.Uuid.html");
+
+    /// Serialize a [`Uuid`] as a upper case hyphenated string enclosed in braces,
+    /// such as `{936DA01F-9ABD-4D9D-80C7-02AF85C822A8}`.
+    ///
+    /// [`Uuid`]: ../../../struct.Uuid.html
+    pub mod uppercase {
+        use super::super::*;
+
+        impl_serde_with!(
+            Braced,
+            true,
+            "an upper case",
+            "../../../struct.Uuid.html"
+        );
+    }
+}
+
+/// Serialize a [`Uuid`] as a lower case URN, such as
+/// `urn:uuid:936da01f-9abd-4d9d-80c7-02af85c822a8`.
+///
+/// Use with `#[serde(with = "uuid::serde::urn")]`, or with
+/// `#[serde(with = "uuid::serde::urn::uppercase")]` for upper case
+/// digits. Deserialization accepts the same strings as the [`Uuid`]
+/// `Deserialize` impl.
+///
+/// [`Uuid`]: ../../struct.Uuid.html
+pub mod urn {
+    use super::*;
+
+    impl_serde_with!(Urn, false, "a lower case", "../../struct.Uuid.html");
+
+    /// Serialize a [`Uuid`] as a upper case URN,
+    /// such as `urn:uuid:936DA01F-9ABD-4D9D-80C7-02AF85C822A8`.
+    ///
+    /// [`Uuid`]: ../../../struct.Uuid.html
+    pub mod uppercase {
+        use super::super::*;
+
+        impl_serde_with!(
+            Urn,
+            true,
+            "an upper case",
+            "../../../struct.Uuid.html"
+        );
     }
 }
 
 #[cfg(all(test, feature = "serde"))]
 mod serde_tests {
-    use serde_test;
 
     use crate::prelude::*;
 
@@ -117,4 +430,327 @@ mod serde_tests {
             &[serde_test::Token::Bytes(uuid_bytes)],
         );
     }
+
+    #[test]
+    fn test_bincode_is_compact() {
+        let u = crate::test_util::new();
+        let encoded = bincode::serialize(&u).unwrap();
+
+        // a `u64` length prefix followed by the raw bytes
+        assert_eq!(encoded.len(), 8 + 16);
+        assert_eq!(&encoded[8..], u.as_bytes());
+
+        assert_eq!(bincode::deserialize::<Uuid>(&encoded).unwrap(), u);
+    }
+
+    #[test]
+    fn test_json_is_readable() {
+        let u = crate::test_util::new();
+        let encoded = serde_json::to_string(&u).unwrap();
+
+        assert_eq!(encoded, "\"f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4\"");
+        assert_eq!(serde_json::from_str::<Uuid>(&encoded).unwrap(), u);
+    }
+
+    const UUID_BYTES: &[u8] = &[
+        0xf9, 0x16, 0x8c, 0x5e, 0xce, 0xb2, 0x4f, 0xaa, 0xb6, 0xbf, 0x32, 0x9b,
+        0xf3, 0x9f, 0xa1, 0xe4,
+    ];
+
+    #[test]
+    fn test_deserialize_lenient() {
+        use serde_test::{Configure, Token};
+
+        let u = crate::test_util::new();
+
+        for s in &[
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+            "F9168C5ECEB24FAAB6BF329BF39FA1E4",
+            "{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}",
+            "urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+        ] {
+            serde_test::assert_de_tokens(&u.readable(), &[Token::Str(s)]);
+            serde_test::assert_de_tokens(&u.compact(), &[Token::Str(s)]);
+            serde_test::assert_de_tokens(
+                &u.compact(),
+                &[Token::Bytes(s.as_bytes())],
+            );
+        }
+
+        serde_test::assert_de_tokens(
+            &u.readable(),
+            &[Token::BorrowedBytes(UUID_BYTES)],
+        );
+        serde_test::assert_de_tokens(
+            &u.compact(),
+            &[Token::ByteBuf(UUID_BYTES)],
+        );
+    }
+
+    #[test]
+    fn test_deserialize_lenient_invalid() {
+        use serde_test::Token;
+
+        serde_test::assert_de_tokens_error::<serde_test::Readable<Uuid>>(
+            &[Token::Bytes(&[0xff; 15])],
+            "invalid length 15, expected a UUID string or 16 bytes",
+        );
+        serde_test::assert_de_tokens_error::<serde_test::Compact<Uuid>>(
+            &[Token::Str("{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4)")],
+            "invalid length: expected one of [36, 32], found 38",
+        );
+        serde_test::assert_de_tokens_error::<serde_test::Readable<Uuid>>(
+            &[Token::U64(1)],
+            "invalid type: integer `1`, expected a UUID string or 16 bytes",
+        );
+    }
+
+    #[test]
+    fn test_deserialize_borrowed() {
+        use serde::de::value::{
+            BorrowedBytesDeserializer, BorrowedStrDeserializer, Error,
+        };
+        use serde::Deserialize;
+
+        let u = crate::test_util::new();
+
+        let de = BorrowedStrDeserializer::<Error>::new(
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4",
+        );
+        assert_eq!(Uuid::deserialize(de).unwrap(), u);
+
+        let de = BorrowedBytesDeserializer::<Error>::new(UUID_BYTES);
+        assert_eq!(Uuid::deserialize(de).unwrap(), u);
+    }
+
+    #[test]
+    fn test_deserialize_seq() {
+        use serde_test::{Configure, Token};
+
+        let u = crate::test_util::new();
+
+        let mut tokens = vec![Token::Seq { len: Some(16) }];
+        tokens.extend(UUID_BYTES.iter().map(|&b| Token::U8(b)));
+        tokens.push(Token::SeqEnd);
+
+        serde_test::assert_de_tokens(&u.readable(), &tokens);
+        serde_test::assert_de_tokens(&u.compact(), &tokens);
+
+        let json = serde_json::to_string(UUID_BYTES).unwrap();
+        assert_eq!(serde_json::from_str::<Uuid>(&json).unwrap(), u);
+    }
+
+    #[test]
+    fn test_deserialize_seq_invalid() {
+        use serde_test::Token;
+
+        let mut tokens = vec![Token::Seq { len: Some(15) }];
+        tokens.extend(UUID_BYTES[..15].iter().map(|&b| Token::U8(b)));
+        tokens.push(Token::SeqEnd);
+
+        serde_test::assert_de_tokens_error::<serde_test::Readable<Uuid>>(
+            &tokens,
+            "invalid length 15, expected a UUID string or 16 bytes",
+        );
+
+        let json = serde_json::to_string(&[0u8; 17]).unwrap();
+        assert!(serde_json::from_str::<Uuid>(&json).is_err());
+
+        let json = serde_json::to_string(&[256u16; 16]).unwrap();
+        assert!(serde_json::from_str::<Uuid>(&json).is_err());
+    }
+
+    #[test]
+    fn test_with_modules() {
+        use serde_test::{Configure, Token};
+
+        #[derive(
+            serde_derive::Serialize,
+            serde_derive::Deserialize,
+            Clone,
+            Copy,
+            Debug,
+            PartialEq,
+        )]
+        struct Formats {
+            #[serde(with = "crate::serde::simple")]
+            simple: Uuid,
+            #[serde(with = "crate::serde::braced")]
+            braced: Uuid,
+            #[serde(with = "crate::serde::urn")]
+            urn: Uuid,
+            #[serde(with = "crate::serde::compact")]
+            compact: Uuid,
+        }
+
+        let u = crate::test_util::new();
+        let formats = Formats {
+            simple: u,
+            braced: u,
+            urn: u,
+            compact: u,
+        };
+
+        let mut tokens = vec![
+            Token::Struct {
+                name: "Formats",
+                len: 4,
+            },
+            Token::Str("simple"),
+            Token::Str("f9168c5eceb24faab6bf329bf39fa1e4"),
+            Token::Str("braced"),
+            Token::Str("{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}"),
+            Token::Str("urn"),
+            Token::Str("urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4"),
+            Token::Str("compact"),
+            Token::Tuple { len: 16 },
+        ];
+        tokens.extend(UUID_BYTES.iter().map(|&b| Token::U8(b)));
+        tokens.extend(vec![Token::TupleEnd, Token::StructEnd]);
+
+        serde_test::assert_tokens(&formats.readable(), &tokens);
+        serde_test::assert_tokens(&formats.compact(), &tokens);
+
+        let json = serde_json::to_string(&formats).unwrap();
+        assert_eq!(serde_json::from_str::<Formats>(&json).unwrap(), formats);
+    }
+
+    #[test]
+    fn test_uppercase_modules() {
+        use serde_test::Token;
+
+        #[derive(
+            serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq,
+        )]
+        struct Formats {
+            #[serde(with = "crate::serde::uppercase")]
+            hyphenated: Uuid,
+            #[serde(with = "crate::serde::simple::uppercase")]
+            simple: Uuid,
+            #[serde(with = "crate::serde::braced::uppercase")]
+            braced: Uuid,
+            #[serde(with = "crate::serde::urn::uppercase")]
+            urn: Uuid,
+        }
+
+        let u = crate::test_util::new();
+
+        serde_test::assert_tokens(
+            &Formats {
+                hyphenated: u,
+                simple: u,
+                braced: u,
+                urn: u,
+            },
+            &[
+                Token::Struct {
+                    name: "Formats",
+                    len: 4,
+                },
+                Token::Str("hyphenated"),
+                Token::Str("F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4"),
+                Token::Str("simple"),
+                Token::Str("F9168C5ECEB24FAAB6BF329BF39FA1E4"),
+                Token::Str("braced"),
+                Token::Str("{F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4}"),
+                Token::Str("urn"),
+                Token::Str("urn:uuid:F9168C5E-CEB2-4FAA-B6BF-329BF39FA1E4"),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_adapters() {
+        use serde_test::{Configure, Token};
+
+        let u = crate::test_util::new();
+
+        serde_test::assert_tokens(
+            &u.to_hyphenated().compact(),
+            &[Token::Str("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4")],
+        );
+        serde_test::assert_tokens(
+            &u.to_simple().readable(),
+            &[Token::Str("f9168c5eceb24faab6bf329bf39fa1e4")],
+        );
+        serde_test::assert_tokens(
+            &u.to_urn(),
+            &[Token::Str("urn:uuid:f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4")],
+        );
+        serde_test::assert_tokens(
+            &u.to_braced(),
+            &[Token::Str("{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}")],
+        );
+        serde_test::assert_ser_tokens(
+            &u.to_braced_ref(),
+            &[Token::Str("{f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4}")],
+        );
+        serde_test::assert_de_tokens(
+            &u.to_simple(),
+            &[Token::Bytes(b"F9168C5ECEB24FAAB6BF329BF39FA1E4")],
+        );
+    }
+
+    #[test]
+    fn test_adapters_invalid() {
+        use crate::adapter::{Braced, Simple};
+        use serde_test::Token;
+
+        serde_test::assert_de_tokens_error::<Simple>(
+            &[Token::Str("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4")],
+            "invalid length: expected 32, found 36",
+        );
+        serde_test::assert_de_tokens_error::<Braced>(
+            &[Token::Bytes(&[0xff; 38])],
+            "invalid value: byte array, expected a hyphenated UUID string \
+             enclosed in braces",
+        );
+    }
+
+    #[test]
+    fn test_typed() {
+        use crate::typed::TypedUuid;
+        use serde_test::{Configure, Token};
+
+        let u = TypedUuid::<()>::from_uuid(crate::test_util::new());
+
+        serde_test::assert_tokens(
+            &u.readable(),
+            &[Token::Str("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4")],
+        );
+
+        let u = TypedUuid::<()>::from_uuid(
+            Uuid::from_slice(b"F9168C5E-CEB2-4F").unwrap(),
+        );
+        serde_test::assert_tokens(
+            &u.compact(),
+            &[Token::Bytes(b"F9168C5E-CEB2-4F")],
+        );
+    }
+
+    #[cfg(feature = "v1")]
+    #[test]
+    fn test_timestamp() {
+        use crate::v1::Timestamp;
+        use serde_test::Token;
+
+        serde_test::assert_tokens(
+            &Timestamp::from_rfc4122(137_256_576_000_000_000, 42),
+            &[
+                Token::Tuple { len: 2 },
+                Token::U64(137_256_576_000_000_000),
+                Token::U16(42),
+                Token::TupleEnd,
+            ],
+        );
+
+        let json =
+            serde_json::to_string(&Timestamp::from_rfc4122(7, 1)).unwrap();
+        assert_eq!(json, "[7,1]");
+        assert_eq!(
+            serde_json::from_str::<Timestamp>(&json).unwrap(),
+            Timestamp::from_rfc4122(7, 1)
+        );
+    }
 }
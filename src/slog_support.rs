@@ -10,7 +10,7 @@
 // except according to those terms.
 
 use crate::prelude::*;
-use slog;
+use crate::typed::TypedUuid;
 
 impl slog::Value for Uuid {
     fn serialize(
@@ -23,6 +23,17 @@ impl slog::Value for Uuid {
     }
 }
 
+impl<T: ?Sized> slog::Value for TypedUuid<T> {
+    fn serialize(
+        &self,
+        record: &slog::Record<'_>,
+        key: slog::Key,
+        serializer: &mut dyn slog::Serializer,
+    ) -> Result<(), slog::Error> {
+        slog::Value::serialize(self.as_uuid(), record, key, serializer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -35,5 +46,8 @@ mod tests {
         let root = slog::Logger::root(slog::Discard.fuse(), slog::o!());
         let u1 = test_util::new();
         crit!(root, "test"; "u1" => u1);
+
+        let typed = crate::typed::TypedUuid::<()>::from_uuid(u1);
+        crit!(root, "test"; "typed" => typed);
     }
 }
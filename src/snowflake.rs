@@ -0,0 +1,174 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::prelude::*;
+
+/// The number of bits of a Snowflake ID taken by the worker.
+const WORKER_BITS: u32 = 10;
+
+/// The number of bits of a Snowflake ID taken by the sequence.
+const SEQUENCE_BITS: u32 = 12;
+
+/// The number of bits of a Snowflake ID taken by the timestamp, including
+/// the otherwise unused sign bit.
+const TIMESTAMP_BITS: u32 = 64 - WORKER_BITS - SEQUENCE_BITS;
+
+impl Uuid {
+    /// Creates a custom (version 8) UUID from a [Snowflake ID].
+    ///
+    /// `epoch` is the Unix timestamp in milliseconds that the Snowflake
+    /// timestamps count from, such as `1288834974657` for Twitter's. The
+    /// UUID starts with the Unix timestamp of the ID in milliseconds, like
+    /// a version 7 UUID, followed by its 10-bit worker and 12-bit sequence,
+    /// so UUIDs sort in the same order as the IDs they were created from,
+    /// even across services with different epochs.
+    ///
+    /// Timestamps that don't fit into 48 bits once `epoch` is added are
+    /// truncated, which can't happen for any epoch before the year 10000.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let epoch = 1_288_834_974_657;
+    /// let id = 1_541_815_603_606_036_480;
+    ///
+    /// let uuid = Uuid::from_snowflake(id, epoch);
+    ///
+    /// assert_eq!(uuid.get_version_num(), 8);
+    /// assert_eq!(uuid.get_snowflake_parts(), Some((1_656_432_460_105, 378, 0)));
+    /// assert_eq!(uuid.to_snowflake(epoch), Some(id));
+    /// ```
+    ///
+    /// [Snowflake ID]: https://en.wikipedia.org/wiki/Snowflake_ID
+    pub const fn from_snowflake(id: u64, epoch: u64) -> Uuid {
+        let timestamp = (id >> (WORKER_BITS + SEQUENCE_BITS))
+            .wrapping_add(epoch)
+            & 0xffff_ffff_ffff;
+        let worker_and_sequence =
+            id & ((1 << (WORKER_BITS + SEQUENCE_BITS)) - 1);
+
+        Uuid::from_v8_bits(
+            (timestamp as u128) << 74 | (worker_and_sequence as u128) << 52,
+        )
+    }
+
+    /// Returns the Snowflake ID a UUID was created from with
+    /// [`Uuid::from_snowflake`], counting from `epoch`.
+    ///
+    /// Returns `None` if this isn't a custom (version 8) UUID, or if its
+    /// timestamp can't be expressed as a Snowflake ID with `epoch`.
+    ///
+    /// [`Uuid::from_snowflake`]: #method.from_snowflake
+    pub fn to_snowflake(&self, epoch: u64) -> Option<u64> {
+        let (timestamp, worker, sequence) = self.get_snowflake_parts()?;
+
+        let timestamp = timestamp.checked_sub(epoch)?;
+        if timestamp >> TIMESTAMP_BITS != 0 {
+            return None;
+        }
+
+        Some(
+            timestamp << (WORKER_BITS + SEQUENCE_BITS)
+                | u64::from(worker) << SEQUENCE_BITS
+                | u64::from(sequence),
+        )
+    }
+
+    /// Returns the Unix timestamp in milliseconds, the worker and the
+    /// sequence of the Snowflake ID a UUID was created from with
+    /// [`Uuid::from_snowflake`].
+    ///
+    /// Returns `None` if this isn't a custom (version 8) UUID.
+    ///
+    /// [`Uuid::from_snowflake`]: #method.from_snowflake
+    pub fn get_snowflake_parts(&self) -> Option<(u64, u16, u16)> {
+        let bits = self.v8_bits()?;
+
+        let timestamp = (bits >> 74) as u64;
+        let worker = (bits >> (52 + SEQUENCE_BITS)) as u16 & 0x3ff;
+        let sequence = (bits >> 52) as u16 & 0xfff;
+
+        Some((timestamp, worker, sequence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_util;
+
+    const EPOCH: u64 = 1_288_834_974_657;
+
+    fn snowflake(timestamp: u64, worker: u64, sequence: u64) -> u64 {
+        timestamp << 22 | worker << 12 | sequence
+    }
+
+    #[test]
+    fn test_snowflake_roundtrip() {
+        for &id in &[
+            0,
+            1,
+            snowflake(1, 0x3ff, 0xfff),
+            snowflake(367_597_485_448, 10, 42),
+            u64::MAX,
+        ] {
+            let uuid = Uuid::from_snowflake(id, EPOCH);
+
+            assert_eq!(uuid.get_version_num(), 8);
+            assert_eq!(uuid.get_variant(), Some(Variant::RFC4122));
+            assert_eq!(uuid.to_snowflake(EPOCH), Some(id));
+        }
+
+        let uuid = Uuid::from_snowflake(snowflake(1000, 0x155, 0xaaa), EPOCH);
+
+        assert_eq!(
+            uuid.get_snowflake_parts(),
+            Some((EPOCH + 1000, 0x155, 0xaaa))
+        );
+        assert_eq!(
+            uuid.to_snowflake(0),
+            Some(snowflake(EPOCH + 1000, 0x155, 0xaaa))
+        );
+        assert_eq!(uuid.to_snowflake(EPOCH + 1001), None);
+    }
+
+    #[test]
+    fn test_snowflake_order() {
+        let ids = [
+            snowflake(0, 0x3ff, 0xfff),
+            snowflake(1, 0, 0),
+            snowflake(1, 0, 1),
+            snowflake(1, 1, 0),
+            snowflake(0x1ff_ffff_ffff, 0, 0),
+        ];
+
+        for pair in ids.windows(2) {
+            assert!(
+                Uuid::from_snowflake(pair[0], EPOCH)
+                    < Uuid::from_snowflake(pair[1], EPOCH)
+            );
+        }
+
+        // the same instant from services with different epochs
+        assert_eq!(
+            Uuid::from_snowflake(snowflake(2000, 1, 1), 1000),
+            Uuid::from_snowflake(snowflake(1000, 1, 1), 2000)
+        );
+    }
+
+    #[test]
+    fn test_not_a_snowflake() {
+        assert_eq!(test_util::new().get_snowflake_parts(), None);
+        assert_eq!(Uuid::nil().to_snowflake(0), None);
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A [`Uuid`] that sorts by the time it was generated at.
+//!
+//! [`Uuid`]: ../struct.Uuid.html
+
+use crate::prelude::*;
+use crate::std::{cmp::Ordering, fmt};
+
+/// A [`Uuid`] that is ordered by its timestamp.
+///
+/// Version 1 UUIDs are compared by their timestamp first, then by their
+/// clock sequence and node, using [`Uuid::sort_key`]. Other UUIDs are
+/// compared by their bytes, which for version 7 UUIDs is already the
+/// order they were generated in. This makes it possible to key a
+/// `BTreeMap` or `BinaryHeap` by event time with UUIDs directly.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeSet;
+/// use uuid::{time_ordered::TimeOrdered, Uuid};
+///
+/// let earlier = Uuid::parse_str("ffffffff-0000-11e7-8000-010203040506").unwrap();
+/// let later = Uuid::parse_str("00000000-0001-11e7-8000-010203040506").unwrap();
+///
+/// let events: BTreeSet<_> =
+///     vec![TimeOrdered(later), TimeOrdered(earlier)].into_iter().collect();
+///
+/// assert_eq!(events.iter().next(), Some(&TimeOrdered(earlier)));
+/// ```
+///
+/// [`Uuid`]: ../struct.Uuid.html
+/// [`Uuid::sort_key`]: ../struct.Uuid.html#method.sort_key
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct TimeOrdered(pub Uuid);
+
+impl PartialOrd for TimeOrdered {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TimeOrdered {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.sort_key().cmp(&other.0.sort_key())
+    }
+}
+
+impl From<Uuid> for TimeOrdered {
+    fn from(uuid: Uuid) -> Self {
+        TimeOrdered(uuid)
+    }
+}
+
+impl From<TimeOrdered> for Uuid {
+    fn from(uuid: TimeOrdered) -> Self {
+        uuid.0
+    }
+}
+
+impl fmt::Debug for TimeOrdered {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for TimeOrdered {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    fn parse(s: &str) -> TimeOrdered {
+        TimeOrdered(Uuid::parse_str(s).unwrap())
+    }
+
+    #[test]
+    fn test_v1_by_time() {
+        let earlier = parse("ffffffff-0000-11e7-8000-010203040506");
+        let later = parse("00000000-0001-11e7-8000-010203040506");
+        let latest = parse("00000000-0000-11e8-8000-010203040506");
+
+        assert!(earlier < later);
+        assert!(later < latest);
+        assert!(earlier.0 > later.0);
+
+        // same time, ordered by clock sequence
+        let next = parse("ffffffff-0000-11e7-8001-010203040506");
+        assert!(earlier < next && next < later);
+    }
+
+    #[test]
+    fn test_other_by_bytes() {
+        let a = TimeOrdered(test_util::new());
+        let b = TimeOrdered(test_util::new2());
+
+        assert_eq!(a.cmp(&b), a.0.cmp(&b.0));
+        assert!(TimeOrdered(Uuid::nil()) < a);
+        assert!(a < TimeOrdered(Uuid::max()));
+
+        let v7_earlier = parse("017f22e2-79b0-7cc3-98c4-dc0c0c07398f");
+        let v7_later = parse("017f22e2-79b1-7000-8000-000000000000");
+        assert!(v7_earlier < v7_later);
+    }
+
+    #[test]
+    fn test_eq() {
+        let uuid = test_util::new();
+
+        assert_eq!(TimeOrdered::from(uuid), TimeOrdered(uuid));
+        assert_eq!(Uuid::from(TimeOrdered(uuid)), uuid);
+        assert_eq!(TimeOrdered(uuid).cmp(&TimeOrdered(uuid)), Ordering::Equal);
+    }
+}
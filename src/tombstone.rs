@@ -0,0 +1,97 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::prelude::*;
+
+/// The mask mixed into a UUID to derive its tombstone.
+///
+/// This is part of the tombstone format: changing it changes every
+/// tombstone ID.
+const TOMBSTONE_MASK: Bytes = [
+    0x74, 0x6f, 0x6d, 0x62, 0x73, 0x74, 0x6f, 0x6e, 0x65, 0x3a, 0x75, 0x75,
+    0x69, 0x64, 0x3a, 0x38,
+];
+
+impl Uuid {
+    /// Returns the tombstone ID paired with this UUID.
+    ///
+    /// A tombstone is a shadow ID that stands in for a record after it has
+    /// been soft-deleted. It is a custom (version 8) UUID derived from the
+    /// original by a fixed transformation, so it can be computed from the
+    /// original ID at any time without being stored alongside it.
+    ///
+    /// The version and variant bits of the original are overwritten, so
+    /// UUIDs that only differ in those bits share a tombstone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let id = Uuid::parse_str("936DA01F9ABD4d9d80C702AF85C822A8").unwrap();
+    /// let tombstone = id.tombstone_of();
+    ///
+    /// assert_eq!(tombstone.get_version_num(), 8);
+    /// assert_eq!(tombstone, id.tombstone_of());
+    /// assert!(tombstone.is_tombstone_of(&id));
+    /// ```
+    pub fn tombstone_of(&self) -> Uuid {
+        let mut bytes = *self.as_bytes();
+
+        for (byte, mask) in bytes.iter_mut().zip(TOMBSTONE_MASK.iter()) {
+            *byte ^= mask;
+        }
+
+        Builder::from_bytes(bytes)
+            .set_variant(Variant::RFC4122)
+            .set_version(Version::Custom)
+            .build()
+    }
+
+    /// Tests if this UUID is the tombstone ID paired with `original`.
+    ///
+    /// See [`Uuid::tombstone_of`] for details.
+    ///
+    /// [`Uuid::tombstone_of`]: struct.Uuid.html#method.tombstone_of
+    pub fn is_tombstone_of(&self, original: &Uuid) -> bool {
+        *self == original.tombstone_of()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_tombstone_of() {
+        let uuid = test_util::new();
+        let tombstone = uuid.tombstone_of();
+
+        assert_ne!(tombstone, uuid);
+        assert_eq!(tombstone, uuid.tombstone_of());
+        assert_eq!(tombstone.get_version(), Some(Version::Custom));
+        assert_eq!(tombstone.get_variant(), Some(Variant::RFC4122));
+
+        assert_ne!(tombstone, test_util::new2().tombstone_of());
+        assert_ne!(Uuid::nil().tombstone_of(), Uuid::nil());
+    }
+
+    #[test]
+    fn test_is_tombstone_of() {
+        let uuid = test_util::new();
+
+        assert!(uuid.tombstone_of().is_tombstone_of(&uuid));
+        assert!(!uuid.is_tombstone_of(&uuid));
+        assert!(!uuid.tombstone_of().is_tombstone_of(&test_util::new2()));
+        assert!(!uuid.is_tombstone_of(&uuid.tombstone_of()));
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A [`Uuid`] tagged with the kind of thing it identifies.
+//!
+//! [`Uuid`]: ../struct.Uuid.html
+
+use crate::prelude::*;
+use crate::std::{cmp::Ordering, fmt, hash, marker::PhantomData, str};
+
+/// A [`Uuid`] that identifies a `T`.
+///
+/// `TypedUuid<T>` has the same size and representation as a `Uuid`, but
+/// IDs of different types can't be mixed up: a function taking a
+/// `TypedUuid<User>` won't accept a `TypedUuid<Order>`. `T` is only used
+/// as a marker, so it doesn't need to implement any traits, and can be an
+/// uninhabited type.
+///
+/// It formats, parses, hashes and compares like the `Uuid` it holds, and
+/// is serialized in the same way when the `serde` feature is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::{typed::TypedUuid, Uuid};
+///
+/// enum User {}
+/// enum Order {}
+///
+/// type UserId = TypedUuid<User>;
+/// type OrderId = TypedUuid<Order>;
+///
+/// fn orders_of(user: UserId) -> Vec<OrderId> {
+///     # let _ = user;
+///     vec![OrderId::from_uuid(Uuid::nil())]
+/// }
+///
+/// let user: UserId = "936da01f-9abd-4d9d-80c7-02af85c822a8".parse().unwrap();
+///
+/// assert_eq!(user.to_string(), "936da01f-9abd-4d9d-80c7-02af85c822a8");
+/// assert_eq!(orders_of(user).len(), 1);
+/// ```
+///
+/// [`Uuid`]: ../struct.Uuid.html
+#[repr(transparent)]
+pub struct TypedUuid<T: ?Sized> {
+    uuid: Uuid,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: ?Sized> TypedUuid<T> {
+    /// Tags `uuid` as the ID of a `T`.
+    pub const fn from_uuid(uuid: Uuid) -> Self {
+        TypedUuid {
+            uuid,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the untyped [`Uuid`].
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    pub const fn as_uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+
+    /// Returns the untyped [`Uuid`].
+    ///
+    /// [`Uuid`]: ../struct.Uuid.html
+    pub const fn into_uuid(self) -> Uuid {
+        self.uuid
+    }
+
+    /// Creates the nil ID of a `T`.
+    pub const fn nil() -> Self {
+        TypedUuid::from_uuid(Uuid::nil())
+    }
+
+    /// Parses the ID of a `T` from any of the formats accepted by
+    /// [`Uuid::parse_str`].
+    ///
+    /// [`Uuid::parse_str`]: ../struct.Uuid.html#method.parse_str
+    pub fn parse_str(input: &str) -> Result<Self, crate::Error> {
+        Uuid::parse_str(input).map(TypedUuid::from_uuid)
+    }
+
+    /// Returns the 16 bytes of the ID.
+    pub const fn as_bytes(&self) -> &Bytes {
+        self.uuid.as_bytes()
+    }
+}
+
+// `T` is only a marker, so these are implemented by hand rather than
+// derived, which would require `T` to implement them as well.
+
+impl<T: ?Sized> Clone for TypedUuid<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for TypedUuid<T> {}
+
+impl<T: ?Sized> PartialEq for TypedUuid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.uuid == other.uuid
+    }
+}
+
+impl<T: ?Sized> Eq for TypedUuid<T> {}
+
+impl<T: ?Sized> PartialOrd for TypedUuid<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized> Ord for TypedUuid<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.uuid.cmp(&other.uuid)
+    }
+}
+
+impl<T: ?Sized> hash::Hash for TypedUuid<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.uuid.hash(state)
+    }
+}
+
+impl<T: ?Sized> Default for TypedUuid<T> {
+    fn default() -> Self {
+        TypedUuid::nil()
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for TypedUuid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.uuid, f)
+    }
+}
+
+impl<T: ?Sized> fmt::Display for TypedUuid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.uuid, f)
+    }
+}
+
+impl<T: ?Sized> fmt::LowerHex for TypedUuid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.uuid, f)
+    }
+}
+
+impl<T: ?Sized> fmt::UpperHex for TypedUuid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.uuid, f)
+    }
+}
+
+impl<T: ?Sized> str::FromStr for TypedUuid<T> {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TypedUuid::parse_str(s)
+    }
+}
+
+impl<T: ?Sized> AsRef<Uuid> for TypedUuid<T> {
+    fn as_ref(&self) -> &Uuid {
+        &self.uuid
+    }
+}
+
+impl<T: ?Sized> From<Uuid> for TypedUuid<T> {
+    fn from(uuid: Uuid) -> Self {
+        TypedUuid::from_uuid(uuid)
+    }
+}
+
+impl<T: ?Sized> From<TypedUuid<T>> for Uuid {
+    fn from(uuid: TypedUuid<T>) -> Self {
+        uuid.uuid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::std::mem;
+    use crate::test_util;
+
+    // not `Clone`, `Eq` or `Debug`
+    struct User;
+
+    enum Order {}
+
+    #[test]
+    fn test_typed() {
+        let uuid = test_util::new();
+        let id = TypedUuid::<User>::from_uuid(uuid);
+
+        assert_eq!(id.into_uuid(), uuid);
+        assert_eq!(*id.as_uuid(), uuid);
+        assert_eq!(id.as_bytes(), uuid.as_bytes());
+        assert_eq!(Uuid::from(id), uuid);
+        assert_eq!(TypedUuid::<User>::from(uuid), id);
+
+        let copy = id;
+        assert_eq!(copy, id);
+        assert!(TypedUuid::<Order>::nil() < TypedUuid::from_uuid(uuid));
+        assert_eq!(TypedUuid::<str>::default().into_uuid(), Uuid::nil());
+    }
+
+    #[test]
+    fn test_parse() {
+        let uuid = test_util::new();
+        let id: TypedUuid<User> =
+            "f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4".parse().unwrap();
+
+        assert_eq!(id.into_uuid(), uuid);
+        assert_eq!(
+            TypedUuid::<User>::parse_str("f9168c5eceb24faab6bf329bf39fa1e4"),
+            Ok(id)
+        );
+        assert!(TypedUuid::<User>::parse_str("f9168c5e").is_err());
+    }
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(mem::size_of::<TypedUuid<User>>(), 16);
+        assert_eq!(mem::align_of::<TypedUuid<User>>(), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fmt() {
+        use crate::std::string::ToString;
+
+        let uuid = test_util::new();
+        let id = TypedUuid::<User>::from_uuid(uuid);
+
+        assert_eq!(id.to_string(), uuid.to_string());
+        assert_eq!(format!("{:?}", id), format!("{:?}", uuid));
+        assert_eq!(format!("{:#}", id), format!("{:#}", uuid));
+        assert_eq!(format!("{:X}", id), format!("{:X}", uuid));
+    }
+}
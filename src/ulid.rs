@@ -0,0 +1,126 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::prelude::*;
+
+impl Uuid {
+    /// Creates a UUID from the 128-bit value of a [ULID].
+    ///
+    /// The conversion is bit for bit, so the 48-bit millisecond timestamp
+    /// of the ULID ends up in the first 6 bytes of the UUID, and the UUID
+    /// sorts the same as the ULID. The result usually has neither a
+    /// version nor a variant.
+    ///
+    /// Note that usage of this method requires the `ulid` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let ulid = 0x0172_5d6c_2e31_a0f6_53b8_0df6_3c21_9f64;
+    /// let uuid = Uuid::from_ulid(ulid);
+    ///
+    /// assert_eq!(uuid.to_ulid(), ulid);
+    /// assert_eq!(uuid.get_ulid_timestamp(), 0x0172_5d6c_2e31);
+    /// ```
+    ///
+    /// [ULID]: https://github.com/ulid/spec
+    pub const fn from_ulid(ulid: u128) -> Uuid {
+        Uuid::from_u128(ulid)
+    }
+
+    /// Parses a UUID from the 26 character textual form of a ULID.
+    ///
+    /// This is the same as [`Uuid::from_base32`]. Use
+    /// [`Uuid::encode_base32`] to format a UUID as a ULID string.
+    ///
+    /// Note that usage of this method requires the `ulid` feature of this
+    /// crate to be enabled.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `input` isn't a valid ULID
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let uuid = Uuid::from_ulid_str("01BX5ZZKBKACTAV9WEVGEMMVRZ").unwrap();
+    ///
+    /// assert_eq!(
+    ///     uuid.to_hyphenated().to_string(),
+    ///     "015f4bff-cd73-5334-ada7-8edc1d4a6f1f"
+    /// );
+    /// assert_eq!(
+    ///     uuid.encode_base32(&mut [0; 26]),
+    ///     "01BX5ZZKBKACTAV9WEVGEMMVRZ"
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::from_base32`]: #method.from_base32
+    /// [`Uuid::encode_base32`]: #method.encode_base32
+    pub fn from_ulid_str(input: &str) -> Result<Uuid, crate::Error> {
+        Uuid::from_base32(input)
+    }
+
+    /// Returns the 128-bit value of the UUID as a ULID.
+    ///
+    /// This is the inverse of [`Uuid::from_ulid`].
+    ///
+    /// Note that usage of this method requires the `ulid` feature of this
+    /// crate to be enabled.
+    ///
+    /// [`Uuid::from_ulid`]: #method.from_ulid
+    pub fn to_ulid(&self) -> u128 {
+        self.as_u128()
+    }
+
+    /// Returns the millisecond Unix timestamp of the UUID read as a ULID,
+    /// which is stored in its first 48 bits.
+    ///
+    /// Note that usage of this method requires the `ulid` feature of this
+    /// crate to be enabled.
+    pub fn get_ulid_timestamp(&self) -> u64 {
+        (self.as_u128() >> 80) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_ulid_roundtrip() {
+        let uuid = test_util::new();
+
+        assert_eq!(Uuid::from_ulid(uuid.to_ulid()), uuid);
+        assert_eq!(
+            Uuid::from_ulid_str(uuid.encode_base32(&mut [0; 26])),
+            Ok(uuid)
+        );
+        assert_eq!(uuid.get_ulid_timestamp(), 0xf916_8c5e_ceb2);
+    }
+
+    #[test]
+    fn test_ulid_str() {
+        // from the ULID specification
+        let uuid = Uuid::from_ulid_str("01ARZ3NDEKTSV4RRFFQ69G5FAV").unwrap();
+
+        assert_eq!(uuid.get_ulid_timestamp(), 1_469_922_850_259);
+        assert!(Uuid::from_ulid_str("01ARZ3NDEKTSV4RRFFQ69G5FA").is_err());
+        assert!(Uuid::from_ulid_str("81ARZ3NDEKTSV4RRFFQ69G5FAV").is_err());
+    }
+}
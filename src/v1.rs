@@ -2,20 +2,88 @@
 //!
 //! Note that you need feature `v1` in order to use these features.
 
+use crate::entropy::EntropySource;
 use crate::prelude::*;
+use crate::std::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    ops::{Add, AddAssign, Sub, SubAssign},
+    time::Duration,
+};
+#[cfg(any(target_has_atomic = "ptr", target_has_atomic = "16"))]
 use core::sync::atomic;
 
-/// The number of 100 ns ticks between the UUID epoch
-/// `1582-10-15 00:00:00` and the Unix epoch `1970-01-01 00:00:00`.
-const UUID_TICKS_BETWEEN_EPOCHS: u64 = 0x01B2_1DD2_1381_4000;
+use crate::info::UUID_TICKS_BETWEEN_EPOCHS;
 
 /// A thread-safe, stateful context for the v1 generator to help ensure
 /// process-wide uniqueness.
+///
+/// This type is only available on targets that support atomic operations.
+/// Use [`LocalContext`] on other targets.
+///
+/// [`LocalContext`]: struct.LocalContext.html
+#[cfg(target_has_atomic = "ptr")]
 #[derive(Debug)]
 pub struct Context {
     count: atomic::AtomicUsize,
 }
 
+/// A single-threaded, stateful context for the v1 generator.
+///
+/// `LocalContext` works like [`Context`], but keeps its counter in a
+/// [`Cell`] instead of an atomic, so it is available on targets without
+/// atomic operations, such as `thumbv6m` and AVR microcontrollers. It can't
+/// be shared between threads.
+///
+/// [`Context`]: struct.Context.html
+/// [`Cell`]: https://doc.rust-lang.org/core/cell/struct.Cell.html
+#[derive(Debug)]
+pub struct LocalContext {
+    count: Cell<u16>,
+}
+
+/// A single-threaded context for the v1 generator that owns a fixed share of
+/// the clock sequence space.
+///
+/// The 14-bit clock sequence is split into `shards` ranges of equal size,
+/// and each context counts through its own range. Giving every thread a
+/// context for a different shard keeps their UUIDs apart without sharing
+/// a counter between them, so generating a UUID doesn't touch memory that
+/// other threads write to, unlike with [`Context`].
+///
+/// Each shard has fewer clock sequences than a whole context, so a thread
+/// can generate at most `16384 / shards` UUIDs with the same timestamp
+/// before they repeat.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use uuid::v1::{ShardedContext, Timestamp};
+/// use uuid::Uuid;
+///
+/// let threads = ShardedContext::partition(4)
+///     .map(|context| {
+///         thread::spawn(move || {
+///             let ts = Timestamp::from_unix(&context, 1497624119, 1234);
+///             Uuid::new_v1(ts, &[1, 2, 3, 4, 5, 6]).expect("failed to generate UUID")
+///         })
+///     })
+///     .collect::<Vec<_>>();
+///
+/// for thread in threads {
+///     thread.join().unwrap();
+/// }
+/// ```
+///
+/// [`Context`]: struct.Context.html
+#[derive(Debug)]
+pub struct ShardedContext {
+    start: u16,
+    len: u16,
+    count: Cell<u16>,
+}
+
 /// Stores the number of nanoseconds from an epoch and a counter for ensuring
 /// V1 ids generated on the same host are unique.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -25,10 +93,14 @@ pub struct Timestamp {
 }
 
 impl Timestamp {
+    /// The largest number of ticks that fits into the 60-bit timestamp of a
+    /// UUID, which is early in the year 5236.
+    pub const MAX_TICKS: u64 = (1 << 60) - 1;
+
     /// Construct a `Timestamp` from its raw component values: an RFC4122
     /// timestamp and counter.
     ///
-    /// RFC4122, which defines the V1 UUID, specifies a 60-byte timestamp format
+    /// RFC4122, which defines the V1 UUID, specifies a 60-bit timestamp format
     /// as the number of 100-nanosecond intervals elapsed since 00:00:00.00,
     /// 15 Oct 1582, "the date of the Gregorian reform of the Christian
     /// calendar."
@@ -39,13 +111,48 @@ impl Timestamp {
     /// counter generators that might be used.
     ///
     /// Internally, the timestamp is stored as a `u64`. For this reason, dates
-    /// prior to October 1582 are not supported.
+    /// prior to October 1582 are not supported. Ticks above
+    /// [`Timestamp::MAX_TICKS`] don't fit into a UUID, use
+    /// [`Timestamp::try_from_rfc4122`] to reject them.
     ///
     /// [`ClockSequence`]: trait.ClockSequence.html
+    /// [`Timestamp::MAX_TICKS`]: #associatedconstant.MAX_TICKS
+    /// [`Timestamp::try_from_rfc4122`]: #method.try_from_rfc4122
     pub const fn from_rfc4122(ticks: u64, counter: u16) -> Self {
         Timestamp { ticks, counter }
     }
 
+    /// Construct a `Timestamp` from an RFC4122 timestamp and counter, like
+    /// [`Timestamp::from_rfc4122`], checking that the timestamp fits into
+    /// the 60 bits of a UUID.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `ticks` is greater than
+    /// [`Timestamp::MAX_TICKS`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::v1::Timestamp;
+    ///
+    /// assert!(Timestamp::try_from_rfc4122(Timestamp::MAX_TICKS, 0).is_ok());
+    /// assert!(Timestamp::try_from_rfc4122(Timestamp::MAX_TICKS + 1, 0).is_err());
+    /// ```
+    ///
+    /// [`Timestamp::from_rfc4122`]: #method.from_rfc4122
+    /// [`Timestamp::MAX_TICKS`]: #associatedconstant.MAX_TICKS
+    pub const fn try_from_rfc4122(
+        ticks: u64,
+        counter: u16,
+    ) -> Result<Self, crate::Error> {
+        if ticks > Timestamp::MAX_TICKS {
+            return Err(crate::Error::timestamp_out_of_range(ticks));
+        }
+
+        Ok(Timestamp { ticks, counter })
+    }
+
     /// Construct a `Timestamp` from a unix timestamp and sequence-generating
     /// `context`.
     ///
@@ -69,15 +176,19 @@ impl Timestamp {
     /// If uniqueness and monotonicity is required, the user is responsible for
     /// ensuring that the time value always increases between calls (including
     /// between restarts of the process and device).
+    ///
+    /// Times too far in the future to be counted in a `u64` saturate to
+    /// `u64::MAX` ticks instead of overflowing.
     pub fn from_unix(
         context: impl ClockSequence,
         seconds: u64,
         subsec_nanos: u32,
     ) -> Self {
         let counter = context.generate_sequence(seconds, subsec_nanos);
-        let ticks = UUID_TICKS_BETWEEN_EPOCHS
-            + seconds * 10_000_000
-            + u64::from(subsec_nanos) / 100;
+        let ticks = seconds
+            .saturating_mul(10_000_000)
+            .saturating_add(UUID_TICKS_BETWEEN_EPOCHS)
+            .saturating_add(u64::from(subsec_nanos) / 100);
 
         Timestamp { ticks, counter }
     }
@@ -99,12 +210,48 @@ impl Timestamp {
     /// Internally, the time is stored in 100-nanosecond intervals,
     /// thus the maximum precision represented by the fractional nanoseconds
     /// value is less than its unit size (100 ns vs. 1 ns).
+    ///
+    /// Times before Jan 1 1970 can't be represented, and are returned as
+    /// `(0, 0)`. Use [`Timestamp::checked_to_unix`] to tell them apart from
+    /// the epoch itself.
+    ///
+    /// [`Timestamp::checked_to_unix`]: #method.checked_to_unix
     pub const fn to_unix(&self) -> (u64, u32) {
-        (
-            (self.ticks - UUID_TICKS_BETWEEN_EPOCHS) / 10_000_000,
-            ((self.ticks - UUID_TICKS_BETWEEN_EPOCHS) % 10_000_000) as u32
-                * 100,
-        )
+        let ticks = self.ticks.saturating_sub(UUID_TICKS_BETWEEN_EPOCHS);
+
+        (ticks / 10_000_000, (ticks % 10_000_000) as u32 * 100)
+    }
+
+    /// Returns the timestamp converted to the seconds and fractional
+    /// nanoseconds since Jan 1 1970, or `None` if it's before then.
+    ///
+    /// This is the same as [`Timestamp::to_unix`], except for times before
+    /// Jan 1 1970, which version 1 and 6 UUIDs can hold. It matches
+    /// [`UuidInfo::unix_timestamp`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::v1::Timestamp;
+    ///
+    /// let ts = Timestamp::from_rfc4122(0x01B2_1DD2_1381_4000 + 12_340_000, 0);
+    /// assert_eq!(ts.checked_to_unix(), Some((1, 234_000_000)));
+    ///
+    /// // 15 Oct 1582
+    /// let ts = Timestamp::from_rfc4122(0, 0);
+    /// assert_eq!(ts.checked_to_unix(), None);
+    /// assert_eq!(ts.to_unix(), (0, 0));
+    /// ```
+    ///
+    /// [`Timestamp::to_unix`]: #method.to_unix
+    /// [`UuidInfo::unix_timestamp`]: ../info/struct.UuidInfo.html#method.unix_timestamp
+    pub const fn checked_to_unix(&self) -> Option<(u64, u32)> {
+        match self.ticks.checked_sub(UUID_TICKS_BETWEEN_EPOCHS) {
+            Some(ticks) => {
+                Some((ticks / 10_000_000, (ticks % 10_000_000) as u32 * 100))
+            }
+            None => None,
+        }
     }
 
     /// Returns the timestamp converted into nanoseconds elapsed since Jan 1
@@ -113,8 +260,116 @@ impl Timestamp {
     /// measured in (100 ns vs. 1 ns). The value returned represents the
     /// same duration as [`Timestamp::to_unix`]; this provides it in nanosecond
     /// units for convenience.
+    ///
+    /// Times before Jan 1 1970 are returned as `0`, and times after the
+    /// year 2554, which don't fit into a `u64` of nanoseconds, as
+    /// `u64::MAX`. Use [`Timestamp::checked_to_unix_nanos`] to tell them
+    /// apart.
+    ///
+    /// [`Timestamp::checked_to_unix_nanos`]: #method.checked_to_unix_nanos
     pub const fn to_unix_nanos(&self) -> u64 {
-        (self.ticks - UUID_TICKS_BETWEEN_EPOCHS) * 100
+        self.ticks
+            .saturating_sub(UUID_TICKS_BETWEEN_EPOCHS)
+            .saturating_mul(100)
+    }
+
+    /// Returns the timestamp converted into nanoseconds elapsed since Jan 1
+    /// 1970, or `None` if it's before then or doesn't fit into a `u64`.
+    ///
+    /// See [`Timestamp::to_unix_nanos`] for the saturating version.
+    ///
+    /// [`Timestamp::to_unix_nanos`]: #method.to_unix_nanos
+    pub const fn checked_to_unix_nanos(&self) -> Option<u64> {
+        match self.ticks.checked_sub(UUID_TICKS_BETWEEN_EPOCHS) {
+            Some(ticks) => ticks.checked_mul(100),
+            None => None,
+        }
+    }
+}
+
+/// Returns the number of whole 100-nanosecond intervals in `duration`.
+fn duration_to_ticks(duration: Duration) -> u64 {
+    duration
+        .as_secs()
+        .saturating_mul(10_000_000)
+        .saturating_add(u64::from(duration.subsec_nanos() / 100))
+}
+
+/// Moves the timestamp later by a duration, keeping its counter.
+///
+/// The duration is truncated to whole 100-nanosecond intervals, and the
+/// result saturates at `u64::MAX` ticks, just like [`Timestamp::from_unix`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use uuid::v1::Timestamp;
+///
+/// let ts = Timestamp::from_rfc4122(1_000, 7);
+///
+/// assert_eq!(
+///     (ts + Duration::from_nanos(1_250)).to_rfc4122(),
+///     (1_012, 7)
+/// );
+/// assert_eq!(
+///     (ts + Duration::from_nanos(1_250)) - ts,
+///     Duration::from_nanos(1_200)
+/// );
+/// ```
+///
+/// [`Timestamp::from_unix`]: struct.Timestamp.html#method.from_unix
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, duration: Duration) -> Timestamp {
+        Timestamp {
+            ticks: self.ticks.saturating_add(duration_to_ticks(duration)),
+            counter: self.counter,
+        }
+    }
+}
+
+impl AddAssign<Duration> for Timestamp {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+/// Moves the timestamp earlier by a duration, keeping its counter.
+///
+/// The duration is truncated to whole 100-nanosecond intervals, and the
+/// result saturates at the start of the Gregorian calendar.
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, duration: Duration) -> Timestamp {
+        Timestamp {
+            ticks: self.ticks.saturating_sub(duration_to_ticks(duration)),
+            counter: self.counter,
+        }
+    }
+}
+
+impl SubAssign<Duration> for Timestamp {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+/// Returns the time between two timestamps, ignoring their counters.
+///
+/// Like subtracting [`Instant`]s, this saturates at zero if `earlier` is
+/// actually later.
+///
+/// [`Instant`]: https://doc.rust-lang.org/std/time/struct.Instant.html
+impl Sub<Timestamp> for Timestamp {
+    type Output = Duration;
+
+    fn sub(self, earlier: Timestamp) -> Duration {
+        let ticks = self.ticks.saturating_sub(earlier.ticks);
+
+        Duration::new(ticks / 10_000_000, (ticks % 10_000_000) as u32 * 100)
     }
 }
 
@@ -126,12 +381,143 @@ pub trait ClockSequence {
     fn generate_sequence(&self, seconds: u64, subsec_nanos: u32) -> u16;
 }
 
-impl<'a, T: ClockSequence + ?Sized> ClockSequence for &'a T {
+impl<T: ClockSequence + ?Sized> ClockSequence for &T {
     fn generate_sequence(&self, seconds: u64, subsec_nanos: u32) -> u16 {
         (**self).generate_sequence(seconds, subsec_nanos)
     }
 }
 
+/// Uses the counter as the clock sequence, incrementing it at every
+/// request, just like [`Context`].
+///
+/// This is useful to keep the counter in a `static` with other state, or
+/// to read it back, such as for checkpointing.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicU16, Ordering};
+/// use uuid::v1::Timestamp;
+///
+/// static COUNTER: AtomicU16 = AtomicU16::new(42);
+///
+/// let ts = Timestamp::from_unix(&COUNTER, 1497624119, 1234);
+///
+/// assert_eq!(ts.to_rfc4122().1, 42);
+/// assert_eq!(COUNTER.load(Ordering::Relaxed), 43);
+/// ```
+///
+/// [`Context`]: struct.Context.html
+#[cfg(target_has_atomic = "16")]
+impl ClockSequence for atomic::AtomicU16 {
+    fn generate_sequence(&self, _: u64, _: u32) -> u16 {
+        self.fetch_add(1, atomic::Ordering::Relaxed)
+    }
+}
+
+/// A clock sequence generated by a closure, created with [`from_fn`].
+///
+/// [`from_fn`]: fn.from_fn.html
+#[derive(Clone, Copy, Debug)]
+pub struct FromFn<F>(F);
+
+/// Creates a clock sequence that calls `f` with the seconds and fractional
+/// nanoseconds of the timestamp.
+///
+/// Closures can't implement [`ClockSequence`] directly, as that would
+/// overlap with the implementation for references.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::v1::{self, Timestamp};
+///
+/// // the fractional milliseconds as the clock sequence
+/// let context = v1::from_fn(|_, nanos| (nanos % 1_000_000 / 100) as u16);
+/// let ts = Timestamp::from_unix(&context, 1497624119, 1_234_500);
+///
+/// assert_eq!(ts.to_rfc4122().1, 2345);
+/// ```
+///
+/// [`ClockSequence`]: trait.ClockSequence.html
+pub fn from_fn<F: Fn(u64, u32) -> u16>(f: F) -> FromFn<F> {
+    FromFn(f)
+}
+
+impl<F: Fn(u64, u32) -> u16> ClockSequence for FromFn<F> {
+    fn generate_sequence(&self, seconds: u64, subsec_nanos: u32) -> u16 {
+        (self.0)(seconds, subsec_nanos)
+    }
+}
+
+/// A single-threaded clock sequence that's random for every request.
+///
+/// RFC4122 allows random clock sequences for generators that don't keep
+/// state, such as ones on separate hosts with the same node ID. Unlike the
+/// counters of [`Context`], they are only unique with high probability.
+///
+/// If `source` fails to provide random bytes, the previous clock sequence
+/// plus one is used instead.
+///
+/// # Examples
+///
+/// ```
+/// use uuid::entropy::EntropySource;
+/// use uuid::v1::{RandomClockSequence, Timestamp};
+///
+/// struct Trng;
+///
+/// impl EntropySource for Trng {
+///     type Error = ();
+///
+///     fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ()> {
+///         // read from the hardware RNG peripheral here
+/// #       for b in dest.iter_mut() { *b = 0x42; }
+///         Ok(())
+///     }
+/// }
+///
+/// let context = RandomClockSequence::new(Trng);
+/// let ts = Timestamp::from_unix(&context, 1497624119, 1234);
+/// # assert_eq!(ts.to_rfc4122().1, 0x4242);
+/// ```
+///
+/// [`Context`]: struct.Context.html
+#[derive(Debug)]
+pub struct RandomClockSequence<S> {
+    source: RefCell<S>,
+    last: Cell<u16>,
+}
+
+impl<S: EntropySource> RandomClockSequence<S> {
+    /// Creates a clock sequence that reads random bytes from `source`.
+    pub const fn new(source: S) -> Self {
+        RandomClockSequence {
+            source: RefCell::new(source),
+            last: Cell::new(0),
+        }
+    }
+
+    /// Returns the source of random bytes.
+    pub fn into_inner(self) -> S {
+        self.source.into_inner()
+    }
+}
+
+impl<S: EntropySource> ClockSequence for RandomClockSequence<S> {
+    fn generate_sequence(&self, _: u64, _: u32) -> u16 {
+        let mut bytes = [0; 2];
+
+        let sequence = match self.source.borrow_mut().fill_bytes(&mut bytes) {
+            Ok(()) => u16::from_be_bytes(bytes),
+            Err(_) => self.last.get().wrapping_add(1),
+        };
+        self.last.set(sequence);
+
+        sequence
+    }
+}
+
 impl Uuid {
     /// Create a new UUID (version 1) using a time value + sequence +
     /// *NodeId*.
@@ -150,6 +536,14 @@ impl Uuid {
     ///
     /// The NodeID must be exactly 6 bytes long.
     ///
+    /// # Errors
+    ///
+    /// This function will return an error if `node_id` isn't 6 bytes long,
+    /// or if the timestamp has more ticks than fit into a UUID, see
+    /// [`Timestamp::MAX_TICKS`]. Those can come from
+    /// [`Timestamp::from_rfc4122`], or from [`Timestamp::from_unix`] for
+    /// times after the year 5236.
+    ///
     /// Note that usage of this method requires the `v1` feature of this crate
     /// to be enabled.
     ///
@@ -191,6 +585,9 @@ impl Uuid {
     /// [`Timestamp`]: v1/struct.Timestamp.html
     /// [`ClockSequence`]: v1/struct.ClockSequence.html
     /// [`Context`]: v1/struct.Context.html
+    /// [`Timestamp::MAX_TICKS`]: v1/struct.Timestamp.html#associatedconstant.MAX_TICKS
+    /// [`Timestamp::from_rfc4122`]: v1/struct.Timestamp.html#method.from_rfc4122
+    /// [`Timestamp::from_unix`]: v1/struct.Timestamp.html#method.from_unix
     pub fn new_v1(ts: Timestamp, node_id: &[u8]) -> Result<Self, crate::Error> {
         const NODE_ID_LEN: usize = 6;
 
@@ -199,10 +596,13 @@ impl Uuid {
             Err(crate::builder::Error::new(NODE_ID_LEN, len))?;
         }
 
+        if ts.ticks > Timestamp::MAX_TICKS {
+            return Err(crate::Error::timestamp_out_of_range(ts.ticks));
+        }
+
         let time_low = (ts.ticks & 0xFFFF_FFFF) as u32;
         let time_mid = ((ts.ticks >> 32) & 0xFFFF) as u16;
-        let time_high_and_version =
-            (((ts.ticks >> 48) & 0x0FFF) as u16) | (1 << 12);
+        let time_high_and_version = ((ts.ticks >> 48) as u16) | (1 << 12);
 
         let mut d4 = [0; 8];
 
@@ -252,8 +652,40 @@ impl Uuid {
 
         Some(Timestamp::from_rfc4122(ticks, counter))
     }
+
+    /// Compares two V1 UUIDs by their timestamps, and then by their
+    /// counters.
+    ///
+    /// This is the order the UUIDs were generated in, unlike the order of
+    /// their bytes, which starts with the fastest changing part of the
+    /// timestamp. The node IDs aren't compared, so UUIDs from different
+    /// nodes with the same timestamp and counter compare equal.
+    ///
+    /// Returns `None` if either UUID is not V1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use uuid::Uuid;
+    ///
+    /// let earlier = Uuid::parse_str("ffffffff-0000-11e7-8000-010203040506").unwrap();
+    /// let later = Uuid::parse_str("00000000-0001-11e7-8000-010203040506").unwrap();
+    ///
+    /// assert_eq!(earlier.timestamp_cmp(&later), Some(Ordering::Less));
+    /// assert_eq!(earlier.cmp(&later), Ordering::Greater);
+    ///
+    /// assert_eq!(earlier.timestamp_cmp(&Uuid::nil()), None);
+    /// ```
+    pub fn timestamp_cmp(&self, other: &Uuid) -> Option<Ordering> {
+        let a = self.to_timestamp()?;
+        let b = other.to_timestamp()?;
+
+        Some(a.to_rfc4122().cmp(&b.to_rfc4122()))
+    }
 }
 
+#[cfg(target_has_atomic = "ptr")]
 impl Context {
     /// Creates a thread-safe, internally mutable context to help ensure
     /// uniqueness.
@@ -268,14 +700,335 @@ impl Context {
             count: atomic::AtomicUsize::new(count as usize),
         }
     }
+
+    /// Returns the clock sequence the context will hand out next.
+    ///
+    /// Other threads may use the context at the same time, so this is only
+    /// exact while no UUIDs are being generated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::v1::{Context, Timestamp};
+    ///
+    /// let context = Context::new(42);
+    /// let _ = Timestamp::from_unix(&context, 1497624119, 1234);
+    ///
+    /// assert_eq!(context.current(), 43);
+    /// ```
+    pub fn current(&self) -> u16 {
+        (self.count.load(atomic::Ordering::SeqCst) & 0xffff) as u16
+    }
+
+    /// Sets the clock sequence the context will hand out next, such as
+    /// one saved with [`Context::current`] before a restart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::v1::{Context, Timestamp};
+    ///
+    /// let context = Context::new(0);
+    /// context.set(1000);
+    ///
+    /// let ts = Timestamp::from_unix(&context, 1497624119, 1234);
+    ///
+    /// assert_eq!(ts.to_rfc4122().1, 1000);
+    /// ```
+    ///
+    /// [`Context::current`]: #method.current
+    pub fn set(&self, count: u16) {
+        self.count.store(count as usize, atomic::Ordering::SeqCst);
+    }
 }
 
+#[cfg(target_has_atomic = "ptr")]
 impl ClockSequence for Context {
     fn generate_sequence(&self, _: u64, _: u32) -> u16 {
         (self.count.fetch_add(1, atomic::Ordering::SeqCst) & 0xffff) as u16
     }
 }
 
+impl LocalContext {
+    /// Creates a single-threaded context to help ensure uniqueness.
+    ///
+    /// The counter is incremented at every request, and ends up in the
+    /// clock_seq portion of the UUID (the fourth group).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::v1::{LocalContext, Timestamp};
+    /// use uuid::Uuid;
+    ///
+    /// let context = LocalContext::new(42);
+    /// let ts = Timestamp::from_unix(&context, 1497624119, 1234);
+    /// let uuid = Uuid::new_v1(ts, &[1, 2, 3, 4, 5, 6]).expect("failed to generate UUID");
+    ///
+    /// assert_eq!(
+    ///     uuid.to_hyphenated().to_string(),
+    ///     "f3b4958c-52a1-11e7-802a-010203040506"
+    /// );
+    /// ```
+    pub const fn new(count: u16) -> Self {
+        Self {
+            count: Cell::new(count),
+        }
+    }
+}
+
+impl ClockSequence for LocalContext {
+    fn generate_sequence(&self, _: u64, _: u32) -> u16 {
+        let count = self.count.get();
+        self.count.set(count.wrapping_add(1));
+
+        count
+    }
+}
+
+impl ShardedContext {
+    /// The number of distinct clock sequences, which are 14 bits.
+    const SEQUENCES: u32 = 1 << 14;
+
+    /// Creates a context for shard `shard` out of `shards`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is 0 or more than 16384, or if `shard` isn't less
+    /// than `shards`.
+    pub fn new(shard: u16, shards: u16) -> Self {
+        assert!(
+            shards > 0 && u32::from(shards) <= Self::SEQUENCES,
+            "the number of shards must be between 1 and 16384"
+        );
+        assert!(
+            shard < shards,
+            "the shard must be less than the number of shards"
+        );
+
+        let bound = |shard: u16| {
+            (u32::from(shard) * Self::SEQUENCES / u32::from(shards)) as u16
+        };
+        let start = bound(shard);
+
+        ShardedContext {
+            start,
+            len: bound(shard + 1) - start,
+            count: Cell::new(0),
+        }
+    }
+
+    /// Returns a context for each of `shards` shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is 0 or more than 16384.
+    pub fn partition(shards: u16) -> impl Iterator<Item = Self> {
+        // checked here, since the iterator only builds contexts lazily
+        assert!(
+            shards > 0 && u32::from(shards) <= Self::SEQUENCES,
+            "the number of shards must be between 1 and 16384"
+        );
+
+        (0..shards).map(move |shard| ShardedContext::new(shard, shards))
+    }
+}
+
+impl ClockSequence for ShardedContext {
+    fn generate_sequence(&self, _: u64, _: u32) -> u16 {
+        let count = self.count.get();
+        self.count
+            .set(if count + 1 == self.len { 0 } else { count + 1 });
+
+        self.start + count
+    }
+}
+
+#[cfg(all(feature = "std", unix, not(feature = "no-unsafe")))]
+pub use self::file_context::FileContext;
+
+#[cfg(all(feature = "std", unix, not(feature = "no-unsafe")))]
+mod file_context {
+    use super::*;
+    use crate::std::{
+        fs::{File, OpenOptions},
+        io,
+        os::unix::io::AsRawFd,
+        path::Path,
+        ptr,
+        sync::Mutex,
+    };
+
+    /// The size of the state file: the last RFC4122 timestamp and the last
+    /// clock sequence, both little-endian.
+    const STATE_LEN: usize = 10;
+
+    /// A context for the v1 generator that is shared by all processes on a
+    /// host through a small memory-mapped state file.
+    ///
+    /// The file records the last timestamp and clock sequence handed out.
+    /// Each request takes an advisory lock on the file, so processes (and
+    /// threads) sharing it never produce the same clock sequence for the
+    /// same timestamp, even if the system clock moves backwards. The clock
+    /// sequence is only changed when the timestamp hasn't advanced since
+    /// the last request.
+    ///
+    /// Note that the lock is advisory: every process generating v1 UUIDs
+    /// with the same node ID must use a `FileContext` for the same path.
+    ///
+    /// Note that usage of this type requires the `v1` and `std` features of
+    /// this crate to be enabled, and is only available on Unix platforms
+    /// without the `no-unsafe` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use uuid::v1::{FileContext, Timestamp};
+    /// use uuid::Uuid;
+    ///
+    /// let context = FileContext::open("/var/lib/myapp/uuid-v1.state")?;
+    /// let ts = Timestamp::from_unix(&context, 1497624119, 1234);
+    /// let uuid = Uuid::new_v1(ts, &[1, 2, 3, 4, 5, 6]).expect("failed to generate UUID");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[derive(Debug)]
+    pub struct FileContext {
+        file: File,
+        state: Mutex<Mapping>,
+    }
+
+    /// The shared mapping of the state file.
+    #[derive(Debug)]
+    struct Mapping(*mut [u8; STATE_LEN]);
+
+    // the mapping is only accessed through the `Mutex` in `FileContext`
+    unsafe impl Send for Mapping {}
+
+    impl Drop for Mapping {
+        fn drop(&mut self) {
+            unsafe {
+                libc::munmap(self.0.cast(), STATE_LEN);
+            }
+        }
+    }
+
+    impl FileContext {
+        /// Opens the state file at `path`, creating it if it doesn't exist.
+        pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(path)?;
+
+            // a new file reads as zeroes once it's extended, which is the
+            // same as a timestamp and clock sequence of 0
+            if file.metadata()?.len() < STATE_LEN as u64 {
+                file.set_len(STATE_LEN as u64)?;
+            }
+
+            let ptr = unsafe {
+                libc::mmap(
+                    ptr::null_mut(),
+                    STATE_LEN,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_SHARED,
+                    file.as_raw_fd(),
+                    0,
+                )
+            };
+            if ptr == libc::MAP_FAILED {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(FileContext {
+                file,
+                state: Mutex::new(Mapping(ptr.cast())),
+            })
+        }
+
+        /// Returns the clock sequence for a timestamp, updating the state
+        /// file.
+        ///
+        /// This is the fallible version of
+        /// [`ClockSequence::generate_sequence`].
+        ///
+        /// [`ClockSequence::generate_sequence`]: trait.ClockSequence.html#tymethod.generate_sequence
+        pub fn try_generate_sequence(
+            &self,
+            seconds: u64,
+            subsec_nanos: u32,
+        ) -> io::Result<u16> {
+            let ticks = seconds
+                .saturating_mul(10_000_000)
+                .saturating_add(UUID_TICKS_BETWEEN_EPOCHS)
+                .saturating_add(u64::from(subsec_nanos) / 100);
+
+            // the file lock belongs to the open file, so it doesn't keep
+            // out other threads using the same `FileContext`
+            let mapping =
+                self.state.lock().unwrap_or_else(|err| err.into_inner());
+
+            flock(&self.file, libc::LOCK_EX)?;
+            let counter = unsafe {
+                let mut state = ptr::read_volatile(mapping.0);
+                let counter = update_state(&mut state, ticks);
+                ptr::write_volatile(mapping.0, state);
+
+                counter
+            };
+            flock(&self.file, libc::LOCK_UN)?;
+
+            Ok(counter)
+        }
+    }
+
+    impl ClockSequence for FileContext {
+        /// # Panics
+        ///
+        /// Panics if the state file can't be locked. Use
+        /// [`FileContext::try_generate_sequence`] to handle these errors.
+        ///
+        /// [`FileContext::try_generate_sequence`]: struct.FileContext.html#method.try_generate_sequence
+        fn generate_sequence(&self, seconds: u64, subsec_nanos: u32) -> u16 {
+            self.try_generate_sequence(seconds, subsec_nanos)
+                .expect("failed to lock the v1 context state file")
+        }
+    }
+
+    fn flock(file: &File, operation: libc::c_int) -> io::Result<()> {
+        loop {
+            if unsafe { libc::flock(file.as_raw_fd(), operation) } == 0 {
+                return Ok(());
+            }
+
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+
+    fn update_state(state: &mut [u8; STATE_LEN], ticks: u64) -> u16 {
+        let mut last_ticks = [0; 8];
+        last_ticks.copy_from_slice(&state[..8]);
+
+        let last_ticks = u64::from_le_bytes(last_ticks);
+        let mut counter = u16::from_le_bytes([state[8], state[9]]);
+
+        if ticks <= last_ticks {
+            // the clock sequence is 14 bits in a v1 UUID
+            counter = counter.wrapping_add(1) & 0x3FFF;
+        }
+
+        state[..8].copy_from_slice(&ticks.max(last_ticks).to_le_bytes());
+        state[8..].copy_from_slice(&counter.to_le_bytes());
+
+        counter
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,4 +1076,246 @@ mod tests {
             assert_eq!(uuid2.to_timestamp().unwrap().to_rfc4122().1, 1)
         };
     }
+
+    #[test]
+    fn test_timestamp_saturates() {
+        let context = Context::new(0);
+
+        let ts = Timestamp::from_unix(&context, u64::MAX, 999_999_999);
+        assert_eq!(ts.to_rfc4122().0, u64::MAX);
+        assert_eq!(ts.to_unix_nanos(), u64::MAX);
+        assert_eq!(ts.checked_to_unix_nanos(), None);
+        assert!(ts.checked_to_unix().is_some());
+        assert_eq!(
+            Uuid::new_v1(ts, &[1, 2, 3, 4, 5, 6]).unwrap_err().kind(),
+            crate::ErrorKind::TimestampOutOfRange { ticks: u64::MAX }
+        );
+
+        let ts = Timestamp::from_rfc4122(0, 0);
+        assert_eq!(ts.to_unix(), (0, 0));
+        assert_eq!(ts.to_unix_nanos(), 0);
+        assert_eq!(ts.checked_to_unix(), None);
+        assert_eq!(ts.checked_to_unix_nanos(), None);
+
+        // the earliest time a version 1 UUID can hold
+        let uuid = Uuid::parse_str("00000000-0000-1000-8000-010203040506")
+            .expect("failed to parse UUID");
+        assert_eq!(uuid.to_timestamp().unwrap().to_unix(), (0, 0));
+        assert_eq!(uuid.to_timestamp().unwrap().checked_to_unix(), None);
+        assert_eq!(
+            uuid.to_timestamp().unwrap().checked_to_unix(),
+            uuid.describe().unix_timestamp()
+        );
+
+        let ts = Timestamp::from_rfc4122(UUID_TICKS_BETWEEN_EPOCHS, 0);
+        assert_eq!(ts.checked_to_unix(), Some((0, 0)));
+        assert_eq!(ts.checked_to_unix_nanos(), Some(0));
+    }
+
+    #[test]
+    fn test_try_from_rfc4122() {
+        let ts = Timestamp::try_from_rfc4122(Timestamp::MAX_TICKS, 7).unwrap();
+        assert_eq!(ts.to_rfc4122(), (Timestamp::MAX_TICKS, 7));
+        assert_eq!(
+            Uuid::new_v1(ts, &[1, 2, 3, 4, 5, 6]),
+            Uuid::parse_str("ffffffff-ffff-1fff-8007-010203040506")
+        );
+
+        let err = Timestamp::try_from_rfc4122(u64::MAX, 7).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            crate::ErrorKind::TimestampOutOfRange { ticks: u64::MAX }
+        );
+    }
+
+    #[test]
+    fn test_timestamp_arithmetic() {
+        let mut ts = Timestamp::from_rfc4122(UUID_TICKS_BETWEEN_EPOCHS, 3);
+
+        ts += Duration::new(1497624119, 1234);
+        assert_eq!(ts.to_unix(), (1497624119, 1200));
+        assert_eq!(ts.to_rfc4122().1, 3);
+
+        ts -= Duration::from_nanos(200);
+        assert_eq!(ts.to_unix(), (1497624119, 1000));
+        assert_eq!(
+            ts - Timestamp::from_rfc4122(UUID_TICKS_BETWEEN_EPOCHS, 0),
+            Duration::new(1497624119, 1000)
+        );
+
+        // saturates at both ends
+        assert_eq!(Timestamp::from_rfc4122(0, 0) - ts, Duration::from_secs(0));
+        assert_eq!((ts - Duration::MAX).to_rfc4122(), (0, 3));
+        assert_eq!((ts + Duration::MAX).to_rfc4122(), (u64::MAX, 3));
+    }
+
+    #[test]
+    fn test_timestamp_cmp() {
+        let node = [1, 2, 3, 4, 5, 6];
+        let new = |ticks, counter| {
+            Uuid::new_v1(Timestamp::from_rfc4122(ticks, counter), &node)
+                .unwrap()
+        };
+
+        let mut uuids = [
+            new(0x0001_0000_0000, 0),
+            new(0xffff_ffff, 5),
+            new(0xffff_ffff, 1),
+            new(0x0001_0000_0001, 0),
+        ];
+        uuids.sort_by(|a, b| a.timestamp_cmp(b).unwrap());
+
+        assert_eq!(
+            uuids
+                .iter()
+                .map(|u| u.to_timestamp().unwrap().to_rfc4122())
+                .collect::<crate::std::vec::Vec<_>>(),
+            [
+                (0xffff_ffff, 1),
+                (0xffff_ffff, 5),
+                (0x0001_0000_0000, 0),
+                (0x0001_0000_0001, 0),
+            ]
+        );
+
+        let other_node =
+            Uuid::new_v1(Timestamp::from_rfc4122(7, 7), &[9; 6]).unwrap();
+        assert_eq!(new(7, 7).timestamp_cmp(&other_node), Some(Ordering::Equal));
+        assert_eq!(new(7, 7).timestamp_cmp(&Uuid::nil()), None);
+        assert_eq!(Uuid::max().timestamp_cmp(&new(7, 7)), None);
+    }
+
+    #[test]
+    fn test_context_current_and_set() {
+        let context = Context::new(u16::MAX);
+
+        assert_eq!(context.current(), u16::MAX);
+        assert_eq!(context.generate_sequence(0, 0), u16::MAX);
+        assert_eq!(context.current(), 0);
+
+        context.set(7);
+        assert_eq!(context.current(), 7);
+        assert_eq!(context.generate_sequence(0, 0), 7);
+        assert_eq!(context.current(), 8);
+    }
+
+    #[test]
+    fn test_local_context() {
+        let context = LocalContext::new(u16::MAX);
+
+        assert_eq!(context.generate_sequence(0, 0), u16::MAX);
+        assert_eq!(context.generate_sequence(0, 0), 0);
+        assert_eq!(context.generate_sequence(0, 0), 1);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let context =
+            from_fn(|seconds, nanos| (seconds as u16) ^ (nanos as u16));
+
+        assert_eq!(context.generate_sequence(0x0f0f, 0xffff), 0xf0f0);
+        assert_eq!(Timestamp::from_unix(context, 3, 5).to_rfc4122().1, 6);
+    }
+
+    #[cfg(target_has_atomic = "16")]
+    #[test]
+    fn test_atomic_u16() {
+        let counter = atomic::AtomicU16::new(u16::MAX);
+
+        assert_eq!(counter.generate_sequence(0, 0), u16::MAX);
+        assert_eq!(Timestamp::from_unix(&counter, 0, 0).to_rfc4122().1, 0);
+        assert_eq!(counter.into_inner(), 1);
+    }
+
+    #[test]
+    fn test_random_clock_sequence() {
+        struct Flaky(u8);
+
+        impl EntropySource for Flaky {
+            type Error = ();
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ()> {
+                self.0 += 1;
+                if self.0 == 2 {
+                    return Err(());
+                }
+
+                for byte in dest {
+                    *byte = self.0;
+                }
+                Ok(())
+            }
+        }
+
+        let context = RandomClockSequence::new(Flaky(0));
+
+        assert_eq!(context.generate_sequence(0, 0), 0x0101);
+        assert_eq!(context.generate_sequence(0, 0), 0x0102);
+        assert_eq!(context.generate_sequence(0, 0), 0x0303);
+        assert_eq!(context.into_inner().0, 3);
+    }
+
+    #[test]
+    fn test_sharded_context() {
+        let shards = ShardedContext::partition(3)
+            .map(|context| (context.start, context.len))
+            .collect::<crate::std::vec::Vec<_>>();
+        assert_eq!(shards, [(0, 5461), (5461, 5461), (10922, 5462)]);
+
+        let context = ShardedContext::new(1, 3);
+        assert_eq!(context.generate_sequence(0, 0), 5461);
+        assert_eq!(context.generate_sequence(0, 0), 5462);
+        for _ in 2..5461 {
+            context.generate_sequence(0, 0);
+        }
+        assert_eq!(context.generate_sequence(0, 0), 5461);
+
+        let whole = ShardedContext::new(0, 1);
+        whole.count.set(0x3fff);
+        assert_eq!(whole.generate_sequence(0, 0), 0x3fff);
+        assert_eq!(whole.generate_sequence(0, 0), 0);
+
+        let last = ShardedContext::new(16383, 16384);
+        assert_eq!(last.generate_sequence(0, 0), 16383);
+        assert_eq!(last.generate_sequence(0, 0), 16383);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sharded_context_no_shards() {
+        let _ = ShardedContext::partition(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sharded_context_too_many_shards() {
+        let _ = ShardedContext::partition(16385);
+    }
+
+    #[cfg(all(feature = "std", unix, not(feature = "no-unsafe")))]
+    #[test]
+    fn test_file_context() {
+        use crate::std::{env, fs, process};
+
+        let path = env::temp_dir()
+            .join(format!("uuid-test-file-context-{}.state", process::id()));
+        let _ = fs::remove_file(&path);
+
+        {
+            let a = FileContext::open(&path).unwrap();
+            let b = FileContext::open(&path).unwrap();
+
+            assert_eq!(a.generate_sequence(1_496_854_535, 0), 0);
+            assert_eq!(b.generate_sequence(1_496_854_535, 0), 1);
+            assert_eq!(a.generate_sequence(1_496_854_536, 0), 1);
+
+            // the clock moved backwards
+            assert_eq!(b.generate_sequence(1_496_854_535, 0), 2);
+        }
+
+        let c = FileContext::open(&path).unwrap();
+        assert_eq!(c.generate_sequence(1_496_854_536, 0), 3);
+
+        fs::remove_file(&path).unwrap();
+    }
 }
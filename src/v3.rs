@@ -1,5 +1,5 @@
+use crate::namespace::Namespace;
 use crate::prelude::*;
-use md5;
 
 impl Uuid {
     /// Creates a UUID using a name from a namespace, based on the MD5
@@ -12,6 +12,10 @@ impl Uuid {
     /// * [`NAMESPACE_URL`]
     /// * [`NAMESPACE_X500`]
     ///
+    /// To declare your own, or to keep namespaces apart from other UUIDs
+    /// in the type system, see [`Namespace`]. To declare a UUID derived
+    /// from a fixed name as a constant, see [`Uuid::new_v3_const`].
+    ///
     /// Note that usage of this method requires the `v3` feature of this crate
     /// to be enabled.
     ///
@@ -19,22 +23,65 @@ impl Uuid {
     /// [`NAMESPACE_OID`]: #associatedconstant.NAMESPACE_OID
     /// [`NAMESPACE_URL`]: #associatedconstant.NAMESPACE_URL
     /// [`NAMESPACE_X500`]: #associatedconstant.NAMESPACE_X500
+    /// [`Namespace`]: namespace/struct.Namespace.html
+    /// [`Uuid::new_v3_const`]: #method.new_v3_const
     pub fn new_v3(namespace: &Uuid, name: &[u8]) -> Uuid {
-        let mut context = md5::Context::new();
-
-        context.consume(namespace.as_bytes());
-        context.consume(name);
-
-        let computed = context.compute();
-        let bytes = computed.into();
-
-        let mut builder = crate::builder::Builder::from_bytes(bytes);
+        Uuid::new_v3_with(md5::Context::new(), namespace, name)
+    }
 
-        builder
-            .set_variant(Variant::RFC4122)
-            .set_version(Version::Md5);
+    /// Creates a UUID using a name from a namespace, based on the MD5
+    /// hash, in a `const fn`.
+    ///
+    /// This gives the same UUID as [`Uuid::new_v3`], so UUIDs derived from
+    /// fixed names can be declared as constants. It's slower, so prefer
+    /// [`Uuid::new_v3`] outside of constants.
+    ///
+    /// Note that usage of this method requires the `v3` feature of this crate
+    /// to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// const RUST_LANG: Uuid =
+    ///     Uuid::new_v3_const(&Uuid::NAMESPACE_DNS, b"rust-lang.org");
+    ///
+    /// assert_eq!(RUST_LANG.to_string(), "c6db027c-615c-3b4d-959e-1a917747ca5a");
+    /// ```
+    ///
+    /// [`Uuid::new_v3`]: #method.new_v3
+    pub const fn new_v3_const(namespace: &Uuid, name: &[u8]) -> Uuid {
+        Builder::from_bytes(crate::const_hash::md5(namespace.as_bytes(), name))
+            .with_variant(Variant::RFC4122)
+            .with_version(Version::Md5)
+            .into_uuid()
+    }
+}
 
-        builder.build()
+impl Namespace {
+    /// Creates a UUID using a name from this namespace, based on the MD5
+    /// hash.
+    ///
+    /// This is the same as [`Uuid::new_v3`] with the namespace's UUID.
+    ///
+    /// Note that usage of this method requires the `v3` feature of this crate
+    /// to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{namespace::Namespace, Uuid};
+    ///
+    /// assert_eq!(
+    ///     Namespace::DNS.uuid_v3(b"rust-lang.org"),
+    ///     Uuid::new_v3(&Uuid::NAMESPACE_DNS, b"rust-lang.org"),
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::new_v3`]: ../struct.Uuid.html#method.new_v3
+    pub fn uuid_v3(&self, name: &[u8]) -> Uuid {
+        Uuid::new_v3(self.as_uuid(), name)
     }
 }
 
@@ -44,7 +91,7 @@ mod tests {
 
     use crate::std::string::ToString;
 
-    static FIXTURE: &'static [(&'static Uuid, &'static str, &'static str)] = &[
+    static FIXTURE: &[(&Uuid, &str, &str)] = &[
         (
             &Uuid::NAMESPACE_DNS,
             "example.org",
@@ -129,8 +176,8 @@ mod tests {
 
     #[test]
     fn test_new() {
-        for &(ref ns, ref name, _) in FIXTURE {
-            let uuid = Uuid::new_v3(*ns, name.as_bytes());
+        for &(ns, name, _) in FIXTURE {
+            let uuid = Uuid::new_v3(ns, name.as_bytes());
             assert_eq!(uuid.get_version().unwrap(), Version::Md5);
             assert_eq!(uuid.get_variant().unwrap(), Variant::RFC4122);
         }
@@ -138,9 +185,35 @@ mod tests {
 
     #[test]
     fn test_to_hyphenated_string() {
-        for &(ref ns, ref name, ref expected) in FIXTURE {
-            let uuid = Uuid::new_v3(*ns, name.as_bytes());
-            assert_eq!(uuid.to_hyphenated().to_string(), *expected);
+        for &(ns, name, expected) in FIXTURE {
+            let uuid = Uuid::new_v3(ns, name.as_bytes());
+            assert_eq!(uuid.to_hyphenated().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_namespace() {
+        for &(ns, name, expected) in FIXTURE {
+            let uuid = Namespace::new(*ns).uuid_v3(name.as_bytes());
+            assert_eq!(uuid.to_hyphenated().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_const() {
+        const UUID: Uuid =
+            Uuid::new_v3_const(&Uuid::NAMESPACE_URL, b"lorem ipsum");
+
+        assert_eq!(
+            UUID.to_hyphenated().to_string(),
+            "e55ad2e6-fb89-34e8-b012-c5dde3cd67f0"
+        );
+
+        for &(ns, name, _) in FIXTURE {
+            assert_eq!(
+                Uuid::new_v3_const(ns, name.as_bytes()),
+                Uuid::new_v3(ns, name.as_bytes())
+            );
         }
     }
 }
@@ -5,8 +5,9 @@ impl Uuid {
     ///
     /// This uses the [`getrandom`] crate to utilise the operating system's RNG
     /// as the source of random numbers. If you'd like to use a custom generator,
-    /// don't use this method: generate random bytes using your custom generator
-    /// and pass them to the [`uuid::Builder::from_bytes`][from_bytes] function
+    /// don't use this method: use [`Uuid::new_v4_from`] with your generator,
+    /// or generate random bytes and pass them to the
+    /// [`uuid::Builder::from_random_bytes`][from_random_bytes] function
     /// instead.
     ///
     /// Note that usage of this method requires the `v4` feature of this crate
@@ -23,17 +24,50 @@ impl Uuid {
     /// ```
     ///
     /// [`getrandom`]: https://crates.io/crates/getrandom
-    /// [from_bytes]: struct.Builder.html#method.from_bytes
+    /// [`Uuid::new_v4_from`]: struct.Uuid.html#method.new_v4_from
+    /// [from_random_bytes]: struct.Builder.html#method.from_random_bytes
     // TODO: change signature to support uuid's Error.
     pub fn new_v4() -> Result<Uuid, getrandom::Error> {
         let mut bytes = [0u8; 16];
         getrandom::getrandom(&mut bytes)?;
 
-        let uuid = crate::builder::Builder::from_bytes(bytes)
-            .set_variant(Variant::RFC4122)
-            .set_version(Version::Random)
-            .build();
-        Ok(uuid)
+        Ok(crate::builder::Builder::from_random_bytes(bytes).build())
+    }
+
+    /// Creates a random UUID whose last six bytes are the given Unix
+    /// timestamp in milliseconds, which SQL Server sorts by first.
+    ///
+    /// See [`Builder::from_comb_bytes`] for the layout. This uses the
+    /// [`getrandom`] crate for the random part, just like
+    /// [`Uuid::new_v4`].
+    ///
+    /// Note that usage of this method requires the `v4` feature of this crate
+    /// to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let first = Uuid::new_comb(1_600_000_000_000).unwrap();
+    /// let second = Uuid::new_comb(1_600_000_000_001).unwrap();
+    ///
+    /// assert!(first.as_bytes()[10..] < second.as_bytes()[10..]);
+    /// ```
+    ///
+    /// [`Builder::from_comb_bytes`]: struct.Builder.html#method.from_comb_bytes
+    /// [`getrandom`]: https://crates.io/crates/getrandom
+    /// [`Uuid::new_v4`]: struct.Uuid.html#method.new_v4
+    pub fn new_comb(unix_millis: u64) -> Result<Uuid, getrandom::Error> {
+        let mut bytes = [0u8; 16];
+        getrandom::getrandom(&mut bytes)?;
+
+        Ok(
+            crate::builder::Builder::from_comb_bytes(unix_millis, bytes)
+                .build(),
+        )
     }
 }
 
@@ -49,6 +83,20 @@ mod tests {
         assert_eq!(uuid.get_variant(), Some(Variant::RFC4122));
     }
 
+    #[test]
+    fn test_new_comb() {
+        let first = Uuid::new_comb(0x0123_4567_89ab).unwrap();
+        let second = Uuid::new_comb(0x0123_4567_89ac).unwrap();
+
+        assert_eq!(first.get_version(), Some(Version::Random));
+        assert_eq!(first.get_variant(), Some(Variant::RFC4122));
+        assert_eq!(
+            first.as_bytes()[10..],
+            [0x01, 0x23, 0x45, 0x67, 0x89, 0xab]
+        );
+        assert!(first.as_bytes()[10..] < second.as_bytes()[10..]);
+    }
+
     #[test]
     fn test_get_version() {
         let uuid = Uuid::new_v4().unwrap();
@@ -1,5 +1,5 @@
+use crate::namespace::{Namespace, V5Hasher};
 use crate::prelude::*;
-use sha1;
 
 impl Uuid {
     /// Creates a UUID using a name from a namespace, based on the SHA-1 hash.
@@ -11,6 +11,10 @@ impl Uuid {
     /// * [`NAMESPACE_URL`]
     /// * [`NAMESPACE_X500`]
     ///
+    /// To declare your own, or to keep namespaces apart from other UUIDs
+    /// in the type system, see [`Namespace`]. To declare a UUID derived
+    /// from a fixed name as a constant, see [`Uuid::new_v5_const`].
+    ///
     /// Note that usage of this method requires the `v5` feature of this crate
     /// to be enabled.
     ///
@@ -18,23 +22,184 @@ impl Uuid {
     /// [`NAMESPACE_OID`]: struct.Uuid.html#associatedconst.NAMESPACE_OID
     /// [`NAMESPACE_URL`]: struct.Uuid.html#associatedconst.NAMESPACE_URL
     /// [`NAMESPACE_X500`]: struct.Uuid.html#associatedconst.NAMESPACE_X500
+    /// [`Namespace`]: namespace/struct.Namespace.html
+    /// [`Uuid::new_v5_const`]: #method.new_v5_const
     pub fn new_v5(namespace: &Uuid, name: &[u8]) -> Uuid {
-        let mut hash = sha1::Sha1::new();
+        Uuid::new_v5_with(sha1::Sha1::new(), namespace, name)
+    }
 
-        hash.update(namespace.as_bytes());
-        hash.update(name);
+    /// Creates a UUID using a name from a namespace, based on the SHA-1
+    /// hash, in a `const fn`.
+    ///
+    /// This gives the same UUID as [`Uuid::new_v5`], so UUIDs derived from
+    /// fixed names can be declared as constants. It's slower, so prefer
+    /// [`Uuid::new_v5`] outside of constants.
+    ///
+    /// Note that usage of this method requires the `v5` feature of this crate
+    /// to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// const RUST_LANG: Uuid =
+    ///     Uuid::new_v5_const(&Uuid::NAMESPACE_DNS, b"rust-lang.org");
+    ///
+    /// assert_eq!(RUST_LANG.to_string(), "c66bbb60-d62e-5f17-a399-3a0bd237c503");
+    /// ```
+    ///
+    /// [`Uuid::new_v5`]: #method.new_v5
+    pub const fn new_v5_const(namespace: &Uuid, name: &[u8]) -> Uuid {
+        let hash = crate::const_hash::sha1(namespace.as_bytes(), name);
 
-        let buffer = hash.digest().bytes();
+        let mut bytes = [0; 16];
+        let mut i = 0;
+        while i < 16 {
+            bytes[i] = hash[i];
+            i += 1;
+        }
 
-        let mut bytes = crate::Bytes::default();
-        bytes.copy_from_slice(&buffer[..16]);
+        Builder::from_bytes(bytes)
+            .with_variant(Variant::RFC4122)
+            .with_version(Version::Sha1)
+            .into_uuid()
+    }
 
-        let mut builder = crate::builder::Builder::from_bytes(bytes);
-        builder
-            .set_variant(Variant::RFC4122)
-            .set_version(Version::Sha1);
+    /// Creates a UUID using a name made of several parts from a namespace,
+    /// based on the SHA-1 hash.
+    ///
+    /// Concatenating the parts into a single name is ambiguous, as
+    /// `("ab", "c")` and `("a", "bc")` both give `"abc"`. Instead, each part
+    /// is encoded as its length in bytes, as a big-endian `u64`, followed by
+    /// the part itself, and the encoded parts are concatenated into the
+    /// name for [`Uuid::new_v5`]. Other implementations can produce the same
+    /// UUIDs by following that encoding.
+    ///
+    /// Note that usage of this method requires the `v5` feature of this crate
+    /// to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let ns = Uuid::NAMESPACE_URL;
+    ///
+    /// assert_ne!(
+    ///     Uuid::new_v5_parts(&ns, vec![&b"ab"[..], b"c"]),
+    ///     Uuid::new_v5_parts(&ns, vec![&b"a"[..], b"bc"]),
+    /// );
+    ///
+    /// let name = [&[0, 0, 0, 0, 0, 0, 0, 1][..], b"a", &[0, 0, 0, 0, 0, 0, 0, 2], b"bc"].concat();
+    /// assert_eq!(
+    ///     Uuid::new_v5_parts(&ns, vec![&b"a"[..], b"bc"]),
+    ///     Uuid::new_v5(&ns, &name),
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::new_v5`]: #method.new_v5
+    pub fn new_v5_parts<'a>(
+        namespace: &Uuid,
+        parts: impl IntoIterator<Item = &'a [u8]>,
+    ) -> Uuid {
+        let mut hasher = V5Hasher::new(&Namespace::new(*namespace));
+
+        for part in parts {
+            hasher.update(&(part.len() as u64).to_be_bytes());
+            hasher.update(part);
+        }
+
+        hasher.finish()
+    }
 
-        builder.build()
+    /// Creates a UUID using a name from the namespace identified by this
+    /// UUID, based on the SHA-1 hash.
+    ///
+    /// This is the same as [`Uuid::new_v5`] with `self` as the namespace,
+    /// and is meant for hierarchies of name-based UUIDs, where each one is
+    /// the namespace of its children.
+    ///
+    /// Note that usage of this method requires the `v5` feature of this crate
+    /// to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let tenant = Uuid::NAMESPACE_DNS.derive_child(b"tenant");
+    ///
+    /// assert_eq!(tenant, Uuid::new_v5(&Uuid::NAMESPACE_DNS, b"tenant"));
+    /// ```
+    ///
+    /// [`Uuid::new_v5`]: #method.new_v5
+    pub fn derive_child(&self, name: &[u8]) -> Uuid {
+        Uuid::new_v5(self, name)
+    }
+
+    /// Creates a UUID by deriving a child for each name in `path` in turn,
+    /// starting from the namespace identified by this UUID.
+    ///
+    /// Each name is the name of a child of the UUID derived from the names
+    /// before it, so the order of the names matters. An empty path returns
+    /// `self`.
+    ///
+    /// Note that usage of this method requires the `v5` feature of this crate
+    /// to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::Uuid;
+    ///
+    /// let orders = Uuid::NAMESPACE_DNS.derive_path(&["tenant", "eu-west", "orders"]);
+    ///
+    /// assert_eq!(
+    ///     orders,
+    ///     Uuid::NAMESPACE_DNS
+    ///         .derive_child(b"tenant")
+    ///         .derive_child(b"eu-west")
+    ///         .derive_child(b"orders"),
+    /// );
+    /// assert_ne!(
+    ///     orders,
+    ///     Uuid::NAMESPACE_DNS.derive_path(&["eu-west", "tenant", "orders"]),
+    /// );
+    /// ```
+    pub fn derive_path<I>(&self, path: I) -> Uuid
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        path.into_iter()
+            .fold(*self, |parent, name| parent.derive_child(name.as_ref()))
+    }
+}
+
+impl Namespace {
+    /// Creates a UUID using a name from this namespace, based on the SHA-1
+    /// hash.
+    ///
+    /// This is the same as [`Uuid::new_v5`] with the namespace's UUID.
+    ///
+    /// Note that usage of this method requires the `v5` feature of this crate
+    /// to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{namespace::Namespace, Uuid};
+    ///
+    /// assert_eq!(
+    ///     Namespace::DNS.uuid_v5(b"rust-lang.org"),
+    ///     Uuid::new_v5(&Uuid::NAMESPACE_DNS, b"rust-lang.org"),
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::new_v5`]: ../struct.Uuid.html#method.new_v5
+    pub fn uuid_v5(&self, name: &[u8]) -> Uuid {
+        Uuid::new_v5(self.as_uuid(), name)
     }
 }
 
@@ -44,7 +209,7 @@ mod tests {
 
     use crate::std::string::ToString;
 
-    static FIXTURE: &'static [(&'static Uuid, &'static str, &'static str)] = &[
+    static FIXTURE: &[(&Uuid, &str, &str)] = &[
         (
             &Uuid::NAMESPACE_DNS,
             "example.org",
@@ -138,21 +303,91 @@ mod tests {
 
     #[test]
     fn test_hyphenated() {
-        for &(ref ns, ref name, ref expected) in FIXTURE {
-            let uuid = Uuid::new_v5(*ns, name.as_bytes());
+        for &(ns, name, expected) in FIXTURE {
+            let uuid = Uuid::new_v5(ns, name.as_bytes());
+
+            assert_eq!(uuid.to_hyphenated().to_string(), expected)
+        }
+    }
+
+    #[test]
+    fn test_new_v5_parts() {
+        let ns = Uuid::NAMESPACE_DNS;
+        let parts = |parts: &[&'static str]| {
+            Uuid::new_v5_parts(&ns, parts.iter().map(|part| part.as_bytes()))
+        };
+
+        assert_ne!(parts(&["ab", "c"]), parts(&["a", "bc"]));
+        assert_ne!(parts(&["abc"]), parts(&["abc", ""]));
+        assert_ne!(parts(&[]), parts(&[""]));
+        assert_eq!(parts(&[]), Uuid::new_v5(&ns, b""));
+        assert_eq!(
+            parts(&["abc"]),
+            Uuid::new_v5(&ns, b"\0\0\0\0\0\0\0\x03abc")
+        );
+
+        let uuid = parts(&["a", "bc"]);
+        assert_eq!(uuid.get_version(), Some(Version::Sha1));
+        assert_eq!(uuid.get_variant(), Some(Variant::RFC4122));
+    }
+
+    #[test]
+    fn test_derive() {
+        for &(ns, name, expected) in FIXTURE {
+            let uuid = ns.derive_child(name.as_bytes());
+
+            assert_eq!(uuid.to_hyphenated().to_string(), expected);
+            assert_eq!(ns.derive_path(&[name]), uuid);
+        }
+
+        let ns = Uuid::NAMESPACE_URL;
+        let path: &[&[u8]] = &[b"a", b"b", b"c"];
 
-            assert_eq!(uuid.to_hyphenated().to_string(), *expected)
+        assert_eq!(
+            ns.derive_path(path),
+            ns.derive_path(["a", "b", "c"].iter())
+        );
+        assert_eq!(
+            ns.derive_path(path),
+            Uuid::new_v5(&Uuid::new_v5(&Uuid::new_v5(&ns, b"a"), b"b"), b"c")
+        );
+        assert_eq!(ns.derive_path(&[] as &[&str]), ns);
+    }
+
+    #[test]
+    fn test_namespace() {
+        for &(ns, name, expected) in FIXTURE {
+            let uuid = Namespace::new(*ns).uuid_v5(name.as_bytes());
+
+            assert_eq!(uuid.to_hyphenated().to_string(), expected)
         }
     }
 
     #[test]
     fn test_new() {
-        for &(ref ns, ref name, ref u) in FIXTURE {
-            let uuid = Uuid::new_v5(*ns, name.as_bytes());
+        for &(ns, name, u) in FIXTURE {
+            let uuid = Uuid::new_v5(ns, name.as_bytes());
 
             assert_eq!(uuid.get_variant(), Some(Variant::RFC4122));
             assert_eq!(uuid.get_version(), Some(Version::Sha1));
             assert_eq!(Ok(uuid), u.parse());
         }
     }
+
+    #[test]
+    fn test_const() {
+        const UUID: Uuid = Uuid::new_v5_const(&Uuid::NAMESPACE_OID, b"42");
+
+        assert_eq!(
+            UUID.to_hyphenated().to_string(),
+            "ba293c61-ad33-57b9-9671-f3319f57d789"
+        );
+
+        for &(ns, name, _) in FIXTURE {
+            assert_eq!(
+                Uuid::new_v5_const(ns, name.as_bytes()),
+                Uuid::new_v5(ns, name.as_bytes())
+            );
+        }
+    }
 }
@@ -0,0 +1,153 @@
+// Copyright 2013-2014 The Rust Project Developers.
+// Copyright 2018 The Uuid Project Developers.
+//
+// See the COPYRIGHT file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(feature = "v8-sha256")]
+use crate::namespace::Namespace;
+use crate::{packed, prelude::*};
+#[cfg(feature = "v8-sha256")]
+use sha2::{Digest, Sha256};
+
+/// The number of bits of a custom (version 8) UUID that aren't taken by
+/// the version and variant.
+pub(crate) const V8_BITS: u32 = 122;
+
+impl Uuid {
+    /// Creates a custom (version 8) UUID from the lower 122 bits of
+    /// `bits`, filling in the version and variant around them.
+    ///
+    /// The bits are stored most significant first, so UUIDs created from
+    /// larger values compare greater.
+    pub(crate) const fn from_v8_bits(bits: u128) -> Uuid {
+        Uuid::from_u128(packed::unpack(bits) | 0x8 << 76 | 0b10 << 62)
+    }
+
+    /// Returns the 122 bits stored by [`Uuid::from_v8_bits`], if this is a
+    /// custom (version 8) UUID.
+    ///
+    /// [`Uuid::from_v8_bits`]: #method.from_v8_bits
+    pub(crate) fn v8_bits(&self) -> Option<u128> {
+        if self.get_version() != Some(Version::Custom)
+            || self.get_variant() != Some(Variant::RFC4122)
+        {
+            return None;
+        }
+
+        Some(packed::pack(self.as_u128()))
+    }
+
+    /// Creates a custom (version 8) UUID using a name from a namespace,
+    /// based on the SHA-256 hash.
+    ///
+    /// This works like [`Uuid::new_v5`], but with SHA-256 instead of
+    /// SHA-1, as in the name-based example of [RFC 9562, appendix B.2]:
+    /// the first 16 bytes of the hash of the namespace followed by the
+    /// name, with the version and variant set. It uses the same namespaces
+    /// as versions 3 and 5, see [`Namespace`].
+    ///
+    /// Note that usage of this method requires the `v8-sha256` feature of
+    /// this crate to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uuid::{Uuid, Version};
+    ///
+    /// let uuid = Uuid::new_v8_sha256(&Uuid::NAMESPACE_DNS, b"www.example.com");
+    ///
+    /// assert_eq!(uuid.get_version(), Some(Version::Custom));
+    /// assert_eq!(
+    ///     uuid.to_hyphenated().to_string(),
+    ///     "5c146b14-3c52-8afd-938a-375d0df1fbf6"
+    /// );
+    /// ```
+    ///
+    /// [`Uuid::new_v5`]: #method.new_v5
+    /// [`Namespace`]: namespace/struct.Namespace.html
+    /// [RFC 9562, appendix B.2]: https://www.rfc-editor.org/rfc/rfc9562#appendix-B.2
+    #[cfg(feature = "v8-sha256")]
+    pub fn new_v8_sha256(namespace: &Uuid, name: &[u8]) -> Uuid {
+        let mut hash = Sha256::new();
+
+        hash.update(namespace.as_bytes());
+        hash.update(name);
+
+        let mut bytes = crate::Bytes::default();
+        bytes.copy_from_slice(&hash.finalize()[..16]);
+
+        let mut builder = crate::builder::Builder::from_bytes(bytes);
+        builder
+            .set_variant(Variant::RFC4122)
+            .set_version(Version::Custom);
+
+        builder.build()
+    }
+}
+
+#[cfg(feature = "v8-sha256")]
+impl Namespace {
+    /// Creates a custom (version 8) UUID using a name from this namespace,
+    /// based on the SHA-256 hash.
+    ///
+    /// This is the same as [`Uuid::new_v8_sha256`] with the namespace's
+    /// UUID.
+    ///
+    /// Note that usage of this method requires the `v8-sha256` feature of
+    /// this crate to be enabled.
+    ///
+    /// [`Uuid::new_v8_sha256`]: ../struct.Uuid.html#method.new_v8_sha256
+    pub fn uuid_v8_sha256(&self, name: &[u8]) -> Uuid {
+        Uuid::new_v8_sha256(self.as_uuid(), name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn test_v8_bits() {
+        for &bits in &[0, 1, (1 << V8_BITS) - 1, 0x1234_5678_9abc_def0] {
+            let uuid = Uuid::from_v8_bits(bits);
+
+            assert_eq!(uuid.get_version(), Some(Version::Custom));
+            assert_eq!(uuid.get_variant(), Some(Variant::RFC4122));
+            assert_eq!(uuid.v8_bits(), Some(bits));
+        }
+
+        assert!(Uuid::from_v8_bits(1) < Uuid::from_v8_bits(2));
+        assert!(Uuid::from_v8_bits(1 << 62) < Uuid::from_v8_bits(1 << 74));
+        assert_eq!(test_util::new().v8_bits(), None);
+    }
+
+    #[cfg(feature = "v8-sha256")]
+    #[test]
+    fn test_new_v8_sha256() {
+        for &(ns, name, expected) in &[
+            (
+                Namespace::DNS,
+                "www.example.com",
+                "5c146b14-3c52-8afd-938a-375d0df1fbf6",
+            ),
+            (
+                Namespace::DNS,
+                "rust-lang.org",
+                "4da01707-1365-8fb2-8751-c9b55bf38ba8",
+            ),
+        ] {
+            let uuid = ns.uuid_v8_sha256(name.as_bytes());
+
+            assert_eq!(uuid.get_version(), Some(Version::Custom));
+            assert_eq!(uuid.get_variant(), Some(Variant::RFC4122));
+            assert_eq!(Ok(uuid), expected.parse());
+        }
+    }
+}
@@ -3,11 +3,16 @@ use winapi::shared::guiddef;
 
 #[cfg(feature = "guid")]
 impl Uuid {
-    /// Converts a little endian winapi `GUID` into a [`Uuid`]
+    /// Converts a winapi `GUID` into a [`Uuid`]
+    ///
+    /// The integer fields of a `GUID` hold the first three groups of the
+    /// UUID's textual form, so `Data1` of `0x4a35229d` corresponds to a
+    /// [`Uuid`] starting with `4a35229d`, regardless of the native byte
+    /// order the `GUID` is stored in.
     ///
     /// [`Uuid`]: ../struct.Uuid.html
     pub fn from_guid(guid: guiddef::GUID) -> Self {
-        Uuid::from_fields_le(
+        Uuid::from_fields(
             guid.Data1 as u32,
             guid.Data2 as u16,
             guid.Data3 as u16,
@@ -17,11 +22,14 @@ impl Uuid {
                   //       safely unwrap.
     }
 
-    /// Converts a [`Uuid`] into a little endian winapi `GUID`
+    /// Converts a [`Uuid`] into a winapi `GUID`
+    ///
+    /// This is the inverse of [`Uuid::from_guid`].
     ///
     /// [`Uuid`]: ../struct.Uuid.html
+    /// [`Uuid::from_guid`]: ../struct.Uuid.html#method.from_guid
     pub fn to_guid(&self) -> guiddef::GUID {
-        let (data1, data2, data3, data4) = self.to_fields_le();
+        let (data1, data2, data3, data4) = self.as_fields();
 
         guiddef::GUID {
             Data1: data1,
@@ -32,6 +40,20 @@ impl Uuid {
     }
 }
 
+#[cfg(feature = "guid")]
+impl From<guiddef::GUID> for Uuid {
+    fn from(guid: guiddef::GUID) -> Self {
+        Uuid::from_guid(guid)
+    }
+}
+
+#[cfg(feature = "guid")]
+impl From<Uuid> for guiddef::GUID {
+    fn from(uuid: Uuid) -> Self {
+        uuid.to_guid()
+    }
+}
+
 #[cfg(feature = "guid")]
 #[cfg(test)]
 mod tests {
@@ -51,9 +73,10 @@ mod tests {
 
         let uuid = Uuid::from_guid(guid);
         assert_eq!(
-            "9d22354a-2755-304f-8647-9dc54e1ee1e8",
+            "4a35229d-5527-4f30-8647-9dc54e1ee1e8",
             uuid.to_hyphenated().to_string()
         );
+        assert_eq!(Uuid::from(guid), uuid);
     }
 
     #[test]
@@ -77,5 +100,17 @@ mod tests {
                 guid_out.Data4
             )
         );
+
+        let guid_out = guiddef::GUID::from(uuid);
+
+        assert_eq!(
+            (guid_in.Data1, guid_in.Data2, guid_in.Data3, guid_in.Data4),
+            (
+                guid_out.Data1,
+                guid_out.Data2,
+                guid_out.Data3,
+                guid_out.Data4
+            )
+        );
     }
 }